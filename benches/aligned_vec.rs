@@ -0,0 +1,71 @@
+#![cfg(feature = "aligned_vec")]
+#![feature(test)]
+
+extern crate test;
+
+use mem_allocs::aligned_vec::AlignedVec;
+use test::{black_box, Bencher};
+
+const LEN: usize = 4096;
+
+/// Number of `f32` lanes in a 256-bit vector register.
+const LANES: usize = 8;
+
+/// Computes a dot product in lane-sized chunks so the compiler can vectorise the inner loop.
+fn dot(left: &[f32], right: &[f32]) -> f32 {
+    let mut sums = [0.0_f32; LANES];
+    for (left, right) in left.chunks_exact(LANES).zip(right.chunks_exact(LANES)) {
+        for lane in 0..LANES {
+            sums[lane] = left[lane].mul_add(right[lane], sums[lane]);
+        }
+    }
+    sums.iter().sum()
+}
+
+/// Returns `LEN` values with a simple repeating pattern.
+fn values() -> impl Iterator<Item = f32> {
+    (0..17_u8)
+        .cycle()
+        .take(LEN)
+        .map(|value| f32::from(value) * 0.25)
+}
+
+#[bench]
+fn aligned_vec_dot(bencher: &mut Bencher) {
+    let mut left = AlignedVec::<f32, 32>::with_capacity(LEN);
+    let mut right = AlignedVec::<f32, 32>::with_capacity(LEN);
+    for value in values() {
+        left.push(value);
+        right.push(value + 1.0);
+    }
+    bencher.iter(|| dot(black_box(&left), black_box(&right)));
+}
+
+#[bench]
+fn vec_dot(bencher: &mut Bencher) {
+    let left: Vec<f32> = values().collect();
+    let right: Vec<f32> = values().map(|value| value + 1.0).collect();
+    bencher.iter(|| dot(black_box(&left), black_box(&right)));
+}
+
+#[bench]
+fn aligned_vec_push(bencher: &mut Bencher) {
+    bencher.iter(|| {
+        let mut values = AlignedVec::<f32, 32>::new();
+        for value in self::values() {
+            values.push(value);
+        }
+        black_box(values)
+    });
+}
+
+#[bench]
+fn vec_push(bencher: &mut Bencher) {
+    bencher.iter(|| {
+        let mut values = Vec::new();
+        for value in self::values() {
+            values.push(value);
+        }
+        black_box(values)
+    });
+}
@@ -0,0 +1,70 @@
+#![cfg(all(feature = "bitmap_allocator", feature = "slab_allocator"))]
+#![feature(allocator_api, test)]
+
+extern crate test;
+
+use std::alloc::{Allocator, Layout};
+
+use mem_allocs::{bitmap_allocator::BitmapAllocator, slab_allocator::SlabAllocator};
+use test::{black_box, Bencher};
+
+const BLOCK_SIZE: usize = 32;
+
+/// Fills a bitmap allocator with `count` blocks and frees them all again.
+fn bitmap_fill_and_free(bencher: &mut Bencher, count: usize) {
+    let allocator = BitmapAllocator::new(BLOCK_SIZE, count);
+    let layout = Layout::from_size_align(BLOCK_SIZE, 8).unwrap();
+
+    bencher.iter(|| {
+        let blocks: Vec<_> = (0..count)
+            .map(|_| allocator.allocate(layout).unwrap())
+            .collect();
+        for block in blocks {
+            unsafe { allocator.deallocate(black_box(block).cast(), layout) };
+        }
+    });
+}
+
+/// Fills a free-list pool with `count` blocks and frees them all again.
+fn free_list_fill_and_free(bencher: &mut Bencher, count: usize) {
+    let allocator = SlabAllocator::<[u8; BLOCK_SIZE]>::new(count);
+
+    bencher.iter(|| {
+        let blocks: Vec<_> = (0..count)
+            .map(|_| allocator.allocate_one().unwrap())
+            .collect();
+        for block in blocks {
+            unsafe { allocator.deallocate_one(black_box(block)) };
+        }
+    });
+}
+
+#[bench]
+fn bitmap_64_blocks(bencher: &mut Bencher) {
+    bitmap_fill_and_free(bencher, 64);
+}
+
+#[bench]
+fn free_list_64_blocks(bencher: &mut Bencher) {
+    free_list_fill_and_free(bencher, 64);
+}
+
+#[bench]
+fn bitmap_1024_blocks(bencher: &mut Bencher) {
+    bitmap_fill_and_free(bencher, 1024);
+}
+
+#[bench]
+fn free_list_1024_blocks(bencher: &mut Bencher) {
+    free_list_fill_and_free(bencher, 1024);
+}
+
+#[bench]
+fn bitmap_16384_blocks(bencher: &mut Bencher) {
+    bitmap_fill_and_free(bencher, 16384);
+}
+
+#[bench]
+fn free_list_16384_blocks(bencher: &mut Bencher) {
+    free_list_fill_and_free(bencher, 16384);
+}
@@ -0,0 +1,126 @@
+#![feature(allocator_api)]
+
+use std::{
+    alloc::{Allocator, Layout},
+    hint::black_box,
+};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use mem_allocs::arena_allocator::ArenaAllocator;
+
+/// Numbers of blocks allocated per iteration.
+const COUNTS: [usize; 3] = [64, 256, 1024];
+
+/// Size of every block in the `multi_*` scenarios.
+const BLOCK_SIZE: usize = 64;
+
+/// Returns `count` layouts of pseudo-random sizes between 1 and 256 bytes.
+fn random_layouts(count: usize) -> Vec<Layout> {
+    let mut state: u32 = 0x9E37_79B9;
+    (0..count)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            Layout::from_size_align(state as usize % 256 + 1, 8).unwrap()
+        })
+        .collect()
+}
+
+fn multi_alloc(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("multi_alloc");
+    for count in COUNTS {
+        let mut arena = ArenaAllocator::new(count * BLOCK_SIZE);
+        let layout = Layout::from_size_align(BLOCK_SIZE, 8).unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("arena", count),
+            &count,
+            |bencher, &count| {
+                bencher.iter(|| {
+                    for _ in 0..count {
+                        black_box(arena.allocate(layout).unwrap());
+                    }
+                    arena.reset();
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+fn multi_free(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("multi_free");
+    for count in COUNTS {
+        let mut arena = ArenaAllocator::new(count * BLOCK_SIZE);
+        let layout = Layout::from_size_align(BLOCK_SIZE, 8).unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("arena", count),
+            &count,
+            |bencher, &count| {
+                bencher.iter(|| {
+                    let blocks: Vec<_> = (0..count)
+                        .map(|_| arena.allocate(layout).unwrap())
+                        .collect();
+                    for block in blocks {
+                        unsafe { arena.deallocate(black_box(block).cast(), layout) };
+                    }
+                    arena.reset();
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+fn random_alloc(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("random_alloc");
+    for count in COUNTS {
+        let layouts = random_layouts(count);
+        let mut arena = ArenaAllocator::new(count * 264);
+        group.bench_with_input(
+            BenchmarkId::new("arena", count),
+            &layouts,
+            |bencher, layouts| {
+                bencher.iter(|| {
+                    for &layout in layouts {
+                        black_box(arena.allocate(layout).unwrap());
+                    }
+                    arena.reset();
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+fn random_free(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("random_free");
+    for count in COUNTS {
+        let layouts = random_layouts(count);
+        let mut arena = ArenaAllocator::new(count * 264);
+        group.bench_with_input(
+            BenchmarkId::new("arena", count),
+            &layouts,
+            |bencher, layouts| {
+                bencher.iter(|| {
+                    let blocks: Vec<_> = layouts
+                        .iter()
+                        .map(|&layout| arena.allocate(layout).unwrap())
+                        .collect();
+                    // Free every other block first, then the rest, to mix up the order.
+                    for (block, &layout) in blocks.iter().zip(layouts).step_by(2) {
+                        unsafe { arena.deallocate(black_box(*block).cast(), layout) };
+                    }
+                    for (block, &layout) in blocks.iter().zip(layouts).skip(1).step_by(2) {
+                        unsafe { arena.deallocate(black_box(*block).cast(), layout) };
+                    }
+                    arena.reset();
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, multi_alloc, multi_free, random_alloc, random_free);
+criterion_main!(benches);
@@ -0,0 +1,171 @@
+#![feature(allocator_api)]
+
+use std::{
+    alloc::{Allocator, Layout},
+    hint::black_box,
+    ptr::{self, NonNull},
+};
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use mem_allocs::c_allocator::CAllocator;
+
+/// Numbers of blocks allocated per iteration.
+const COUNTS: [usize; 3] = [64, 256, 1024];
+
+/// Size of every block in the `multi_*` scenarios.
+const BLOCK_SIZE: usize = 64;
+
+/// Block sizes compared in the `zeroed` scenario.
+const ZEROED_SIZES: [usize; 3] = [64, 4096, 1 << 20];
+
+/// Returns `count` layouts of pseudo-random sizes between 1 and 256 bytes.
+fn random_layouts(count: usize) -> Vec<Layout> {
+    let mut state: u32 = 0x9E37_79B9;
+    (0..count)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            Layout::from_size_align(state as usize % 256 + 1, 8).unwrap()
+        })
+        .collect()
+}
+
+/// Blocks allocated during one iteration, freed when dropped outside the measurement.
+struct Blocks(Vec<(NonNull<[u8]>, Layout)>);
+
+impl Drop for Blocks {
+    fn drop(&mut self) {
+        for (block, layout) in self.0.drain(..) {
+            unsafe { CAllocator.deallocate(block.cast(), layout) };
+        }
+    }
+}
+
+fn multi_alloc(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("multi_alloc");
+    let layout = Layout::from_size_align(BLOCK_SIZE, 8).unwrap();
+    for count in COUNTS {
+        group.bench_with_input(BenchmarkId::new("c", count), &count, |bencher, &count| {
+            bencher.iter_batched_ref(
+                || Blocks(Vec::with_capacity(count)),
+                |blocks| {
+                    for _ in 0..count {
+                        let block = black_box(CAllocator.allocate(layout).unwrap());
+                        blocks.0.push((block, layout));
+                    }
+                },
+                BatchSize::SmallInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+fn multi_free(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("multi_free");
+    let layout = Layout::from_size_align(BLOCK_SIZE, 8).unwrap();
+    for count in COUNTS {
+        group.bench_with_input(BenchmarkId::new("c", count), &count, |bencher, &count| {
+            bencher.iter(|| {
+                let blocks: Vec<_> = (0..count)
+                    .map(|_| CAllocator.allocate(layout).unwrap())
+                    .collect();
+                for block in blocks {
+                    unsafe { CAllocator.deallocate(black_box(block).cast(), layout) };
+                }
+            });
+        });
+    }
+    group.finish();
+}
+
+fn random_alloc(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("random_alloc");
+    for count in COUNTS {
+        let layouts = random_layouts(count);
+        group.bench_with_input(
+            BenchmarkId::new("c", count),
+            &layouts,
+            |bencher, layouts| {
+                bencher.iter_batched_ref(
+                    || Blocks(Vec::with_capacity(layouts.len())),
+                    |blocks| {
+                        for &layout in layouts {
+                            let block = black_box(CAllocator.allocate(layout).unwrap());
+                            blocks.0.push((block, layout));
+                        }
+                    },
+                    BatchSize::SmallInput,
+                );
+            },
+        );
+    }
+    group.finish();
+}
+
+fn random_free(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("random_free");
+    for count in COUNTS {
+        let layouts = random_layouts(count);
+        group.bench_with_input(
+            BenchmarkId::new("c", count),
+            &layouts,
+            |bencher, layouts| {
+                bencher.iter(|| {
+                    let blocks: Vec<_> = layouts
+                        .iter()
+                        .map(|&layout| CAllocator.allocate(layout).unwrap())
+                        .collect();
+                    // Free every other block first, then the rest, to mix up the order.
+                    for (block, &layout) in blocks.iter().zip(layouts).step_by(2) {
+                        unsafe { CAllocator.deallocate(black_box(*block).cast(), layout) };
+                    }
+                    for (block, &layout) in blocks.iter().zip(layouts).skip(1).step_by(2) {
+                        unsafe { CAllocator.deallocate(black_box(*block).cast(), layout) };
+                    }
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+fn zeroed(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("zeroed");
+    for size in ZEROED_SIZES {
+        let layout = Layout::from_size_align(size, 8).unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("calloc", size),
+            &layout,
+            |bencher, &layout| {
+                bencher.iter(|| {
+                    let block = black_box(CAllocator.allocate_zeroed(layout).unwrap());
+                    unsafe { CAllocator.deallocate(block.cast(), layout) };
+                });
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("alloc_zero", size),
+            &layout,
+            |bencher, &layout| {
+                bencher.iter(|| {
+                    let block = CAllocator.allocate(layout).unwrap();
+                    unsafe { ptr::write_bytes(block.cast::<u8>().as_ptr(), 0, layout.size()) };
+                    unsafe { CAllocator.deallocate(black_box(block).cast(), layout) };
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    multi_alloc,
+    multi_free,
+    random_alloc,
+    random_free,
+    zeroed
+);
+criterion_main!(benches);
@@ -0,0 +1,66 @@
+#![cfg(feature = "free_list_allocator")]
+#![feature(allocator_api, slice_ptr_get, test)]
+
+extern crate test;
+
+use std::{
+    alloc::{Allocator, Layout},
+    ptr::NonNull,
+};
+
+use mem_allocs::free_list_allocator::{FreeListAllocator, SearchStrategy};
+use test::{black_box, Bencher};
+
+/// Number of small/large pairs allocated per iteration.
+const PAIRS: usize = 256;
+
+/// Bytes managed by each allocator; room for every pair plus the requests made after the frees.
+const CAPACITY: usize = 256 * 1024;
+
+/// Alternates small and large allocations, frees every large block to leave the buffer riddled
+/// with holes, then serves a round of medium requests out of the fragmented free list.
+fn fragmentation(bencher: &mut Bencher, strategy: SearchStrategy) {
+    let small = Layout::from_size_align(16, 8).unwrap();
+    let large = Layout::from_size_align(256, 8).unwrap();
+    let medium = Layout::from_size_align(96, 8).unwrap();
+
+    bencher.iter(|| {
+        let allocator = FreeListAllocator::with_strategy(CAPACITY, strategy);
+        let mut small_blocks: Vec<NonNull<u8>> = Vec::with_capacity(PAIRS);
+        let mut large_blocks: Vec<NonNull<u8>> = Vec::with_capacity(PAIRS);
+        for _ in 0..PAIRS {
+            small_blocks.push(allocator.allocate(small).unwrap().as_non_null_ptr());
+            large_blocks.push(allocator.allocate(large).unwrap().as_non_null_ptr());
+        }
+        for block in large_blocks {
+            unsafe { allocator.deallocate(block, large) };
+        }
+
+        let medium_blocks: Vec<NonNull<u8>> = (0..PAIRS)
+            .map(|_| allocator.allocate(medium).unwrap().as_non_null_ptr())
+            .collect();
+        black_box(allocator.free_block_count());
+
+        for block in medium_blocks {
+            unsafe { allocator.deallocate(block, medium) };
+        }
+        for block in small_blocks {
+            unsafe { allocator.deallocate(block, small) };
+        }
+    });
+}
+
+#[bench]
+fn first_fit_fragmentation(bencher: &mut Bencher) {
+    fragmentation(bencher, SearchStrategy::FirstFit);
+}
+
+#[bench]
+fn best_fit_fragmentation(bencher: &mut Bencher) {
+    fragmentation(bencher, SearchStrategy::BestFit);
+}
+
+#[bench]
+fn next_fit_fragmentation(bencher: &mut Bencher) {
+    fragmentation(bencher, SearchStrategy::NextFit);
+}
@@ -0,0 +1,55 @@
+#![cfg(all(
+    feature = "mimalloc",
+    feature = "c_allocator",
+    feature = "arena_allocator"
+))]
+#![feature(allocator_api, test)]
+
+extern crate test;
+
+use std::alloc::{Allocator, Layout};
+
+use mem_allocs::{
+    arena_allocator::ArenaAllocator, c_allocator::CAllocator, mimalloc_allocator::MiMallocAllocator,
+};
+use test::{black_box, Bencher};
+
+const COUNT: usize = 1024;
+const SIZE: usize = 64;
+
+/// Allocates `COUNT` blocks of `SIZE` bytes from `allocator` and frees them all again.
+fn allocate_and_free<A: Allocator>(bencher: &mut Bencher, allocator: &A) {
+    let layout = Layout::from_size_align(SIZE, 8).unwrap();
+
+    bencher.iter(|| {
+        let blocks: Vec<_> = (0..COUNT)
+            .map(|_| allocator.allocate(layout).unwrap())
+            .collect();
+        for block in blocks {
+            unsafe { allocator.deallocate(black_box(block).cast(), layout) };
+        }
+    });
+}
+
+#[bench]
+fn mimalloc_allocate_and_free(bencher: &mut Bencher) {
+    allocate_and_free(bencher, &MiMallocAllocator);
+}
+
+#[bench]
+fn c_allocate_and_free(bencher: &mut Bencher) {
+    allocate_and_free(bencher, &CAllocator);
+}
+
+#[bench]
+fn arena_allocate_and_reset(bencher: &mut Bencher) {
+    let mut arena = ArenaAllocator::new(COUNT * SIZE);
+    let layout = Layout::from_size_align(SIZE, 8).unwrap();
+
+    bencher.iter(|| {
+        for _ in 0..COUNT {
+            black_box(arena.allocate(layout).unwrap());
+        }
+        arena.reset();
+    });
+}
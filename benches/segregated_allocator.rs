@@ -0,0 +1,38 @@
+#![cfg(all(feature = "segregated_allocator", feature = "c_allocator"))]
+#![feature(allocator_api, test)]
+
+extern crate test;
+
+use std::alloc::{Allocator, Layout};
+
+use mem_allocs::{c_allocator::CAllocator, segregated_allocator::SegregatedAllocator};
+use test::{black_box, Bencher};
+
+const COUNT: usize = 1024;
+
+/// Allocates `COUNT` small objects of mixed sizes from `allocator` and frees them all again.
+fn small_objects<A: Allocator>(bencher: &mut Bencher, allocator: &A) {
+    let layouts: Vec<Layout> = (0..COUNT)
+        .map(|index| Layout::from_size_align(8 << (index % 7), 8).unwrap())
+        .collect();
+
+    bencher.iter(|| {
+        let blocks: Vec<_> = layouts
+            .iter()
+            .map(|&layout| allocator.allocate(layout).unwrap())
+            .collect();
+        for (block, &layout) in blocks.into_iter().zip(&layouts) {
+            unsafe { allocator.deallocate(black_box(block).cast(), layout) };
+        }
+    });
+}
+
+#[bench]
+fn segregated_small_objects(bencher: &mut Bencher) {
+    small_objects(bencher, &SegregatedAllocator::new(COUNT));
+}
+
+#[bench]
+fn c_small_objects(bencher: &mut Bencher) {
+    small_objects(bencher, &CAllocator);
+}
@@ -0,0 +1,94 @@
+//! Replays raw fuzzer input as a sequence of arena operations and checks every allocation
+//! against a shadow list of live ranges.
+//!
+//! Each operation starts with an opcode byte. `Allocate` and `AllocateZeroed` are followed by a
+//! little-endian `u16` size and a byte whose low four bits give the base-2 logarithm of the
+//! alignment. Input that ends in the middle of an operation is ignored.
+
+#![no_main]
+#![feature(allocator_api, slice_ptr_get)]
+
+use core::alloc::{Allocator, Layout};
+
+use libfuzzer_sys::fuzz_target;
+use mem_allocs::arena_allocator::ArenaAllocator;
+
+const CAPACITY: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy)]
+enum Operation {
+    Allocate(Layout),
+    AllocateZeroed(Layout),
+    Reset,
+}
+
+/// Decodes the next operation from the front of `data`.
+fn next_operation(data: &mut &[u8]) -> Option<Operation> {
+    let (&opcode, rest) = data.split_first()?;
+    *data = rest;
+
+    if opcode % 3 == 2 {
+        return Some(Operation::Reset);
+    }
+
+    let (&[low, high, align_log2], rest) = data.split_first_chunk()?;
+    *data = rest;
+    let size = usize::from(u16::from_le_bytes([low, high]));
+    let layout = Layout::from_size_align(size, 1 << (align_log2 & 0x0F)).ok()?;
+
+    Some(if opcode % 3 == 0 {
+        Operation::Allocate(layout)
+    } else {
+        Operation::AllocateZeroed(layout)
+    })
+}
+
+/// Runs the operations encoded in `data` against a fresh arena.
+fn run(mut data: &[u8]) {
+    let arena = ArenaAllocator::new(CAPACITY);
+    // A zero-sized allocation from an empty arena points at the start of its buffer.
+    let base = arena.allocate(Layout::new::<()>()).unwrap().as_mut_ptr() as usize;
+    let mut live: Vec<(usize, usize)> = Vec::new();
+
+    while let Some(operation) = next_operation(&mut data) {
+        let (layout, result) = match operation {
+            Operation::Reset => {
+                arena.reset();
+                live.clear();
+                continue;
+            }
+            Operation::Allocate(layout) => (layout, arena.allocate(layout)),
+            Operation::AllocateZeroed(layout) => (layout, arena.allocate_zeroed(layout)),
+        };
+        let Ok(block) = result else {
+            continue;
+        };
+
+        let start = block.as_mut_ptr() as usize;
+        let end = start + layout.size();
+        assert_eq!(block.len(), layout.size());
+        assert_eq!(start % layout.align(), 0, "misaligned block for {layout:?}");
+        assert!(
+            base <= start && end <= base + CAPACITY,
+            "block outside the arena"
+        );
+        for &(live_start, live_end) in &live {
+            assert!(
+                end <= live_start || live_end <= start,
+                "block {start:#x}..{end:#x} overlaps {live_start:#x}..{live_end:#x}"
+            );
+        }
+
+        if let Operation::AllocateZeroed(_) = operation {
+            let bytes = unsafe { core::slice::from_raw_parts(block.as_mut_ptr(), layout.size()) };
+            assert!(bytes.iter().all(|&byte| byte == 0), "zeroed block is dirty");
+        }
+        unsafe { core::ptr::write_bytes(block.as_mut_ptr(), 0xAA, layout.size()) };
+
+        if start < end {
+            live.push((start, end));
+        }
+    }
+}
+
+fuzz_target!(|data: &[u8]| run(data));
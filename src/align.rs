@@ -0,0 +1,23 @@
+//! Address arithmetic shared by the bump-pointer allocators.
+
+/// Rounds `value` up to the next multiple of `align`, which must be a power of two.
+///
+/// Returns `None` if the result does not fit in a `usize`.
+pub const fn align_up(value: usize, align: usize) -> Option<usize> {
+    value.checked_next_multiple_of(align)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Tests that `align_up` rounds up to the alignment and reports overflow.
+    fn test_align_up() {
+        assert_eq!(align_up(0, 8), Some(0));
+        assert_eq!(align_up(1, 8), Some(8));
+        assert_eq!(align_up(16, 8), Some(16));
+        assert_eq!(align_up(usize::MAX - 2, 8), None);
+        assert_eq!(align_up(usize::MAX - 7, 8), Some(usize::MAX - 7));
+    }
+}
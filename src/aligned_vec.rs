@@ -0,0 +1,329 @@
+use core::{
+    alloc::{Allocator, Layout},
+    fmt,
+    marker::PhantomData,
+    mem,
+    num::NonZero,
+    ops::{Deref, DerefMut},
+    ptr::{self, NonNull},
+    slice,
+};
+
+use alloc::alloc::handle_alloc_error;
+
+use crate::c_allocator::CAllocator;
+
+/// A growable array like `Vec<T>` whose buffer always starts at a multiple of `ALIGN` bytes.
+///
+/// The buffer comes from [`CAllocator`] with `ALIGN` as the layout's alignment, which makes the
+/// first element suitable for aligned SIMD loads and stores.
+///
+/// ```
+/// use mem_allocs::aligned_vec::AlignedVec;
+///
+/// let mut values = AlignedVec::<f32, 32>::new();
+/// values.extend_from_slice(&[1.0, 2.0, 3.0]);
+/// assert_eq!(values.as_ptr() as usize % 32, 0);
+/// assert_eq!(values.iter().sum::<f32>(), 6.0);
+/// ```
+pub struct AlignedVec<T, const ALIGN: usize> {
+    ptr: NonNull<T>,
+    len: usize,
+    capacity: usize,
+    _marker: PhantomData<T>,
+}
+
+// SAFETY: the vector owns its elements exclusively, like `Vec<T>`, and `CAllocator` can be used
+// from any thread.
+unsafe impl<T: Send, const ALIGN: usize> Send for AlignedVec<T, ALIGN> {}
+unsafe impl<T: Sync, const ALIGN: usize> Sync for AlignedVec<T, ALIGN> {}
+
+impl<T, const ALIGN: usize> AlignedVec<T, ALIGN> {
+    /// Creates an empty vector without allocating.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ALIGN` is not a power of two or is smaller than the alignment of `T`.
+    #[must_use]
+    pub const fn new() -> Self {
+        assert!(ALIGN.is_power_of_two(), "ALIGN must be a power of two");
+        assert!(
+            ALIGN >= mem::align_of::<T>(),
+            "ALIGN must be at least the alignment of T"
+        );
+
+        Self {
+            ptr: NonNull::without_provenance(NonZero::new(ALIGN).unwrap()),
+            len: 0,
+            capacity: if mem::size_of::<T>() == 0 {
+                usize::MAX
+            } else {
+                0
+            },
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates an empty vector with room for at least `capacity` elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics like [`new`](Self::new), panics if the buffer size overflows `isize`, and calls
+    /// [`handle_alloc_error`] if the allocation fails.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut vec = Self::new();
+        vec.reserve(capacity);
+        vec
+    }
+
+    /// Returns the number of elements in the vector.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the vector holds no elements.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements the vector can hold without reallocating.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the elements as a slice.
+    #[must_use]
+    pub const fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Returns the elements as a mutable slice.
+    #[must_use]
+    pub const fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Makes room for at least `additional` more elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer size overflows `isize` and calls [`handle_alloc_error`] if the
+    /// allocation fails.
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self.len.checked_add(additional).expect("capacity overflow");
+        if required > self.capacity {
+            self.grow_to(required.max(self.capacity * 2).max(4));
+        }
+    }
+
+    /// Appends `value` to the end of the vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics like [`reserve`](Self::reserve) if the vector has to grow.
+    pub fn push(&mut self, value: T) {
+        if self.len == self.capacity {
+            self.reserve(1);
+        }
+        unsafe { self.ptr.add(self.len).write(value) };
+        self.len += 1;
+    }
+
+    /// Removes the last element and returns it, or `None` if the vector is empty.
+    pub const fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        Some(unsafe { self.ptr.add(self.len).read() })
+    }
+
+    /// Removes every element, keeping the buffer.
+    pub fn clear(&mut self) {
+        let elements = ptr::slice_from_raw_parts_mut(self.ptr.as_ptr(), self.len);
+        self.len = 0;
+        unsafe { ptr::drop_in_place(elements) };
+    }
+
+    /// Appends clones of every element in `values`.
+    ///
+    /// # Panics
+    ///
+    /// Panics like [`reserve`](Self::reserve) if the vector has to grow.
+    pub fn extend_from_slice(&mut self, values: &[T])
+    where
+        T: Clone,
+    {
+        self.reserve(values.len());
+        for value in values {
+            self.push(value.clone());
+        }
+    }
+
+    /// Returns the layout of a buffer holding `capacity` elements.
+    fn layout(capacity: usize) -> Layout {
+        Layout::array::<T>(capacity)
+            .and_then(|layout| layout.align_to(ALIGN))
+            .expect("capacity overflow")
+    }
+
+    /// Moves the elements into a buffer for exactly `capacity` elements.
+    fn grow_to(&mut self, capacity: usize) {
+        let new_layout = Self::layout(capacity);
+        let block = if self.capacity == 0 {
+            CAllocator.allocate(new_layout)
+        } else {
+            unsafe { CAllocator.grow(self.ptr.cast(), Self::layout(self.capacity), new_layout) }
+        };
+
+        let Ok(block) = block else {
+            handle_alloc_error(new_layout);
+        };
+        self.ptr = block.as_non_null_ptr().cast();
+        self.capacity = capacity;
+    }
+}
+
+impl<T, const ALIGN: usize> Default for AlignedVec<T, ALIGN> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const ALIGN: usize> Deref for AlignedVec<T, ALIGN> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const ALIGN: usize> DerefMut for AlignedVec<T, ALIGN> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, const ALIGN: usize> Drop for AlignedVec<T, ALIGN> {
+    fn drop(&mut self) {
+        self.clear();
+        if mem::size_of::<T>() != 0 && self.capacity != 0 {
+            unsafe { CAllocator.deallocate(self.ptr.cast(), Self::layout(self.capacity)) };
+        }
+    }
+}
+
+impl<T: fmt::Debug, const ALIGN: usize> fmt::Debug for AlignedVec<T, ALIGN> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_slice(), formatter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{string::String, vec::Vec};
+    use core::cell::Cell;
+
+    /// Counts how often it is dropped through a shared counter.
+    struct DropCounter<'a>(&'a Cell<usize>);
+
+    impl Drop for DropCounter<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    /// Tests that the buffer stays aligned while the vector grows.
+    fn test_push_keeps_alignment() {
+        let mut values = AlignedVec::<u8, 64>::new();
+        for value in 0..=255 {
+            values.push(value);
+            assert_eq!(values.as_ptr() as usize % 64, 0);
+        }
+        assert_eq!(values.len(), 256);
+        assert!(values.capacity() >= 256);
+        assert!(values.iter().copied().eq(0..=255));
+    }
+
+    #[test]
+    /// Tests that `pop` returns the elements in reverse order.
+    fn test_pop() {
+        let mut values = AlignedVec::<String, 32>::new();
+        assert_eq!(values.pop(), None);
+        values.push(String::from("first"));
+        values.push(String::from("second"));
+        assert_eq!(values.pop().as_deref(), Some("second"));
+        assert_eq!(values.pop().as_deref(), Some("first"));
+        assert!(values.is_empty());
+    }
+
+    #[test]
+    /// Tests that elements can be changed through `as_mut_slice` and `DerefMut`.
+    fn test_as_mut_slice() {
+        let mut values = AlignedVec::<u32, 16>::with_capacity(4);
+        values.extend_from_slice(&[1, 2, 3, 4]);
+        values.as_mut_slice()[0] = 10;
+        values[3] *= 10;
+        assert_eq!(values.as_slice(), &[10, 2, 3, 40]);
+        assert_eq!(values.capacity(), 4);
+    }
+
+    #[test]
+    /// Tests that dropping the vector drops every element exactly once.
+    fn test_drop_elements() {
+        let drops = Cell::new(0);
+        let mut values = AlignedVec::<DropCounter<'_>, 8>::new();
+        for _ in 0..10 {
+            values.push(DropCounter(&drops));
+        }
+        drop(values.pop());
+        assert_eq!(drops.get(), 1);
+        drop(values);
+        assert_eq!(drops.get(), 10);
+    }
+
+    #[test]
+    /// Tests that zero-sized elements never allocate.
+    fn test_zero_sized_elements() {
+        let mut values = AlignedVec::<(), 4096>::new();
+        for _ in 0..1000 {
+            values.push(());
+        }
+        assert_eq!(values.len(), 1000);
+        assert_eq!(values.capacity(), usize::MAX);
+        assert_eq!(values.as_ptr() as usize % 4096, 0);
+    }
+
+    #[test]
+    #[should_panic = "ALIGN must be at least the alignment of T"]
+    /// Tests that an alignment below the element's alignment is rejected.
+    fn test_new_rejects_small_align() {
+        let _ = AlignedVec::<u64, 4>::new();
+    }
+
+    #[test]
+    #[should_panic = "ALIGN must be a power of two"]
+    /// Tests that an alignment that is not a power of two is rejected.
+    fn test_new_rejects_non_power_of_two() {
+        let _ = AlignedVec::<u8, 24>::new();
+    }
+
+    #[test]
+    /// Tests that the vector's contents match a `Vec` fed the same values.
+    fn test_matches_vec() {
+        let mut aligned = AlignedVec::<f64, 32>::new();
+        let mut plain = Vec::new();
+        for value in 0..100 {
+            aligned.push(f64::from(value) * 0.5);
+            plain.push(f64::from(value) * 0.5);
+        }
+        assert_eq!(aligned.as_slice(), plain.as_slice());
+        assert_eq!(alloc::format!("{aligned:?}"), alloc::format!("{plain:?}"));
+    }
+}
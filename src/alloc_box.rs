@@ -0,0 +1,164 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    fmt,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    ptr::{self, NonNull},
+};
+
+use alloc::alloc::handle_alloc_error;
+
+/// An owning pointer to a `T` stored in memory from the allocator `A`, like `Box<T, A>`.
+///
+/// The value is dropped and its memory returned to the allocator when the box goes out of scope.
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use std::alloc::Global;
+///
+/// use mem_allocs::alloc_box::AllocBox;
+///
+/// let mut value = AllocBox::new_in(41_u32, Global);
+/// *value += 1;
+/// assert_eq!(*value, 42);
+/// ```
+pub struct AllocBox<T, A: Allocator> {
+    ptr: NonNull<T>,
+    allocator: A,
+    _marker: PhantomData<T>,
+}
+
+// SAFETY: the box owns its value exclusively, like `Box<T, A>`, so it can be sent or shared
+// whenever the value and the allocator can.
+unsafe impl<T: Send, A: Allocator + Send> Send for AllocBox<T, A> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for AllocBox<T, A> {}
+
+impl<T, A: Allocator> AllocBox<T, A> {
+    /// Moves `value` into memory obtained from `allocator`.
+    ///
+    /// # Panics
+    ///
+    /// Calls [`handle_alloc_error`] if the allocation fails.
+    #[must_use]
+    pub fn new_in(value: T, allocator: A) -> Self {
+        Self::try_new_in(value, allocator)
+            .unwrap_or_else(|_| handle_alloc_error(Layout::new::<T>()))
+    }
+
+    /// Moves `value` into memory obtained from `allocator`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError` if the allocation fails; `value` is dropped in that case.
+    pub fn try_new_in(value: T, allocator: A) -> Result<Self, AllocError> {
+        let ptr = allocator
+            .allocate(Layout::new::<T>())?
+            .as_non_null_ptr()
+            .cast::<T>();
+        unsafe { ptr.write(value) };
+        Ok(Self {
+            ptr,
+            allocator,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns a reference to the allocator the value lives in.
+    #[must_use]
+    pub const fn allocator(&self) -> &A {
+        &self.allocator
+    }
+}
+
+impl<T, A: Allocator> Deref for AllocBox<T, A> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T, A: Allocator> DerefMut for AllocBox<T, A> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<T, A: Allocator> Drop for AllocBox<T, A> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(self.ptr.as_ptr());
+            self.allocator
+                .deallocate(self.ptr.cast(), Layout::new::<T>());
+        }
+    }
+}
+
+impl<T: fmt::Debug, A: Allocator> fmt::Debug for AllocBox<T, A> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, formatter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{alloc::Global, string::String, vec::Vec};
+    use core::cell::Cell;
+
+    /// Counts how often it is dropped through a shared counter.
+    struct DropCounter<'a>(&'a Cell<usize>);
+
+    impl Drop for DropCounter<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    /// Tests that the value is reachable through `Deref` and `DerefMut`.
+    fn test_deref() {
+        let mut boxed = AllocBox::new_in(String::from("arena"), Global);
+        boxed.push_str("-box");
+        assert_eq!(boxed.as_str(), "arena-box");
+        assert_eq!(boxed.len(), 9);
+    }
+
+    #[test]
+    /// Tests that dropping the box drops the value exactly once.
+    fn test_drops_value_once() {
+        let drops = Cell::new(0);
+        {
+            let boxed = AllocBox::new_in(DropCounter(&drops), Global);
+            assert_eq!(drops.get(), 0);
+            drop(boxed);
+        }
+        assert_eq!(drops.get(), 1);
+    }
+
+    #[test]
+    /// Tests that values owning heap memory are dropped, so nothing leaks.
+    fn test_drops_nested_allocations() {
+        let drops = Cell::new(0);
+        let boxed = AllocBox::new_in(
+            (0..4).map(|_| DropCounter(&drops)).collect::<Vec<_>>(),
+            Global,
+        );
+        assert_eq!(boxed.len(), 4);
+        drop(boxed);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    /// Tests that a failed allocation is reported and the value dropped.
+    #[cfg(feature = "arena_allocator")]
+    fn test_try_new_in_failure() {
+        let arena = crate::arena_allocator::ArenaAllocator::new(4);
+        let drops = Cell::new(0);
+
+        let boxed = AllocBox::try_new_in([DropCounter(&drops), DropCounter(&drops)], &arena);
+        assert!(boxed.is_err());
+        assert_eq!(drops.get(), 2);
+    }
+}
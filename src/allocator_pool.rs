@@ -0,0 +1,232 @@
+use core::{
+    alloc::Allocator,
+    cell::{Cell, UnsafeCell},
+    marker::PhantomData,
+    ops::Deref,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use alloc::{alloc::Global, vec::Vec};
+
+use crate::arena_allocator::ArenaAllocator;
+
+/// A fixed set of [`ArenaAllocator`]s that threads check out for exclusive use and return when
+/// they are done.
+///
+/// Each arena has an atomic "checked out" flag that [`checkout`](Self::checkout) claims with a
+/// `compare_exchange`, so handing out and returning arenas never takes a lock. Dropping the
+/// returned [`ArenaHandle`] resets the arena before releasing it, so every checkout starts with
+/// an empty arena.
+#[derive(Debug)]
+pub struct LinearAllocatorPool<A: Allocator = Global> {
+    arenas: Vec<UnsafeCell<ArenaAllocator<A>>>,
+    checked_out: Vec<AtomicBool>,
+}
+
+// SAFETY: an arena is only reachable through the `ArenaHandle` that claimed its flag, and the
+// handle is not `Sync`, so each arena is used by one thread at a time. Arenas may move between
+// threads with their handles, which is why the backing allocator has to be `Send`.
+unsafe impl<A: Allocator + Send> Sync for LinearAllocatorPool<A> {}
+
+impl LinearAllocatorPool {
+    /// Creates a pool of `pool_size` arenas with `arena_capacity` bytes each.
+    #[must_use]
+    pub fn new(pool_size: usize, arena_capacity: usize) -> Self {
+        Self::new_with_allocator(pool_size, arena_capacity, Global)
+    }
+}
+
+impl<A: Allocator> LinearAllocatorPool<A> {
+    /// Creates a pool of `pool_size` arenas with `arena_capacity` bytes each, allocating every
+    /// arena's buffer from a clone of `alloc`.
+    #[must_use]
+    pub fn new_with_allocator(pool_size: usize, arena_capacity: usize, alloc: A) -> Self
+    where
+        A: Clone,
+    {
+        Self {
+            arenas: (0..pool_size)
+                .map(|_| {
+                    UnsafeCell::new(ArenaAllocator::new_with_allocator(
+                        arena_capacity,
+                        alloc.clone(),
+                    ))
+                })
+                .collect(),
+            checked_out: (0..pool_size).map(|_| AtomicBool::new(false)).collect(),
+        }
+    }
+
+    /// Returns the number of arenas in the pool.
+    #[must_use]
+    pub const fn pool_size(&self) -> usize {
+        self.arenas.len()
+    }
+
+    /// Returns the number of arenas not currently checked out.
+    #[must_use]
+    pub fn available(&self) -> usize {
+        self.checked_out
+            .iter()
+            .filter(|flag| !flag.load(Ordering::Acquire))
+            .count()
+    }
+
+    /// Checks out a free arena, or returns `None` if every arena is in use.
+    #[must_use]
+    pub fn checkout(&self) -> Option<ArenaHandle<'_, A>> {
+        let index = self.checked_out.iter().position(|flag| {
+            flag.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+        })?;
+        Some(ArenaHandle {
+            pool: self,
+            index,
+            _not_sync: PhantomData,
+        })
+    }
+}
+
+/// Exclusive access to an arena checked out of a [`LinearAllocatorPool`].
+///
+/// Dropping the handle resets the arena and returns it to the pool.
+#[derive(Debug)]
+pub struct ArenaHandle<'a, A: Allocator = Global> {
+    pool: &'a LinearAllocatorPool<A>,
+    index: usize,
+    // Sharing the handle would let two threads use the same arena.
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+impl<A: Allocator> ArenaHandle<'_, A> {
+    /// Returns the position of the arena within the pool.
+    #[must_use]
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl<A: Allocator> Deref for ArenaHandle<'_, A> {
+    type Target = ArenaAllocator<A>;
+
+    fn deref(&self) -> &ArenaAllocator<A> {
+        // SAFETY: the handle claimed the arena's flag, so no other handle reaches this arena.
+        unsafe { &*self.pool.arenas[self.index].get() }
+    }
+}
+
+impl<A: Allocator> Drop for ArenaHandle<'_, A> {
+    fn drop(&mut self) {
+        // SAFETY: the handle claimed the arena's flag, and every borrow taken through `Deref`
+        // ends before the handle can be dropped, so this is the only reference to the arena.
+        unsafe { (*self.pool.arenas[self.index].get()).reset() };
+        self.pool.checked_out[self.index].store(false, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use core::alloc::{Allocator, Layout};
+
+    #[test]
+    /// Tests the `LinearAllocatorPool` with a generic vector in a checked-out arena.
+    fn test_generic_vector_with_allocator_pool() {
+        let pool = LinearAllocatorPool::new(2, 1024);
+        let arena = pool.checkout().unwrap();
+        let mut vector: Vec<usize, &ArenaAllocator> = Vec::with_capacity_in(100, &arena);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    /// Tests that checkouts fail once every arena is in use and succeed again after a return.
+    fn test_checkout_exhaustion() {
+        let pool = LinearAllocatorPool::new(2, 64);
+        let first = pool.checkout().unwrap();
+        let second = pool.checkout().unwrap();
+
+        assert_ne!(first.index(), second.index());
+        assert!(pool.checkout().is_none());
+        assert_eq!(pool.available(), 0);
+
+        drop(first);
+        assert_eq!(pool.available(), 1);
+        assert!(pool.checkout().is_some());
+    }
+
+    #[test]
+    /// Tests that a returned arena is reset before it is handed out again.
+    fn test_return_resets_arena() {
+        let pool = LinearAllocatorPool::new(1, 64);
+        let arena = pool.checkout().unwrap();
+        arena.allocate(Layout::new::<[u8; 8]>()).unwrap();
+        assert_eq!(arena.used(), 8);
+        drop(arena);
+
+        assert_eq!(pool.checkout().unwrap().used(), 0);
+    }
+
+    #[test]
+    /// Tests that concurrent checkouts from many threads never share an arena.
+    fn test_concurrent_checkout() {
+        const THREADS: usize = 8;
+        const ROUNDS: usize = 200;
+
+        let pool = LinearAllocatorPool::new(3, 256);
+        let layout = Layout::new::<u64>();
+
+        std::thread::scope(|scope| {
+            for thread in 0..THREADS {
+                let pool = &pool;
+                scope.spawn(move || {
+                    for round in 0..ROUNDS {
+                        let Some(arena) = pool.checkout() else {
+                            continue;
+                        };
+                        assert_eq!(arena.used(), 0);
+                        let value = arena.allocate(layout).unwrap().cast::<usize>();
+                        let used = arena.used();
+                        let expected = thread * ROUNDS + round;
+                        unsafe { value.write(expected) };
+                        std::thread::yield_now();
+                        // Another thread using the same arena would have overwritten the value or
+                        // moved the offset.
+                        assert_eq!(unsafe { value.read() }, expected);
+                        assert_eq!(arena.used(), used);
+                    }
+                });
+            }
+        });
+
+        assert_eq!(pool.available(), 3);
+    }
+
+    #[test]
+    #[cfg(feature = "c_allocator")]
+    /// Tests that a pool of arenas backed by the `CAllocator` checks out and resets them.
+    fn test_new_with_allocator() {
+        use crate::c_allocator::CAllocator;
+
+        let pool = LinearAllocatorPool::new_with_allocator(2, 128, CAllocator);
+        let arena = pool.checkout().unwrap();
+        assert_eq!(arena.allocator(), &CAllocator);
+        arena.write([7_u8; 64]).unwrap();
+        assert_eq!(arena.used(), 64);
+
+        let index = arena.index();
+        drop(arena);
+        let arena = pool.checkout().unwrap();
+        assert_eq!(arena.index(), index);
+        assert_eq!(arena.used(), 0);
+    }
+}
@@ -0,0 +1,2434 @@
+//! A single-threaded bump-pointer arena and the helpers built around it.
+//!
+//! # Thread safety
+//!
+//! [`ArenaAllocator`] is `Send`, so an arena can be moved to another thread, but it is
+//! deliberately not `Sync`. Its offset lives in an `UnsafeCell` and is bumped without any
+//! synchronisation, so two threads allocating through a shared `&ArenaAllocator` could be handed
+//! the same bytes. Use [`SyncArenaAllocator`](crate::sync_arena::SyncArenaAllocator) when an arena
+//! has to be shared.
+//!
+//! ```compile_fail
+//! use mem_allocs::arena_allocator::ArenaAllocator;
+//!
+//! fn assert_sync<T: Sync>(_: &T) {}
+//!
+//! let arena = ArenaAllocator::new(64);
+//! assert_sync(&arena);
+//! ```
+//!
+//! ```compile_fail
+//! use mem_allocs::arena_allocator::ArenaAllocator;
+//!
+//! let arena = ArenaAllocator::new(64);
+//! std::thread::scope(|scope| {
+//!     scope.spawn(|| arena.used());
+//! });
+//! ```
+
+use core::{
+    alloc::{AllocError, Allocator, GlobalAlloc, Layout},
+    cell::UnsafeCell,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    mem::{self, MaybeUninit},
+    ops::Deref,
+    ptr::{self, NonNull},
+    slice, str,
+    sync::atomic::{compiler_fence, Ordering},
+};
+
+use alloc::{alloc::Global, vec::Vec};
+
+use crate::align::align_up;
+
+/// A bump-pointer memory allocator that hands out memory from a single fixed-size buffer.
+///
+/// Individual deallocations are ignored; memory is reclaimed all at once with [`ArenaAllocator::reset`].
+/// The buffer itself comes from `A`, the global allocator unless the arena was built with
+/// [`ArenaAllocator::new_with_allocator`].
+#[allow(clippy::module_name_repetitions)]
+pub struct ArenaAllocator<A: Allocator = Global> {
+    buffer: UnsafeCell<Vec<MaybeUninit<u8>, A>>,
+    /// Number of bytes skipped at the start of `buffer` so that allocations begin aligned.
+    base_offset: usize,
+    offset: UnsafeCell<usize>,
+    /// Highest offset reached since creation or the last [`ArenaAllocator::reset_peak`].
+    peak: UnsafeCell<usize>,
+    /// Whether every allocation is preceded by a header recording its size.
+    headers: bool,
+}
+
+/// Size of the header written before each allocation by [`ArenaAllocator::with_headers`].
+const HEADER_SIZE: usize = mem::size_of::<usize>();
+
+/// Header value marking a padding word rather than a header. No `Layout` is this large.
+const PADDING: usize = usize::MAX;
+
+// SAFETY: the arena owns its buffer and nothing in it points back into thread-local state, so it
+// may move to another thread along with its backing allocator. Moving requires exclusive
+// ownership, which rules out concurrent access; `Sync` stays unimplemented because allocation
+// through `&self` is unsynchronised.
+unsafe impl<A: Allocator + Send> Send for ArenaAllocator<A> {}
+
+/// Creates an [`ArenaAllocator::EMPTY`] arena, which allocates nothing until it is
+/// [resized](ArenaAllocator::resize).
+impl Default for ArenaAllocator {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+/// Formats the usage as a one-line summary such as `Arena[768/1024 bytes (75%)]`.
+///
+/// ```
+/// use mem_allocs::arena_allocator::ArenaAllocator;
+///
+/// let arena = ArenaAllocator::new(1024);
+/// arena.allocate_array::<u8>(768).unwrap();
+/// println!("{arena}");
+/// assert_eq!(arena.to_string(), "Arena[768/1024 bytes (75%)]");
+/// ```
+impl<A: Allocator> fmt::Display for ArenaAllocator<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (used, capacity) = (self.used(), self.capacity());
+        let percent = if capacity == 0 {
+            0
+        } else {
+            (used as u128 * 100 + capacity as u128 / 2) / capacity as u128
+        };
+        write!(f, "Arena[{used}/{capacity} bytes ({percent}%)]")
+    }
+}
+
+/// Shows the usage counters rather than the raw cells and buffer contents.
+impl<A: Allocator> fmt::Debug for ArenaAllocator<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArenaAllocator")
+            .field("used", &self.used())
+            .field("capacity", &self.capacity())
+            .field("peak_usage", &self.peak_usage())
+            .field("headers", &self.headers)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Cloning creates a new, empty arena with the same capacity.
+///
+/// No allocated data is copied, and pointers into the original arena have nothing to do with the
+/// clone. Extra alignment requested through [`ArenaAllocator::with_alignment`] is not carried
+/// over, but headers from [`ArenaAllocator::with_headers`] are. The new buffer comes from a clone
+/// of the backing allocator.
+impl<A: Allocator + Clone> Clone for ArenaAllocator<A> {
+    fn clone(&self) -> Self {
+        Self {
+            headers: self.headers,
+            ..Self::new_with_allocator(self.capacity(), self.allocator().clone())
+        }
+    }
+}
+
+/// Arenas compare equal when they have the same [`capacity`](ArenaAllocator::capacity) and
+/// [`used`](ArenaAllocator::used) byte count.
+///
+/// This compares a snapshot of the arenas' state, not their history: two equal arenas may have
+/// reached the same usage through entirely different allocations. The buffer contents are not
+/// compared, because the live part of an arena usually holds uninitialised bytes, such as
+/// alignment padding or the spare capacity of a `Vec`, and reading those is undefined behaviour
+/// even just to compare them. Use [`ArenaAllocator::contents_eq`] when the caller knows every
+/// live byte is initialised.
+impl<A: Allocator> PartialEq for ArenaAllocator<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.capacity() == other.capacity() && self.used() == other.used()
+    }
+}
+
+impl<A: Allocator> Eq for ArenaAllocator<A> {}
+
+/// Hashes only the [`capacity`](ArenaAllocator::capacity), which is enough to stay consistent
+/// with equality.
+///
+/// # Hash stability
+///
+/// [`used`](ArenaAllocator::used) is left out on purpose: it changes with every allocation made
+/// through `&self`, which would silently move an arena stored in a `HashSet` or used as a
+/// `HashMap` key to the wrong bucket. Equality still compares it, so an arena that allocates
+/// while it is a key may stop being found by lookups with an arena that was equal to it before.
+impl<A: Allocator> Hash for ArenaAllocator<A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.capacity().hash(state);
+    }
+}
+
+/// Exposes the live part of the buffer, i.e. the first [`used`](ArenaAllocator::used) bytes.
+///
+/// The bytes are `MaybeUninit` because the live part includes alignment padding and any
+/// allocated space that was never written. Reading a block through the slice while it is being
+/// written through a pointer from the arena is undefined behaviour.
+impl<A: Allocator> AsRef<[MaybeUninit<u8>]> for ArenaAllocator<A> {
+    fn as_ref(&self) -> &[MaybeUninit<u8>] {
+        unsafe { slice::from_raw_parts(self.base_ptr().cast::<MaybeUninit<u8>>(), self.used()) }
+    }
+}
+
+/// Exposes the live part of the buffer mutably, for instance to load serialised data back.
+///
+/// Modifying the slice while pointers handed out by the arena are still in use, or using such a
+/// pointer while the slice is borrowed, is undefined behaviour.
+impl<A: Allocator> AsMut<[MaybeUninit<u8>]> for ArenaAllocator<A> {
+    fn as_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+        unsafe { slice::from_raw_parts_mut(self.base_ptr().cast::<MaybeUninit<u8>>(), self.used()) }
+    }
+}
+
+/// A copy of the live bytes and the offset of an [`ArenaAllocator`], produced by
+/// [`ArenaAllocator::snapshot`].
+#[derive(Debug, Clone)]
+pub struct ArenaSnapshot {
+    data: Vec<MaybeUninit<u8>>,
+    offset: usize,
+}
+
+impl ArenaSnapshot {
+    /// Returns the offset the arena had when the snapshot was taken, which is also the number of
+    /// bytes saved.
+    #[must_use]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+/// A saved position inside an [`ArenaAllocator`], produced by [`ArenaAllocator::save`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ArenaMarker(usize);
+
+impl ArenaAllocator {
+    /// An arena without any capacity, which refuses every allocation, zero-sized ones included.
+    ///
+    /// It owns no memory, so it is cheap to create and handy as a placeholder where an arena is
+    /// required but nothing should be allocated. Like any constant, every use is a separate arena.
+    /// [`AllocatorExt::allocate_ext`](crate::error::AllocatorExt::allocate_ext) reports its
+    /// failures as [`AllocFailure::Unsupported`](crate::error::AllocFailure::Unsupported), since no
+    /// reset can make room.
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::{Allocator, Layout};
+    ///
+    /// use mem_allocs::arena_allocator::ArenaAllocator;
+    ///
+    /// /// Copies `input` into `scratch` if it has room, or reports that the caller must fall back.
+    /// fn stage<'a>(scratch: &'a ArenaAllocator, input: &str) -> Option<&'a str> {
+    ///     scratch.write_str_in(input).ok()
+    /// }
+    ///
+    /// let arena = ArenaAllocator::new(64);
+    /// assert_eq!(stage(&arena, "staged"), Some("staged"));
+    /// assert_eq!(stage(&ArenaAllocator::EMPTY, "staged"), None);
+    /// assert!(ArenaAllocator::EMPTY.allocate(Layout::new::<()>()).is_err());
+    /// ```
+    #[allow(clippy::declare_interior_mutable_const)] // Every use is meant to be a fresh arena.
+    pub const EMPTY: Self = Self::from_vec(Vec::new());
+
+    /// Creates a new arena with a backing buffer of `bytes` bytes.
+    ///
+    /// The buffer is allocated on the heap, which cannot happen in a `const fn`. Use
+    /// [`StaticArena`](crate::static_arena::StaticArena) to initialise a `static`.
+    #[must_use]
+    pub fn new(bytes: usize) -> Self {
+        Self::new_with_allocator(bytes, Global)
+    }
+
+    /// Creates a new arena with `bytes` bytes of capacity whose first allocation starts at an
+    /// address aligned to at least `min_align`.
+    ///
+    /// The backing buffer is over-allocated by `min_align - 1` bytes and the unaligned head is
+    /// skipped. `min_align` must be a power of two.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_align` is not a power of two or the buffer size overflows.
+    #[must_use]
+    pub fn with_alignment(bytes: usize, min_align: usize) -> Self {
+        assert!(
+            min_align.is_power_of_two(),
+            "alignment must be a power of two"
+        );
+
+        let padded = bytes
+            .checked_add(min_align - 1)
+            .expect("buffer size overflows");
+        let mut buffer: Vec<MaybeUninit<u8>> = alloc::vec![MaybeUninit::uninit(); padded];
+        let start = buffer.as_ptr() as usize;
+        let base_offset = align_up(start, min_align).expect("buffer size overflows") - start;
+        buffer.truncate(base_offset + bytes);
+
+        Self {
+            base_offset,
+            buffer: UnsafeCell::new(buffer),
+            offset: UnsafeCell::new(0),
+            peak: UnsafeCell::new(0),
+            headers: false,
+        }
+    }
+
+    /// Creates a new arena of `bytes` bytes that writes a `usize` header recording the size
+    /// before every allocation, so the live allocations can be walked with
+    /// [`ArenaAllocator::allocated_regions`].
+    ///
+    /// Each allocation costs up to two extra words: the header and the padding that keeps it
+    /// aligned. Arenas from [`ArenaAllocator::new`] write no headers and have no such overhead.
+    #[must_use]
+    pub fn with_headers(bytes: usize) -> Self {
+        Self {
+            headers: true,
+            ..Self::new(bytes)
+        }
+    }
+
+    /// Creates a new arena that allocates from an existing buffer, starting at offset zero.
+    ///
+    /// The arena uses the whole length of `vec` as its capacity.
+    #[must_use]
+    pub const fn from_vec(vec: Vec<MaybeUninit<u8>>) -> Self {
+        Self {
+            buffer: UnsafeCell::new(vec),
+            base_offset: 0,
+            offset: UnsafeCell::new(0),
+            peak: UnsafeCell::new(0),
+            headers: false,
+        }
+    }
+
+    /// Creates an arena view that allocates from `buf` without allocating any memory itself.
+    #[must_use]
+    pub const fn from_slice(buf: &mut [MaybeUninit<u8>]) -> ArenaAllocatorView<'_> {
+        ArenaAllocatorView::new(buf)
+    }
+
+    /// Creates an arena view over `capacity` bytes of raw memory starting at `ptr`, such as a
+    /// pool handed over by C code, without allocating any memory itself.
+    ///
+    /// The view uses the same bump-pointer logic as [`ArenaAllocator::from_slice`].
+    ///
+    /// ```
+    /// use core::alloc::{GlobalAlloc, Layout};
+    ///
+    /// use mem_allocs::arena_allocator::ArenaAllocator;
+    ///
+    /// // Stands in for a C function such as `uint8_t *firmware_pool(size_t *len);`.
+    /// extern "C" fn firmware_pool(len: *mut usize) -> *mut u8 {
+    ///     static mut POOL: [u32; 256] = [0; 256];
+    ///     unsafe {
+    ///         *len = 1024;
+    ///         (&raw mut POOL).cast()
+    ///     }
+    /// }
+    ///
+    /// let mut len = 0;
+    /// let pool = firmware_pool(&mut len);
+    /// let arena = unsafe { ArenaAllocator::from_raw_parts(pool, len) };
+    ///
+    /// let value = unsafe { arena.alloc(Layout::new::<u32>()) }.cast::<u32>();
+    /// unsafe { value.write(7) };
+    /// assert_eq!(arena.used(), 4);
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// The behaviour is undefined unless all of the following hold:
+    ///
+    /// - `ptr` is non-null, even when `capacity` is zero.
+    /// - `ptr` is valid for reads and writes of `capacity` bytes, which lie in a single allocated
+    ///   object, and stays so for as long as the view or any block from it is in use.
+    /// - Nothing else reads or writes that memory in the meantime, and the memory is not freed
+    ///   while the view is in use; the `'static` lifetime is not checked.
+    /// - `capacity` is at most `isize::MAX`.
+    #[must_use]
+    pub unsafe fn from_raw_parts(ptr: *mut u8, capacity: usize) -> ArenaAllocatorView<'static> {
+        debug_assert!(!ptr.is_null(), "arena memory must not be null");
+        ArenaAllocatorView::new(slice::from_raw_parts_mut(
+            ptr.cast::<MaybeUninit<u8>>(),
+            capacity,
+        ))
+    }
+}
+
+impl<A: Allocator> ArenaAllocator<A> {
+    /// Creates a new arena with a backing buffer of `bytes` bytes allocated from `alloc`, for
+    /// instance a [`CAllocator`](crate::c_allocator::CAllocator) or an `MmapAllocator`.
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    ///
+    /// use mem_allocs::arena_allocator::ArenaAllocator;
+    ///
+    /// let arena = ArenaAllocator::new_with_allocator(1024, System);
+    /// let value = arena.write(42_u64).unwrap();
+    /// assert_eq!(*value, 42);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Calls [`handle_alloc_error`](alloc::alloc::handle_alloc_error) if `alloc` cannot provide
+    /// the buffer.
+    #[must_use]
+    pub fn new_with_allocator(bytes: usize, alloc: A) -> Self {
+        let mut buffer = Vec::with_capacity_in(bytes, alloc);
+        buffer.resize(bytes, MaybeUninit::uninit());
+        Self {
+            buffer: UnsafeCell::new(buffer),
+            base_offset: 0,
+            offset: UnsafeCell::new(0),
+            peak: UnsafeCell::new(0),
+            headers: false,
+        }
+    }
+
+    /// Returns a reference to the allocator the backing buffer lives in.
+    #[must_use]
+    pub fn allocator(&self) -> &A {
+        unsafe { (*self.buffer.get()).allocator() }
+    }
+
+    /// Returns an iterator over the `(pointer, size)` pair of every allocation made since the
+    /// last reset, in address order.
+    ///
+    /// Only arenas built with [`ArenaAllocator::with_headers`] record their allocations; for any
+    /// other arena the iterator is empty. A block that was grown by copying is still reported at
+    /// its old address too, since the arena never frees it.
+    ///
+    /// ```
+    /// use mem_allocs::arena_allocator::ArenaAllocator;
+    ///
+    /// let arena = ArenaAllocator::with_headers(256);
+    /// arena.allocate_one::<u8>().unwrap();
+    /// arena.allocate_array::<u64>(4).unwrap();
+    ///
+    /// let sizes: Vec<usize> = arena.allocated_regions().map(|(_, size)| size).collect();
+    /// assert_eq!(sizes, [1, 32]);
+    /// ```
+    #[must_use]
+    pub fn allocated_regions(&self) -> ArenaRegionIter<'_> {
+        ArenaRegionIter {
+            base: self.base_ptr(),
+            cursor: 0,
+            end: if self.headers { self.used() } else { 0 },
+            _arena: PhantomData,
+        }
+    }
+
+    /// Consumes the arena and returns its backing buffer so the memory can be reused.
+    ///
+    /// Every pointer handed out by the arena is invalidated. For arenas built with
+    /// [`ArenaAllocator::with_alignment`], the returned buffer includes the skipped unaligned head.
+    #[must_use]
+    pub fn into_inner(self) -> Vec<MaybeUninit<u8>, A> {
+        self.buffer.into_inner()
+    }
+
+    /// Returns the total number of bytes available for allocation in the backing buffer.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        unsafe { (*self.buffer.get()).len() - self.base_offset }
+    }
+
+    /// Returns the largest power of two the address of the first allocation is aligned to.
+    #[must_use]
+    pub fn base_align(&self) -> usize {
+        1 << (self.base_ptr() as usize).trailing_zeros()
+    }
+
+    /// Returns the current bump offset, i.e. where the next allocation starts relative to the
+    /// start of the buffer before any alignment padding.
+    ///
+    /// This is a plain snapshot for diagnostics and assertions; it carries no ordering guarantee
+    /// with respect to other operations on the arena.
+    #[must_use]
+    pub fn offset(&self) -> usize {
+        unsafe { *self.offset.get() }
+    }
+
+    /// Returns the number of bytes consumed so far, including alignment padding.
+    #[must_use]
+    pub fn used(&self) -> usize {
+        self.offset()
+    }
+
+    /// Returns the number of bytes still available for allocation.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.capacity() - self.used()
+    }
+
+    /// Returns `true` if nothing has been allocated since the arena was created or last reset.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.used() == 0
+    }
+
+    /// Returns `true` if the whole buffer is used, so every allocation that is not zero-sized
+    /// fails.
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Returns `true` if the next allocation would start at a multiple of `align` without any
+    /// padding.
+    ///
+    /// `align` must be a power of two.
+    #[must_use]
+    pub fn is_aligned_to(&self, align: usize) -> bool {
+        debug_assert!(align.is_power_of_two(), "alignment must be a power of two");
+        (self.base_ptr() as usize + self.used()).is_multiple_of(align)
+    }
+
+    /// Returns the highest [`used`](ArenaAllocator::used) value reached over the lifetime of the
+    /// arena.
+    ///
+    /// The high-water mark survives [`ArenaAllocator::reset`]; clear it with
+    /// [`ArenaAllocator::reset_peak`].
+    #[must_use]
+    pub fn peak_usage(&self) -> usize {
+        unsafe { *self.peak.get() }
+    }
+
+    /// Clears the high-water mark, lowering it to the current usage.
+    pub fn reset_peak(&self) {
+        unsafe { *self.peak.get() = self.used() };
+    }
+
+    /// Returns `true` if `ptr` points into the buffer of this arena.
+    #[must_use]
+    pub fn contains(&self, ptr: NonNull<u8>) -> bool {
+        let start = self.base_ptr() as usize;
+        let address = ptr.as_ptr() as usize;
+        start <= address && address < start + self.capacity()
+    }
+
+    /// Returns `true` if both arenas compare equal and their live bytes are identical.
+    ///
+    /// This is the byte-for-byte comparison that `==` leaves out, because it reads the whole
+    /// live part of both buffers, padding included.
+    ///
+    /// # Safety
+    ///
+    /// Every one of the first [`used`](ArenaAllocator::used) bytes of both arenas must be
+    /// initialised, for instance because every block was written in full or allocated with
+    /// `allocate_zeroed`, and none of them may be written while the comparison runs.
+    #[must_use]
+    pub unsafe fn contents_eq(&self, other: &Self) -> bool {
+        let bytes = |arena: &Self| {
+            let live = arena.as_ref();
+            slice::from_raw_parts(live.as_ptr().cast::<u8>(), live.len())
+        };
+        self == other && bytes(self) == bytes(other)
+    }
+
+    /// Changes the capacity of the arena to `new_capacity` bytes, keeping everything allocated so
+    /// far.
+    ///
+    /// **Growing may move the backing buffer.** The contents are carried over, but every pointer
+    /// handed out before the resize is invalidated and must not be used afterwards; taking
+    /// `&mut self` makes the borrow checker enforce this for allocations that borrow the arena.
+    /// A moved buffer also loses any extra alignment requested with
+    /// [`ArenaAllocator::with_alignment`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError`, leaving the arena untouched, if `new_capacity` is smaller than
+    /// [`ArenaAllocator::used`], since shrinking would cut off live allocations, or if the larger
+    /// buffer cannot be allocated.
+    pub fn resize(&mut self, new_capacity: usize) -> Result<(), AllocError> {
+        if new_capacity < self.used() {
+            return Err(AllocError);
+        }
+
+        let new_len = self
+            .base_offset
+            .checked_add(new_capacity)
+            .ok_or(AllocError)?;
+        let buffer = self.buffer.get_mut();
+        if let Some(additional) = new_len.checked_sub(buffer.len()) {
+            buffer
+                .try_reserve_exact(additional)
+                .map_err(|_| AllocError)?;
+        }
+        buffer.resize(new_len, MaybeUninit::uninit());
+        Ok(())
+    }
+
+    /// Resets the arena, making its whole buffer available again.
+    ///
+    /// The old contents are left in the buffer; use [`ArenaAllocator::reset_to_zero`] when they
+    /// must not leak into later allocations. Taking `&mut self` makes the borrow checker reject a
+    /// reset while anything borrowing the arena, such as a `Vec<_, &ArenaAllocator>` or a
+    /// reference from [`ArenaAllocator::write`], is still alive. Raw pointers handed out before
+    /// the reset must no longer be used.
+    pub const fn reset(&mut self) {
+        *self.offset.get_mut() = 0;
+    }
+
+    /// Overwrites everything allocated so far with zeros, then resets the arena.
+    ///
+    /// Use this instead of [`ArenaAllocator::reset`] when the arena held sensitive data, such as
+    /// keys or passwords, that must not survive into the next epoch. The bytes are cleared with
+    /// volatile writes, so the compiler cannot elide them as dead stores. Like
+    /// [`ArenaAllocator::reset`], it needs a unique borrow of the arena.
+    pub fn reset_to_zero(&mut self) {
+        let base = self.base_ptr();
+        for offset in 0..self.used() {
+            // SAFETY: the first `used` bytes lie inside the buffer, and the unique borrow means no
+            // allocation from the arena is still in use.
+            unsafe { ptr::write_volatile(base.add(offset), 0) };
+        }
+        compiler_fence(Ordering::SeqCst);
+        self.reset();
+    }
+
+    /// Saves the current position of the arena so it can be rolled back with [`ArenaAllocator::restore`].
+    #[must_use]
+    pub fn save(&self) -> ArenaMarker {
+        ArenaMarker(self.used())
+    }
+
+    /// Rolls the arena back to a position previously obtained with [`ArenaAllocator::save`].
+    ///
+    /// # Safety
+    ///
+    /// Every pointer allocated after `marker` was saved becomes invalid and must not be used
+    /// afterwards. `marker` must come from this arena and must not lie beyond its current position.
+    pub unsafe fn restore(&self, marker: ArenaMarker) {
+        debug_assert!(marker.0 <= self.used());
+        *self.offset.get() = marker.0;
+    }
+
+    /// Copies the live bytes of the arena, so they can be brought back with
+    /// [`ArenaAllocator::restore_snapshot`] after a speculative computation.
+    ///
+    /// Unlike an [`ArenaMarker`], which only remembers the offset, a snapshot also undoes changes
+    /// made to blocks that were already allocated when it was taken.
+    #[must_use]
+    pub fn snapshot(&self) -> ArenaSnapshot {
+        ArenaSnapshot {
+            data: self.as_ref().to_vec(),
+            offset: self.used(),
+        }
+    }
+
+    /// Copies the bytes saved in `snapshot` back into the arena and restores its offset.
+    ///
+    /// **Every pointer handed out before the restore is invalidated**, including pointers to
+    /// blocks that already existed when the snapshot was taken, and must not be used afterwards.
+    /// Taking `&mut self` makes the borrow checker enforce this for allocations that borrow the
+    /// arena. Clone the snapshot first to roll back to it more than once.
+    ///
+    /// ```
+    /// use mem_allocs::arena_allocator::ArenaAllocator;
+    ///
+    /// let mut arena = ArenaAllocator::new(64);
+    /// arena.write(1_u8).unwrap();
+    /// let snapshot = arena.snapshot();
+    ///
+    /// // Speculatively change the value and allocate more.
+    /// *arena.write(2_u8).unwrap() = 3;
+    ///
+    /// arena.restore_snapshot(snapshot);
+    /// assert_eq!(arena.used(), 1);
+    /// assert_eq!(unsafe { arena.as_ref()[0].assume_init() }, 1);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the snapshot holds more bytes than the arena's capacity.
+    pub fn restore_snapshot(&mut self, snapshot: ArenaSnapshot) {
+        let ArenaSnapshot { data, offset } = snapshot;
+        assert!(
+            offset <= self.capacity(),
+            "snapshot of {offset} bytes does not fit in an arena of {} bytes",
+            self.capacity()
+        );
+        // SAFETY: the snapshot holds `offset` bytes, which fit in the buffer as checked above, and
+        // the unique borrow means no allocation from the arena is still in use.
+        unsafe {
+            ptr::copy_nonoverlapping(
+                data.as_ptr(),
+                self.base_ptr().cast::<MaybeUninit<u8>>(),
+                offset,
+            );
+        }
+        self.set_offset(offset);
+    }
+
+    /// Allocates uninitialized memory for `count` values of type `T`.
+    ///
+    /// ```
+    /// use mem_allocs::arena_allocator::ArenaAllocator;
+    ///
+    /// let arena = ArenaAllocator::new(64);
+    /// let values = arena.allocate_array::<u32>(4).unwrap();
+    /// for (index, value) in (0..4).enumerate() {
+    ///     unsafe { values.cast::<u32>().add(index).as_ptr().write(value) };
+    /// }
+    /// assert_eq!(unsafe { values.as_ref() }, [0, 1, 2, 3]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError` if the array layout overflows or the arena is out of space.
+    pub fn allocate_array<T>(&self, count: usize) -> Result<NonNull<[T]>, AllocError> {
+        let layout = Layout::array::<T>(count).map_err(|_| AllocError)?;
+        let allocated_ptr = self.allocate(layout)?;
+        Ok(NonNull::slice_from_raw_parts(
+            allocated_ptr.as_non_null_ptr().cast::<T>(),
+            count,
+        ))
+    }
+
+    /// Allocates uninitialized memory for a single value of type `T`.
+    ///
+    /// ```
+    /// use mem_allocs::arena_allocator::ArenaAllocator;
+    ///
+    /// let arena = ArenaAllocator::new(64);
+    /// let value = arena.allocate_one::<u64>().unwrap();
+    /// unsafe { value.as_ptr().write(42) };
+    /// assert_eq!(unsafe { *value.as_ptr() }, 42);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError` if the arena is out of space.
+    pub fn allocate_one<T>(&self) -> Result<NonNull<T>, AllocError> {
+        let allocated_ptr = self.allocate(Layout::new::<T>())?;
+        Ok(allocated_ptr.as_non_null_ptr().cast::<T>())
+    }
+
+    /// Moves `value` into the arena and returns a reference to it.
+    ///
+    /// The arena never runs destructors: `value` is not dropped when the arena is reset or
+    /// dropped, so anything it owns, such as a `Box` or a `Vec` on the global heap, is leaked.
+    /// The reference borrows the arena, so the arena cannot be reset while it is alive.
+    ///
+    /// ```
+    /// use mem_allocs::arena_allocator::ArenaAllocator;
+    ///
+    /// let arena = ArenaAllocator::new(64);
+    /// let point = arena.write((3_i32, 4_i32)).unwrap();
+    /// point.0 += 1;
+    /// assert_eq!(*point, (4, 4));
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use mem_allocs::arena_allocator::ArenaAllocator;
+    ///
+    /// let mut arena = ArenaAllocator::new(64);
+    /// let point = arena.write((3_i32, 4_i32)).unwrap();
+    /// arena.reset();
+    /// point.0 += 1;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError`, dropping `value`, if the arena is out of space.
+    #[allow(clippy::mut_from_ref)] // Every call returns a fresh block, so references never alias.
+    pub fn write<T>(&self, value: T) -> Result<&mut T, AllocError> {
+        let allocated_ptr = self.allocate_one::<T>()?;
+        unsafe {
+            allocated_ptr.as_ptr().write(value);
+            Ok(&mut *allocated_ptr.as_ptr())
+        }
+    }
+
+    /// Copies `values` into the arena and returns a reference to the copy.
+    ///
+    /// As with [`write`](Self::write), the copy keeps the arena borrowed until it is dropped.
+    ///
+    /// ```
+    /// use mem_allocs::arena_allocator::ArenaAllocator;
+    ///
+    /// let arena = ArenaAllocator::new(64);
+    /// let copy = arena.write_slice(&[1_u16, 2, 3]).unwrap();
+    /// copy[0] = 7;
+    /// assert_eq!(copy, [7, 2, 3]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError` if the arena is out of space.
+    #[allow(clippy::mut_from_ref)] // Every call returns a fresh block, so references never alias.
+    pub fn write_slice<T: Copy>(&self, values: &[T]) -> Result<&mut [T], AllocError> {
+        let allocated_ptr = self.allocate_array::<T>(values.len())?.cast::<T>();
+        unsafe {
+            ptr::copy_nonoverlapping(values.as_ptr(), allocated_ptr.as_ptr(), values.len());
+            Ok(slice::from_raw_parts_mut(
+                allocated_ptr.as_ptr(),
+                values.len(),
+            ))
+        }
+    }
+
+    /// Returns `true` if a block for `layout` would fit right now, without allocating it.
+    ///
+    /// The check accounts for alignment padding and headers exactly as an allocation would.
+    ///
+    /// ```
+    /// use core::alloc::Layout;
+    ///
+    /// use mem_allocs::arena_allocator::ArenaAllocator;
+    ///
+    /// let arena = ArenaAllocator::new(16);
+    /// arena.allocate_one::<u8>().unwrap();
+    /// assert!(arena.can_allocate(Layout::new::<u64>()));
+    /// assert!(!arena.can_allocate(Layout::new::<[u64; 2]>()));
+    /// assert_eq!(arena.used(), 1);
+    /// ```
+    #[must_use]
+    pub fn can_allocate(&self, layout: Layout) -> bool {
+        self.place(self.used(), layout).is_some()
+    }
+
+    /// Returns `true` if blocks for all `layouts`, allocated in order, would fit right now,
+    /// without allocating any of them.
+    #[must_use]
+    pub fn can_allocate_all(&self, layouts: &[Layout]) -> bool {
+        layouts
+            .iter()
+            .try_fold(self.used(), |offset, &layout| {
+                self.place(offset, layout).map(|(_, end)| end)
+            })
+            .is_some()
+    }
+
+    /// Advances the offset so that the next allocation starts at a multiple of `align`, even if
+    /// its own layout asks for less.
+    ///
+    /// No usable bytes are allocated; the skipped bytes simply count as padding. In an arena with
+    /// headers, the offset stops one header before the aligned address, so the header of the
+    /// next block ends right where the block has to start.
+    ///
+    /// ```
+    /// use mem_allocs::arena_allocator::ArenaAllocator;
+    ///
+    /// let arena = ArenaAllocator::new(256);
+    /// arena.allocate_one::<u8>().unwrap();
+    /// arena.align_next_allocation(64).unwrap();
+    /// let block = arena.allocate_one::<u8>().unwrap();
+    /// assert_eq!(block.as_ptr() as usize % 64, 0);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError`, leaving the arena untouched, if `align` is not a power of two or
+    /// the padding would not fit in the remaining space.
+    pub fn align_next_allocation(&self, align: usize) -> Result<(), AllocError> {
+        if !align.is_power_of_two() {
+            return Err(AllocError);
+        }
+
+        let base = self.base_ptr() as usize;
+        let offset = self.used();
+        let header = if self.headers { HEADER_SIZE } else { 0 };
+        let cursor = (base + offset).checked_add(header).ok_or(AllocError)?;
+        let new_offset = align_up(cursor, align.max(header)).ok_or(AllocError)? - header - base;
+        if new_offset > self.capacity() {
+            return Err(AllocError);
+        }
+
+        if self.headers {
+            // SAFETY: the padding ends at `new_offset`, which lies inside the buffer.
+            unsafe { self.write_padding(offset, new_offset) };
+        }
+        self.set_offset(new_offset);
+        Ok(())
+    }
+
+    /// Allocates one block for every layout in `layouts`, all or nothing.
+    ///
+    /// Before anything is allocated, the worst case for the whole batch, every size plus the
+    /// largest padding its alignment can need, is checked against [`ArenaAllocator::remaining`].
+    /// If it does not fit, the call fails without touching the arena. Once the check has passed,
+    /// the blocks are allocated one by one; should one of them still fail, the blocks before it
+    /// stay allocated and the offset stays advanced.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError` if the worst-case size of the batch exceeds the remaining space.
+    pub fn batch_allocate(&self, layouts: &[Layout]) -> Result<Vec<NonNull<u8>>, AllocError> {
+        let total = layouts
+            .iter()
+            .try_fold(0_usize, |total, layout| {
+                total
+                    .checked_add(layout.size())?
+                    .checked_add(layout.align() - 1)?
+                    .checked_add(self.header_overhead())
+            })
+            .ok_or(AllocError)?;
+        if total > self.remaining() {
+            return Err(AllocError);
+        }
+
+        layouts
+            .iter()
+            .map(|&layout| Ok(self.allocate(layout)?.as_non_null_ptr()))
+            .collect()
+    }
+
+    /// Allocates a block for `layout`, resetting the arena and retrying once if it is full.
+    ///
+    /// This suits streaming pipelines that fill the arena with a batch, process it and start
+    /// over. The returned `bool` is `true` if a reset happened before the allocation. **In that
+    /// case every pointer previously handed out by the arena is invalidated** and must not be
+    /// used again.
+    ///
+    /// ```
+    /// use core::alloc::Layout;
+    ///
+    /// use mem_allocs::arena_allocator::ArenaAllocator;
+    ///
+    /// let mut arena = ArenaAllocator::new(64);
+    /// let layout = Layout::new::<[u8; 48]>();
+    ///
+    /// assert!(!arena.try_allocate_or_reset(layout).unwrap().1);
+    /// assert!(arena.try_allocate_or_reset(layout).unwrap().1);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError` if the block does not fit even in the empty arena. The arena has
+    /// been reset by then.
+    pub fn try_allocate_or_reset(
+        &mut self,
+        layout: Layout,
+    ) -> Result<(NonNull<[u8]>, bool), AllocError> {
+        if let Ok(allocated_ptr) = self.allocate(layout) {
+            return Ok((allocated_ptr, false));
+        }
+        self.reset();
+        Ok((self.allocate(layout)?, true))
+    }
+
+    /// Copies `s` into the arena and returns the copy.
+    ///
+    /// ```
+    /// use mem_allocs::arena_allocator::ArenaAllocator;
+    ///
+    /// let arena = ArenaAllocator::new(64);
+    /// let greeting = arena.write_str_in("hello").unwrap();
+    /// assert_eq!(greeting, "hello");
+    /// assert_eq!(arena.used(), 5);
+    /// ```
+    ///
+    /// The copy borrows the arena, so resetting the arena while it is alive does not compile:
+    ///
+    /// ```compile_fail
+    /// use mem_allocs::arena_allocator::ArenaAllocator;
+    ///
+    /// let mut arena = ArenaAllocator::new(64);
+    /// let greeting = arena.write_str_in("hello").unwrap();
+    /// arena.reset();
+    /// assert_eq!(greeting, "hello");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError` if the string does not fit in the remaining space.
+    pub fn write_str_in<'a>(&'a self, s: &str) -> Result<&'a str, AllocError> {
+        let allocated_ptr = self.allocate(Layout::for_value(s))?.as_mut_ptr();
+        unsafe {
+            ptr::copy_nonoverlapping(s.as_ptr(), allocated_ptr, s.len());
+            Ok(str::from_utf8_unchecked(slice::from_raw_parts(
+                allocated_ptr,
+                s.len(),
+            )))
+        }
+    }
+
+    /// Returns a pointer to the start of the usable part of the backing buffer.
+    pub(crate) fn base_ptr(&self) -> *mut u8 {
+        unsafe {
+            (*self.buffer.get())
+                .as_mut_ptr()
+                .add(self.base_offset)
+                .cast::<u8>()
+        }
+    }
+
+    /// Returns the most space a header and its padding can add to one allocation.
+    const fn header_overhead(&self) -> usize {
+        if self.headers {
+            2 * HEADER_SIZE - 1
+        } else {
+            0
+        }
+    }
+
+    /// Writes the header for a block of `size` bytes at offset `start`, filling the words
+    /// between offset `from` and the header with padding markers.
+    ///
+    /// # Safety
+    ///
+    /// The words from `from` rounded up to a header boundary until `start` must lie inside the
+    /// buffer, and `start` must directly follow a header boundary.
+    #[allow(clippy::cast_ptr_alignment)] // Headers and padding sit on word boundaries.
+    unsafe fn write_header(&self, from: usize, start: usize, size: usize) {
+        self.write_padding(from, start - HEADER_SIZE);
+        self.base_ptr()
+            .add(start - HEADER_SIZE)
+            .cast::<usize>()
+            .write(size);
+    }
+
+    /// Fills the words between offset `from` rounded up to a header boundary and offset `to`
+    /// with padding markers.
+    ///
+    /// # Safety
+    ///
+    /// Those words must lie inside the buffer.
+    #[allow(clippy::cast_ptr_alignment)] // Padding sits on word boundaries.
+    unsafe fn write_padding(&self, from: usize, to: usize) {
+        let base = self.base_ptr();
+        let mut word =
+            align_up(base as usize + from, HEADER_SIZE).unwrap_unchecked() - base as usize;
+        while word < to {
+            base.add(word).cast::<usize>().write(PADDING);
+            word += HEADER_SIZE;
+        }
+    }
+
+    /// Returns the start and end offsets a block for `layout` would get if the arena were at
+    /// `offset`, or `None` if it would not fit.
+    pub(crate) fn place(&self, offset: usize, layout: Layout) -> Option<(usize, usize)> {
+        let base = self.base_ptr() as usize;
+        let mut cursor = base.checked_add(offset)?;
+        if self.headers {
+            cursor = align_up(cursor, HEADER_SIZE)?.checked_add(HEADER_SIZE)?;
+        }
+        let start = align_up(cursor, layout.align())? - base;
+        let end = start.checked_add(layout.size())?;
+        // A zero-capacity arena refuses even zero-sized blocks, so `EMPTY` never hands anything out.
+        (end <= self.capacity() && self.capacity() != 0).then_some((start, end))
+    }
+
+    /// Moves the offset to `end`, raising the high-water mark if needed.
+    fn set_offset(&self, end: usize) {
+        unsafe {
+            *self.offset.get() = end;
+            *self.peak.get() = (*self.peak.get()).max(end);
+        }
+    }
+
+    /// Resizes the block at `block_ptr` from `old_size` to `new_size` bytes by moving the offset,
+    /// which is only possible for the most recent allocation. Returns whether it succeeded.
+    #[allow(clippy::cast_ptr_alignment)] // A header sits on the word boundary before the block.
+    fn resize_in_place(&self, block_ptr: *mut u8, old_size: usize, new_size: usize) -> bool {
+        let start = block_ptr as usize - self.base_ptr() as usize;
+        let is_last = start + old_size == self.used();
+
+        match start.checked_add(new_size) {
+            Some(new_end) if is_last && new_end <= self.capacity() => {
+                if self.headers {
+                    unsafe { block_ptr.sub(HEADER_SIZE).cast::<usize>().write(new_size) };
+                }
+                self.set_offset(new_end);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+unsafe impl<A: Allocator> Allocator for ArenaAllocator<A> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let base = self.base_ptr();
+        let offset = self.used();
+        let (start, end) = self.place(offset, layout).ok_or(AllocError)?;
+
+        if self.headers {
+            unsafe { self.write_header(offset, start, layout.size()) };
+        }
+        self.set_offset(end);
+        let allocated_ptr = unsafe { base.add(start) };
+
+        NonNull::new(ptr::slice_from_raw_parts_mut(allocated_ptr, layout.size())).ok_or(AllocError)
+    }
+
+    /// The buffer may still hold bytes from before a reset, so the memory is always cleared. The
+    /// block is untyped, so it is cleared with a single `write_bytes`, i.e. a `memset`, rather
+    /// than by writing `mem::zeroed` values.
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let allocated_ptr = self.allocate(layout)?;
+        unsafe { ptr::write_bytes(allocated_ptr.as_mut_ptr(), 0, layout.size()) };
+        Ok(allocated_ptr)
+    }
+
+    unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {}
+
+    unsafe fn grow(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let is_aligned = (old_ptr.as_ptr() as usize).is_multiple_of(new_layout.align());
+        if is_aligned
+            && self.resize_in_place(old_ptr.as_ptr(), old_layout.size(), new_layout.size())
+        {
+            return Ok(NonNull::slice_from_raw_parts(old_ptr, new_layout.size()));
+        }
+
+        let new_ptr = self.allocate(new_layout)?;
+        ptr::copy_nonoverlapping(old_ptr.as_ptr(), new_ptr.as_mut_ptr(), old_layout.size());
+        Ok(new_ptr)
+    }
+
+    /// The most recent allocation shrinks in place by moving the offset back, which frees the
+    /// tail for later allocations. Any other block is copied into fresh space, since its old
+    /// memory cannot be reclaimed anyway; the old space stays consumed until the arena is reset.
+    unsafe fn shrink(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let is_aligned = (old_ptr.as_ptr() as usize).is_multiple_of(new_layout.align());
+        if is_aligned
+            && self.resize_in_place(old_ptr.as_ptr(), old_layout.size(), new_layout.size())
+        {
+            return Ok(NonNull::slice_from_raw_parts(old_ptr, new_layout.size()));
+        }
+
+        let new_ptr = self.allocate(new_layout)?;
+        ptr::copy_nonoverlapping(old_ptr.as_ptr(), new_ptr.as_mut_ptr(), new_layout.size());
+        Ok(new_ptr)
+    }
+}
+
+unsafe impl<A: Allocator> GlobalAlloc for ArenaAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.allocate(layout)
+            .map_or(ptr::null_mut(), NonNull::as_mut_ptr)
+    }
+
+    /// The buffer may still hold bytes from before a reset, so the memory is always cleared.
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let allocated_ptr = self.alloc(layout);
+        if !allocated_ptr.is_null() {
+            ptr::write_bytes(allocated_ptr, 0, layout.size());
+        }
+        allocated_ptr
+    }
+
+    unsafe fn dealloc(&self, _: *mut u8, _: Layout) {}
+
+    /// The last allocation is resized in place by moving the offset. Any other block is copied
+    /// into fresh space, and its old memory stays consumed until the arena is reset.
+    unsafe fn realloc(&self, old_ptr: *mut u8, old_layout: Layout, new_size: usize) -> *mut u8 {
+        if self.resize_in_place(old_ptr, old_layout.size(), new_size) {
+            return old_ptr;
+        }
+
+        let new_layout = Layout::from_size_align_unchecked(new_size, old_layout.align());
+        let new_ptr = self.alloc(new_layout);
+        if !new_ptr.is_null() {
+            ptr::copy_nonoverlapping(old_ptr, new_ptr, old_layout.size().min(new_size));
+        }
+        new_ptr
+    }
+}
+
+/// An iterator over the allocations of an [`ArenaAllocator`] built with
+/// [`ArenaAllocator::with_headers`], produced by [`ArenaAllocator::allocated_regions`].
+///
+/// Each item is the start of an allocation and its size in bytes.
+#[derive(Debug, Clone)]
+pub struct ArenaRegionIter<'a> {
+    base: *const u8,
+    /// Offset from which the next header is searched.
+    cursor: usize,
+    /// Arena offset at which the walk stops.
+    end: usize,
+    _arena: PhantomData<&'a [MaybeUninit<u8>]>,
+}
+
+impl Iterator for ArenaRegionIter<'_> {
+    type Item = (*const u8, usize);
+
+    #[allow(clippy::cast_ptr_alignment)] // Headers and padding sit on word boundaries.
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.cursor < self.end {
+            let base = self.base as usize;
+            let header = align_up(base + self.cursor, HEADER_SIZE)? - base;
+            // SAFETY: every word between two allocations was written as padding or a header.
+            let size = unsafe { self.base.add(header).cast::<usize>().read() };
+            self.cursor = header + HEADER_SIZE;
+            if size != PADDING {
+                let start = self.cursor;
+                self.cursor += size;
+                return Some((unsafe { self.base.add(start) }, size));
+            }
+        }
+        None
+    }
+}
+
+/// A guard that rolls an [`ArenaAllocator`] back to the position it had when the guard was created.
+///
+/// The guard borrows the arena uniquely, so nothing allocated before the scope can be used while
+/// it is alive, and everything allocated through [`ArenaScope::arena`] borrows the guard.
+pub struct ArenaScope<'a, A: Allocator = Global> {
+    arena: &'a mut ArenaAllocator<A>,
+    marker: ArenaMarker,
+}
+
+impl<'a, A: Allocator> ArenaScope<'a, A> {
+    /// Saves the current position of `arena`, restoring it when the guard is dropped.
+    #[must_use]
+    pub fn new(arena: &'a mut ArenaAllocator<A>) -> Self {
+        let marker = arena.save();
+        Self { arena, marker }
+    }
+
+    /// Returns the arena this scope allocates from, borrowed for no longer than the scope.
+    #[must_use]
+    pub const fn arena(&self) -> &ArenaAllocator<A> {
+        self.arena
+    }
+}
+
+impl<A: Allocator> Drop for ArenaScope<'_, A> {
+    fn drop(&mut self) {
+        // SAFETY: allocations made through `arena` borrow `self`, so none of them outlive this
+        // point, and the unique borrow kept the arena from being reset below `marker`.
+        unsafe { self.arena.restore(self.marker) };
+    }
+}
+
+/// A scratch region of an [`ArenaAllocator`] that is released when the scope is dropped.
+///
+/// This is an [`ArenaScope`] whose allocations go through the scope itself via `Deref`, so
+/// everything allocated borrows the scope and the borrow checker rejects any attempt to let it
+/// escape. The scope borrows the arena uniquely for its whole lifetime, and dropping it restores
+/// the arena exactly like dropping the inner [`ArenaScope`].
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use mem_allocs::arena_allocator::{ArenaAllocator, ScopedArena};
+///
+/// fn sum_of_squares(arena: &mut ArenaAllocator, values: &[u64]) -> u64 {
+///     let scratch = ScopedArena::new(arena);
+///     let mut squares = Vec::with_capacity_in(values.len(), &*scratch);
+///     squares.extend(values.iter().map(|value| value * value));
+///     squares.iter().sum()
+/// }
+///
+/// let mut arena = ArenaAllocator::new(1024);
+/// assert_eq!(sum_of_squares(&mut arena, &[1, 2, 3]), 14);
+/// assert_eq!(arena.used(), 0);
+/// ```
+///
+/// A collection allocated from the arena before the scope cannot be used inside it:
+///
+/// ```compile_fail
+/// #![feature(allocator_api)]
+///
+/// use mem_allocs::arena_allocator::{ArenaAllocator, ScopedArena};
+///
+/// let mut arena = ArenaAllocator::new(1024);
+/// let mut outer = Vec::with_capacity_in(4, &arena);
+/// let scratch = ScopedArena::new(&mut arena);
+/// outer.push(1_u8);
+/// drop(scratch);
+/// ```
+pub struct ScopedArena<'a, A: Allocator = Global>(ArenaScope<'a, A>);
+
+impl<'a, A: Allocator> ScopedArena<'a, A> {
+    /// Saves the current position of `arena`, restoring it when the scope is dropped.
+    #[must_use]
+    pub fn new(arena: &'a mut ArenaAllocator<A>) -> Self {
+        Self(ArenaScope::new(arena))
+    }
+}
+
+impl<A: Allocator> Deref for ScopedArena<'_, A> {
+    type Target = ArenaAllocator<A>;
+
+    fn deref(&self) -> &ArenaAllocator<A> {
+        self.0.arena()
+    }
+}
+
+/// A [`fmt::Write`] sink that builds a string directly in an [`ArenaAllocator`].
+///
+/// The string is the most recent allocation of the arena and grows in place with every write, so
+/// formatting needs no intermediate buffer. Allocating anything else from the arena before
+/// calling [`ArenaWriter::finish`] ends the string: further writes fail with [`fmt::Error`].
+///
+/// ```
+/// use core::fmt::Write;
+///
+/// use mem_allocs::arena_allocator::{ArenaAllocator, ArenaWriter};
+///
+/// let arena = ArenaAllocator::new(64);
+/// let mut writer = ArenaWriter::new(&arena);
+/// write!(writer, "{} + {} = {}", 2, 3, 2 + 3).unwrap();
+/// assert_eq!(writer.finish(), Ok("2 + 3 = 5"));
+/// ```
+#[derive(Debug)]
+pub struct ArenaWriter<'a, A: Allocator = Global> {
+    arena: &'a ArenaAllocator<A>,
+    /// Offset of the string in the buffer, or `None` if not even an empty block fit.
+    start: Option<usize>,
+    len: usize,
+}
+
+impl<'a, A: Allocator> ArenaWriter<'a, A> {
+    /// Starts an empty string at the current position of `arena`.
+    #[must_use]
+    pub fn new(arena: &'a ArenaAllocator<A>) -> Self {
+        let start = arena
+            .allocate(Layout::new::<[u8; 0]>())
+            .ok()
+            .map(|block| block.as_mut_ptr() as usize - arena.base_ptr() as usize);
+        Self {
+            arena,
+            start,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of bytes written so far.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if nothing has been written yet.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the string written so far, which stays in the arena.
+    ///
+    /// The writer borrows the arena for `'a`, so the arena cannot be reset and the bytes cannot be
+    /// overwritten while the string is alive.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`str::Utf8Error`] if the written bytes are not valid UTF-8. Writes only ever copy
+    /// whole `&str`s, so this does not happen in practice.
+    pub fn finish(self) -> Result<&'a str, str::Utf8Error> {
+        let Some(start) = self.start else {
+            return Ok("");
+        };
+        str::from_utf8(unsafe { slice::from_raw_parts(self.arena.base_ptr().add(start), self.len) })
+    }
+}
+
+impl<A: Allocator> fmt::Write for ArenaWriter<'_, A> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if s.is_empty() {
+            return Ok(());
+        }
+        let start = self.start.ok_or(fmt::Error)?;
+        let new_len = self.len.checked_add(s.len()).ok_or(fmt::Error)?;
+        let block_ptr = unsafe { self.arena.base_ptr().add(start) };
+        if !self.arena.resize_in_place(block_ptr, self.len, new_len) {
+            return Err(fmt::Error);
+        }
+
+        unsafe { ptr::copy_nonoverlapping(s.as_ptr(), block_ptr.add(self.len), s.len()) };
+        self.len = new_len;
+        Ok(())
+    }
+}
+
+/// A bump-pointer memory allocator that borrows its backing buffer instead of owning it.
+///
+/// This brings the arena's bump logic to memory that already exists, such as a `static` buffer in
+/// an embedded program, without any heap allocation. The lifetime `'a` keeps the view from
+/// outliving the buffer. The view is neither `Send` nor `Sync`; wrap it and implement those traits
+/// yourself if you can guarantee the buffer is never accessed concurrently.
+#[derive(Debug)]
+pub struct ArenaAllocatorView<'a> {
+    buffer: *mut MaybeUninit<u8>,
+    capacity: usize,
+    offset: UnsafeCell<usize>,
+    _marker: PhantomData<&'a mut [MaybeUninit<u8>]>,
+}
+
+impl<'a> ArenaAllocatorView<'a> {
+    /// Creates a new arena view allocating from `buf`.
+    #[must_use]
+    pub const fn new(buf: &'a mut [MaybeUninit<u8>]) -> Self {
+        Self {
+            buffer: buf.as_mut_ptr(),
+            capacity: buf.len(),
+            offset: UnsafeCell::new(0),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the total size of the borrowed buffer in bytes.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of bytes consumed so far, including alignment padding.
+    #[must_use]
+    pub fn used(&self) -> usize {
+        unsafe { *self.offset.get() }
+    }
+
+    /// Returns the number of bytes still available for allocation.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.capacity - self.used()
+    }
+
+    /// Resets the view, making the whole buffer available again.
+    ///
+    /// Like [`ArenaAllocator::reset`], this takes `&mut self` so that nothing borrowing the view
+    /// can outlive the reset. Raw pointers handed out before the reset must no longer be used.
+    pub const fn reset(&mut self) {
+        *self.offset.get_mut() = 0;
+    }
+}
+
+unsafe impl Allocator for ArenaAllocatorView<'_> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let base = self.buffer.cast::<u8>();
+        let offset = self.used();
+        let start =
+            align_up(base as usize + offset, layout.align()).ok_or(AllocError)? - base as usize;
+        let end = start.checked_add(layout.size()).ok_or(AllocError)?;
+
+        if end > self.capacity {
+            return Err(AllocError);
+        }
+
+        unsafe { *self.offset.get() = end };
+        let allocated_ptr = unsafe { base.add(start) };
+
+        NonNull::new(ptr::slice_from_raw_parts_mut(allocated_ptr, layout.size())).ok_or(AllocError)
+    }
+
+    /// The borrowed buffer may hold bytes from before a reset, or from before the view was
+    /// created, so the memory is always cleared.
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let allocated_ptr = self.allocate(layout)?;
+        unsafe { ptr::write_bytes(allocated_ptr.as_mut_ptr(), 0, layout.size()) };
+        Ok(allocated_ptr)
+    }
+
+    unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {}
+}
+
+unsafe impl GlobalAlloc for ArenaAllocatorView<'_> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.allocate(layout)
+            .map_or(ptr::null_mut(), NonNull::as_mut_ptr)
+    }
+
+    unsafe fn dealloc(&self, _: *mut u8, _: Layout) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `ArenaAllocator` with a generic vector.
+    fn test_generic_vector_with_arena_allocator() {
+        let allocator = ArenaAllocator::new(1024);
+        let mut vector: Vec<usize, &ArenaAllocator> = Vec::with_capacity_in(100, &allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    /// Tests that allocations respect the requested alignment.
+    fn test_alignment() {
+        let arena = ArenaAllocator::new(256);
+        arena
+            .allocate(Layout::from_size_align(1, 1).unwrap())
+            .unwrap();
+        let aligned = arena
+            .allocate(Layout::from_size_align(8, 64).unwrap())
+            .unwrap();
+
+        assert_eq!(aligned.as_mut_ptr() as usize % 64, 0);
+    }
+
+    #[test]
+    /// Tests that allocation fails once the arena is exhausted and succeeds again after a reset.
+    fn test_out_of_memory_and_reset() {
+        let mut arena = ArenaAllocator::new(64);
+        let layout = Layout::from_size_align(64, 1).unwrap();
+
+        assert!(arena.allocate(layout).is_ok());
+        assert!(arena.allocate(layout).is_err());
+
+        arena.reset();
+        assert_eq!(arena.offset(), 0);
+        assert!(arena.allocate(layout).is_ok());
+    }
+
+    #[test]
+    /// Tests that growing the last allocation extends it in place.
+    fn test_grow_in_place() {
+        let arena = ArenaAllocator::new(128);
+        let old_layout = Layout::from_size_align(16, 8).unwrap();
+        let new_layout = Layout::from_size_align(64, 8).unwrap();
+
+        let block = arena.allocate(old_layout).unwrap();
+        let used = arena.offset();
+        let grown = unsafe {
+            arena
+                .grow(block.as_non_null_ptr(), old_layout, new_layout)
+                .unwrap()
+        };
+
+        assert_eq!(grown.as_mut_ptr(), block.as_mut_ptr());
+        assert_eq!(grown.len(), 64);
+        assert_eq!(arena.offset(), used + 48);
+    }
+
+    #[test]
+    /// Tests that growing an allocation that is not the last one copies it to a new block.
+    fn test_grow_by_copy() {
+        let arena = ArenaAllocator::new(128);
+        let old_layout = Layout::from_size_align(16, 1).unwrap();
+        let new_layout = Layout::from_size_align(32, 1).unwrap();
+
+        let block = arena.allocate(old_layout).unwrap();
+        unsafe { ptr::write_bytes(block.as_mut_ptr(), 0x7F, old_layout.size()) };
+        arena.allocate(old_layout).unwrap();
+
+        let grown = unsafe {
+            arena
+                .grow(block.as_non_null_ptr(), old_layout, new_layout)
+                .unwrap()
+        };
+
+        assert_ne!(grown.as_mut_ptr(), block.as_mut_ptr());
+        assert_eq!(arena.offset(), 64);
+        let bytes = unsafe { core::slice::from_raw_parts(grown.as_mut_ptr(), old_layout.size()) };
+        assert!(bytes.iter().all(|&byte| byte == 0x7F));
+    }
+
+    #[test]
+    /// Tests `GlobalAlloc::realloc` in place for the last block and by copy otherwise.
+    fn test_global_realloc() {
+        let arena = ArenaAllocator::with_alignment(128, 8);
+        let layout = Layout::from_size_align(16, 8).unwrap();
+
+        unsafe {
+            let block = GlobalAlloc::alloc(&arena, layout);
+            ptr::write_bytes(block, 0x5A, 16);
+
+            let grown = GlobalAlloc::realloc(&arena, block, layout, 32);
+            assert_eq!(grown, block);
+            assert_eq!(arena.offset(), 32);
+
+            let shrunk =
+                GlobalAlloc::realloc(&arena, grown, Layout::from_size_align(32, 8).unwrap(), 8);
+            assert_eq!(shrunk, block);
+            assert_eq!(arena.offset(), 8);
+
+            GlobalAlloc::alloc(&arena, layout);
+            let moved =
+                GlobalAlloc::realloc(&arena, shrunk, Layout::from_size_align(8, 8).unwrap(), 24);
+            assert_ne!(moved, block);
+            assert_eq!(arena.offset(), 48);
+            let bytes = core::slice::from_raw_parts(moved, 8);
+            assert!(bytes.iter().all(|&byte| byte == 0x5A));
+
+            assert!(GlobalAlloc::realloc(&arena, moved, layout, 256).is_null());
+        }
+    }
+
+    #[test]
+    /// Tests that `contains` only accepts pointers from the arena's own buffer.
+    fn test_contains() {
+        let arena = ArenaAllocator::new(64);
+        let other = ArenaAllocator::new(64);
+        let layout = Layout::from_size_align(16, 8).unwrap();
+
+        let block = arena.allocate(layout).unwrap();
+        let foreign = other.allocate(layout).unwrap();
+
+        assert!(arena.contains(block.as_non_null_ptr()));
+        assert!(!arena.contains(foreign.as_non_null_ptr()));
+        assert!(!other.contains(block.as_non_null_ptr()));
+    }
+
+    #[test]
+    /// Tests that `reset_to_zero` wipes the bytes handed out before the reset.
+    fn test_reset_to_zero() {
+        let mut arena = ArenaAllocator::new(64);
+        let layout = Layout::from_size_align(48, 8).unwrap();
+
+        let block = arena.allocate(layout).unwrap();
+        unsafe { ptr::write_bytes(block.as_mut_ptr(), 0xA5, 48) };
+        arena.reset_to_zero();
+        assert_eq!(arena.used(), 0);
+
+        let reused = arena.allocate(layout).unwrap();
+        assert_eq!(reused.as_mut_ptr(), block.as_mut_ptr());
+        let bytes = unsafe { core::slice::from_raw_parts(reused.as_mut_ptr(), 48) };
+        assert!(bytes.iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    /// Tests that `peak_usage` keeps the highest usage across resets until `reset_peak`.
+    fn test_peak_usage() {
+        let mut arena = ArenaAllocator::new(256);
+        let mut highest = 0;
+
+        for size in [48, 160, 96] {
+            arena
+                .allocate(Layout::from_size_align(size, 1).unwrap())
+                .unwrap();
+            highest = highest.max(arena.used());
+            assert_eq!(arena.peak_usage(), highest);
+            arena.reset();
+        }
+        assert_eq!(arena.peak_usage(), 160);
+
+        arena.reset_peak();
+        assert_eq!(arena.peak_usage(), 0);
+        arena
+            .allocate(Layout::from_size_align(32, 1).unwrap())
+            .unwrap();
+        assert_eq!(arena.peak_usage(), 32);
+    }
+
+    #[test]
+    /// Tests the one-line `Display` summary and the `Debug` counters.
+    fn test_formatting() {
+        use alloc::format;
+
+        let mut arena = ArenaAllocator::new(1024);
+        arena.allocate_array::<u8>(768).unwrap();
+        assert_eq!(format!("{arena}"), "Arena[768/1024 bytes (75%)]");
+        arena.reset();
+        assert_eq!(format!("{arena}"), "Arena[0/1024 bytes (0%)]");
+        assert_eq!(
+            format!("{arena:?}"),
+            "ArenaAllocator { used: 0, capacity: 1024, peak_usage: 768, headers: false, .. }"
+        );
+        assert_eq!(
+            format!("{}", ArenaAllocator::new(0)),
+            "Arena[0/0 bytes (0%)]"
+        );
+    }
+
+    #[test]
+    /// Tests that a clone is an empty arena of the same capacity, independent of the original.
+    fn test_clone_is_fresh() {
+        let arena = ArenaAllocator::new(64);
+        let layout = Layout::from_size_align(32, 1).unwrap();
+        let block = arena.allocate(layout).unwrap();
+        unsafe { ptr::write_bytes(block.as_mut_ptr(), 0x11, 32) };
+
+        let clone = arena.clone();
+        assert_eq!(clone.capacity(), arena.capacity());
+        assert_eq!(clone.used(), 0);
+
+        let cloned_block = clone.allocate(layout).unwrap();
+        assert!(!arena.contains(cloned_block.as_non_null_ptr()));
+        unsafe { ptr::write_bytes(cloned_block.as_mut_ptr(), 0x22, 32) };
+        clone.allocate(layout).unwrap();
+
+        assert_eq!(arena.used(), 32);
+        assert_eq!(clone.used(), 64);
+        let bytes = unsafe { core::slice::from_raw_parts(block.as_mut_ptr(), 32) };
+        assert!(bytes.iter().all(|&byte| byte == 0x11));
+    }
+
+    #[test]
+    /// Tests that `try_allocate_or_reset` only resets when the arena is full and fails for blocks
+    /// larger than the arena.
+    fn test_try_allocate_or_reset() {
+        let mut arena = ArenaAllocator::with_alignment(64, 8);
+        let layout = Layout::from_size_align(40, 8).unwrap();
+
+        let (first, reset) = arena.try_allocate_or_reset(layout).unwrap();
+        assert!(!reset);
+        let (second, reset) = arena.try_allocate_or_reset(layout).unwrap();
+        assert!(reset);
+        assert_eq!(first.as_mut_ptr(), second.as_mut_ptr());
+        assert_eq!(arena.used(), 40);
+
+        assert!(arena
+            .try_allocate_or_reset(Layout::from_size_align(65, 1).unwrap())
+            .is_err());
+        assert_eq!(arena.used(), 0);
+    }
+
+    #[test]
+    /// Tests an arena view over raw memory that Rust did not allocate through the arena.
+    fn test_from_raw_parts() {
+        let mut buffer = [0_u64; 8];
+        let arena = unsafe { ArenaAllocator::from_raw_parts(buffer.as_mut_ptr().cast(), 64) };
+        assert_eq!(arena.capacity(), 64);
+
+        let block = arena
+            .allocate(Layout::from_size_align(40, 8).unwrap())
+            .unwrap();
+        assert_eq!(block.as_mut_ptr(), buffer.as_mut_ptr().cast::<u8>());
+        assert!(arena
+            .allocate(Layout::from_size_align(32, 8).unwrap())
+            .is_err());
+    }
+
+    #[test]
+    /// Tests that the most recent allocation shrinks in place and gives its tail back.
+    fn test_shrink_in_place() {
+        let arena = ArenaAllocator::with_alignment(128, 8);
+        let old_layout = Layout::from_size_align(64, 8).unwrap();
+        let new_layout = Layout::from_size_align(16, 8).unwrap();
+
+        let block = arena.allocate(old_layout).unwrap();
+        unsafe { ptr::write_bytes(block.as_mut_ptr(), 0x5A, 64) };
+        let shrunk =
+            unsafe { arena.shrink(block.as_non_null_ptr(), old_layout, new_layout) }.unwrap();
+
+        assert_eq!(shrunk.as_mut_ptr(), block.as_mut_ptr());
+        assert_eq!(shrunk.len(), 16);
+        assert_eq!(arena.used(), 16);
+        assert!(unsafe { shrunk.as_ref() }.iter().all(|&byte| byte == 0x5A));
+    }
+
+    #[test]
+    /// Tests that a block below the top of the arena is shrunk by copying.
+    fn test_shrink_by_copy() {
+        let arena = ArenaAllocator::with_alignment(256, 8);
+        let old_layout = Layout::from_size_align(64, 8).unwrap();
+        let new_layout = Layout::from_size_align(16, 8).unwrap();
+
+        let block = arena.allocate(old_layout).unwrap();
+        unsafe { ptr::write_bytes(block.as_mut_ptr(), 0xA5, 64) };
+        arena.allocate(Layout::new::<u64>()).unwrap();
+        let shrunk =
+            unsafe { arena.shrink(block.as_non_null_ptr(), old_layout, new_layout) }.unwrap();
+
+        assert_ne!(shrunk.as_mut_ptr(), block.as_mut_ptr());
+        assert_eq!(arena.used(), 64 + 8 + 16);
+        assert!(unsafe { shrunk.as_ref() }.iter().all(|&byte| byte == 0xA5));
+    }
+
+    #[test]
+    /// Tests that `batch_allocate` serves a batch that fits and rejects one that may not.
+    fn test_batch_allocate() {
+        let arena = ArenaAllocator::new(256);
+        let layouts = [
+            Layout::from_size_align(16, 8).unwrap(),
+            Layout::from_size_align(3, 1).unwrap(),
+            Layout::from_size_align(32, 16).unwrap(),
+        ];
+
+        let blocks = arena.batch_allocate(&layouts).unwrap();
+        assert_eq!(blocks.len(), 3);
+        for (block, layout) in blocks.iter().zip(&layouts) {
+            assert_eq!(block.as_ptr() as usize % layout.align(), 0);
+        }
+        assert!(blocks[1].as_ptr() as usize >= blocks[0].as_ptr() as usize + 16);
+        assert!(blocks[2].as_ptr() as usize >= blocks[1].as_ptr() as usize + 3);
+
+        let used = arena.used();
+        let too_big = [Layout::from_size_align(64, 8).unwrap(); 4];
+        assert!(arena.batch_allocate(&too_big).is_err());
+        assert_eq!(arena.used(), used);
+    }
+
+    #[test]
+    /// Tests that an arena with headers reports every allocation and one without reports none.
+    fn test_allocated_regions() {
+        let mut arena = ArenaAllocator::with_headers(256);
+        let first = arena
+            .allocate(Layout::from_size_align(3, 1).unwrap())
+            .unwrap();
+        let second = arena
+            .allocate(Layout::from_size_align(16, 64).unwrap())
+            .unwrap();
+        let third = arena
+            .allocate(Layout::from_size_align(0, 2).unwrap())
+            .unwrap();
+
+        let regions: Vec<(*const u8, usize)> = arena.allocated_regions().collect();
+        assert_eq!(
+            regions,
+            [
+                (first.as_mut_ptr().cast_const(), 3),
+                (second.as_mut_ptr().cast_const(), 16),
+                (third.as_mut_ptr().cast_const(), 0),
+            ]
+        );
+
+        let grown = unsafe {
+            arena.grow(
+                third.as_non_null_ptr(),
+                Layout::from_size_align(0, 2).unwrap(),
+                Layout::from_size_align(10, 2).unwrap(),
+            )
+        }
+        .unwrap();
+        assert_eq!(grown.as_mut_ptr(), third.as_mut_ptr());
+        assert_eq!(arena.allocated_regions().last().unwrap().1, 10);
+
+        arena.reset();
+        assert_eq!(arena.allocated_regions().count(), 0);
+
+        let plain = ArenaAllocator::new(64);
+        plain.allocate(Layout::new::<u64>()).unwrap();
+        assert_eq!(plain.allocated_regions().count(), 0);
+    }
+
+    #[test]
+    /// Tests that huge alignments and sizes fail cleanly instead of overflowing.
+    fn test_alignment_overflow() {
+        assert_eq!(align_up(usize::MAX - 2, 8), None);
+        assert_eq!(align_up(usize::MAX - 7, 8), Some(usize::MAX - 7));
+
+        let arena = ArenaAllocator::new(64);
+        arena
+            .allocate(Layout::from_size_align(60, 1).unwrap())
+            .unwrap();
+
+        // The largest alignment a `Layout` accepts for a non-empty allocation.
+        let huge_align = Layout::from_size_align(1, 1 << (usize::BITS - 2)).unwrap();
+        assert!(arena.allocate(huge_align).is_err());
+        let huge_size = Layout::from_size_align(isize::MAX as usize, 1).unwrap();
+        assert!(arena.allocate(huge_size).is_err());
+        assert_eq!(arena.used(), 60);
+    }
+
+    #[test]
+    /// Tests that restoring a marker rolls back allocations made after it was saved.
+    fn test_save_and_restore() {
+        let arena = ArenaAllocator::new(128);
+        let layout = Layout::from_size_align(16, 1).unwrap();
+
+        arena.allocate(layout).unwrap();
+        let marker = arena.save();
+        let first = arena.allocate(layout).unwrap();
+        arena.allocate(layout).unwrap();
+        assert_eq!(arena.offset(), 48);
+
+        unsafe { arena.restore(marker) };
+        assert_eq!(arena.offset(), 16);
+
+        let second = arena.allocate(layout).unwrap();
+        assert_eq!(first.as_mut_ptr(), second.as_mut_ptr());
+    }
+
+    #[test]
+    /// Tests the typed `allocate_array` and `allocate_one` helpers.
+    fn test_typed_allocation() {
+        let arena = ArenaAllocator::with_alignment(64, 8);
+        arena
+            .allocate(Layout::from_size_align(1, 1).unwrap())
+            .unwrap();
+
+        let value = arena.allocate_one::<u64>().unwrap();
+        assert!(value.is_aligned());
+        unsafe { value.as_ptr().write(7) };
+
+        let values = arena.allocate_array::<u32>(4).unwrap();
+        assert_eq!(values.len(), 4);
+        assert!(values.cast::<u32>().is_aligned());
+        assert_eq!(arena.used(), 32);
+
+        assert!(arena.allocate_array::<u64>(usize::MAX).is_err());
+        assert!(arena.allocate_array::<u64>(8).is_err());
+        assert_eq!(unsafe { *value.as_ptr() }, 7);
+    }
+
+    #[test]
+    /// Tests that the backing buffer survives a round trip through `into_inner` and `from_vec`.
+    fn test_into_inner_round_trip() {
+        let arena = ArenaAllocator::new(64);
+        let block = arena
+            .allocate(Layout::from_size_align(16, 1).unwrap())
+            .unwrap();
+
+        let buffer = arena.into_inner();
+        assert_eq!(buffer.len(), 64);
+        assert_eq!(
+            buffer.as_ptr().cast::<u8>(),
+            block.as_mut_ptr().cast_const()
+        );
+
+        let arena = ArenaAllocator::from_vec(buffer);
+        assert_eq!(arena.used(), 0);
+        assert_eq!(arena.capacity(), 64);
+        let reused = arena
+            .allocate(Layout::from_size_align(16, 1).unwrap())
+            .unwrap();
+        assert_eq!(reused.as_mut_ptr(), block.as_mut_ptr());
+    }
+
+    #[test]
+    /// Tests that an arena can be moved to another thread and used there.
+    fn test_send_to_thread() {
+        extern crate std;
+
+        let arena = ArenaAllocator::new(64);
+        let used = std::thread::spawn(move || {
+            arena
+                .allocate(Layout::from_size_align(16, 1).unwrap())
+                .unwrap();
+            arena.used()
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(used, 16);
+    }
+
+    #[test]
+    /// Tests that `with_alignment` aligns the first allocation to a cache line and to a page,
+    /// including after a reset.
+    fn test_with_alignment() {
+        for min_align in [64, 4096] {
+            let mut arena = ArenaAllocator::with_alignment(100, min_align);
+            assert_eq!(arena.capacity(), 100);
+            assert!(arena.base_align() >= min_align);
+
+            let layout = Layout::from_size_align(100, 1).unwrap();
+            let block = arena.allocate(layout).unwrap();
+            assert_eq!(block.as_mut_ptr() as usize % min_align, 0);
+            assert!(arena.allocate(Layout::new::<u8>()).is_err());
+
+            arena.reset();
+            assert_eq!(arena.allocate(layout).unwrap(), block);
+        }
+    }
+
+    #[test]
+    /// Tests that `resize` grows the arena while keeping its contents and offset.
+    fn test_resize_grow() {
+        let mut arena = ArenaAllocator::new(16);
+        let layout = Layout::from_size_align(16, 1).unwrap();
+        let block = arena.allocate(layout).unwrap();
+        unsafe { ptr::write_bytes(block.as_mut_ptr(), 0xAB, 16) };
+        assert!(arena.allocate(layout).is_err());
+
+        arena.resize(64).unwrap();
+        assert_eq!(arena.capacity(), 64);
+        assert_eq!(arena.used(), 16);
+        let bytes = unsafe { core::slice::from_raw_parts(arena.base_ptr(), 16) };
+        assert!(bytes.iter().all(|&byte| byte == 0xAB));
+        assert!(arena.allocate(layout).is_ok());
+    }
+
+    #[test]
+    /// Tests that `resize` refuses to cut off live allocations but may shrink unused space.
+    fn test_resize_shrink() {
+        let mut arena = ArenaAllocator::new(64);
+        arena
+            .allocate(Layout::from_size_align(32, 1).unwrap())
+            .unwrap();
+
+        assert!(arena.resize(31).is_err());
+        assert_eq!(arena.capacity(), 64);
+        assert_eq!(arena.used(), 32);
+
+        arena.resize(32).unwrap();
+        assert_eq!(arena.capacity(), 32);
+        assert_eq!(arena.remaining(), 0);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "Miri aborts instead of failing allocations this large")]
+    /// Tests that a resize too large to allocate fails without touching the arena.
+    fn test_resize_overflow() {
+        let mut arena = ArenaAllocator::new(64);
+
+        assert!(arena.resize(usize::MAX).is_err());
+        assert!(arena.resize(isize::MAX as usize).is_err());
+        assert_eq!(arena.capacity(), 64);
+    }
+
+    #[test]
+    /// Tests that zeroed allocations are cleared even when reusing memory after a reset.
+    fn test_zeroed_after_reset() {
+        let mut arena = ArenaAllocator::new(64);
+        let layout = Layout::from_size_align(64, 1).unwrap();
+
+        let block = arena.allocate(layout).unwrap();
+        unsafe { ptr::write_bytes(block.as_mut_ptr(), 0xAB, 64) };
+
+        arena.reset();
+        let zeroed = arena.allocate_zeroed(layout).unwrap();
+        assert_eq!(zeroed.as_mut_ptr(), block.as_mut_ptr());
+        let bytes = unsafe { core::slice::from_raw_parts(zeroed.as_mut_ptr(), 64) };
+        assert!(bytes.iter().all(|&byte| byte == 0));
+
+        unsafe { ptr::write_bytes(zeroed.as_mut_ptr(), 0xCD, 64) };
+        arena.reset();
+        let zeroed = unsafe { GlobalAlloc::alloc_zeroed(&arena, layout) };
+        let bytes = unsafe { core::slice::from_raw_parts(zeroed, 64) };
+        assert!(bytes.iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    /// Tests that an `ArenaScope` restores the arena when dropped.
+    fn test_arena_scope() {
+        let mut arena = ArenaAllocator::new(128);
+        let layout = Layout::from_size_align(16, 1).unwrap();
+
+        arena.allocate(layout).unwrap();
+        {
+            let scope = ArenaScope::new(&mut arena);
+            scope.arena().allocate(layout).unwrap();
+            scope.arena().allocate(layout).unwrap();
+            assert_eq!(scope.arena().used(), 48);
+        }
+
+        assert_eq!(arena.used(), 16);
+    }
+
+    #[test]
+    /// Tests that a `ScopedArena` allocates through `Deref` and restores the arena when dropped.
+    fn test_scoped_arena() {
+        let mut arena = ArenaAllocator::new(1024);
+        let outer = ptr::from_mut(arena.write(1_u8).unwrap());
+
+        {
+            let scratch = ScopedArena::new(&mut arena);
+            let mut inner: Vec<usize, &ArenaAllocator> = Vec::with_capacity_in(10, &*scratch);
+            inner.extend(0..10);
+            assert_eq!(inner.iter().sum::<usize>(), 45);
+            assert!(scratch.used() > 1);
+        }
+
+        assert_eq!(arena.used(), 1);
+        assert_eq!(unsafe { *outer }, 1);
+    }
+
+    #[test]
+    /// Tests that an `ArenaAllocatorView` allocates from the borrowed buffer.
+    fn test_arena_allocator_view() {
+        let mut buffer = [MaybeUninit::<u8>::uninit(); 64];
+        let start = buffer.as_ptr() as usize;
+        let mut view = ArenaAllocator::from_slice(&mut buffer);
+        let layout = Layout::from_size_align(32, 1).unwrap();
+
+        let first = view.allocate(layout).unwrap();
+        let second = view.allocate(layout).unwrap();
+        assert_eq!(first.as_mut_ptr() as usize, start);
+        assert_eq!(second.as_mut_ptr() as usize, start + 32);
+        assert!(view.allocate(layout).is_err());
+
+        view.reset();
+        assert_eq!(view.remaining(), 64);
+    }
+
+    #[test]
+    /// Tests that `is_empty` and `is_full` track allocations and resets.
+    fn test_is_empty_and_is_full() {
+        let mut arena = ArenaAllocator::new(16);
+        assert!(arena.is_empty());
+        assert!(!arena.is_full());
+
+        arena
+            .allocate(Layout::from_size_align(8, 1).unwrap())
+            .unwrap();
+        assert!(!arena.is_empty());
+        assert!(!arena.is_full());
+
+        arena
+            .allocate(Layout::from_size_align(8, 1).unwrap())
+            .unwrap();
+        assert!(arena.is_full());
+
+        arena.reset();
+        assert!(arena.is_empty());
+        assert!(!arena.is_full());
+    }
+
+    #[test]
+    /// Tests that `is_aligned_to` reflects the alignment of the bump pointer.
+    fn test_is_aligned_to() {
+        let arena = ArenaAllocator::with_alignment(64, 16);
+        assert!(arena.is_aligned_to(16));
+
+        arena
+            .allocate(Layout::from_size_align(1, 1).unwrap())
+            .unwrap();
+        assert!(arena.is_aligned_to(1));
+        assert!(!arena.is_aligned_to(2));
+
+        arena
+            .allocate(Layout::from_size_align(7, 1).unwrap())
+            .unwrap();
+        assert!(arena.is_aligned_to(8));
+        assert!(!arena.is_aligned_to(16));
+    }
+
+    #[test]
+    /// Tests that `write_str_in` copies the string into the arena and fails when it does not fit.
+    fn test_write_str_in() {
+        let arena = ArenaAllocator::new(8);
+        let first = arena.write_str_in("abc").unwrap();
+        let second = arena.write_str_in("défg").unwrap();
+
+        assert_eq!(first, "abc");
+        assert_eq!(second, "défg");
+        assert!(arena.contains(NonNull::from(first.as_bytes()).cast()));
+        assert!(arena.write_str_in("!").is_err());
+    }
+
+    #[test]
+    /// Tests that an `ArenaWriter` grows its string in place across several writes.
+    fn test_arena_writer() {
+        use core::fmt::Write;
+
+        let arena = ArenaAllocator::new(64);
+        arena.allocate_one::<u8>().unwrap();
+        let mut writer = ArenaWriter::new(&arena);
+        let side = "left";
+        write!(writer, "{side}-{}", 40 + 2).unwrap();
+        writer.write_char('!').unwrap();
+
+        assert_eq!(writer.len(), 8);
+        assert_eq!(arena.used(), 9);
+        assert_eq!(writer.finish(), Ok("left-42!"));
+    }
+
+    #[test]
+    /// Tests that writes fail once the arena is full or another allocation follows the string.
+    fn test_arena_writer_errors() {
+        use core::fmt::Write;
+
+        let arena = ArenaAllocator::new(8);
+        let mut writer = ArenaWriter::new(&arena);
+        writer.write_str("12345").unwrap();
+        assert!(writer.write_str("6789").is_err());
+        assert_eq!(writer.len(), 5);
+
+        arena.allocate_one::<u8>().unwrap();
+        assert!(writer.write_str("6").is_err());
+        assert_eq!(writer.finish(), Ok("12345"));
+    }
+
+    #[test]
+    /// Tests that an `ArenaWriter` keeps the block header up to date in an arena with headers.
+    fn test_arena_writer_with_headers() {
+        use core::fmt::Write;
+
+        let arena = ArenaAllocator::with_headers(64);
+        let mut writer = ArenaWriter::new(&arena);
+        writer.write_str("hello").unwrap();
+        writer.write_str(", world").unwrap();
+
+        let regions: Vec<(*const u8, usize)> = arena.allocated_regions().collect();
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].1, 12);
+        assert_eq!(writer.finish(), Ok("hello, world"));
+    }
+
+    #[test]
+    /// Tests that arenas with the same capacity and usage are equal and that allocating keeps the
+    /// hash.
+    fn test_eq_and_hash() {
+        extern crate std;
+
+        use std::hash::{BuildHasher, RandomState};
+
+        let first = ArenaAllocator::with_alignment(64, 2);
+        let second = ArenaAllocator::with_alignment(64, 2);
+        first.allocate_array::<u8>(24).unwrap();
+        second.allocate_array::<u16>(12).unwrap();
+
+        assert_eq!(first, second);
+        let state = RandomState::new();
+        let hash = state.hash_one(&first);
+        assert_eq!(hash, state.hash_one(&second));
+
+        first.allocate_array::<u8>(8).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(state.hash_one(&first), hash);
+    }
+
+    #[test]
+    /// Tests that `contents_eq` tells apart equal arenas whose live bytes differ.
+    fn test_contents_eq() {
+        let first = ArenaAllocator::new(64);
+        let second = ArenaAllocator::new(64);
+        let layout = Layout::from_size_align(8, 1).unwrap();
+        first.allocate_zeroed(layout).unwrap();
+        let block = second.allocate_zeroed(layout).unwrap();
+        assert!(unsafe { first.contents_eq(&second) });
+
+        unsafe { block.as_mut_ptr().write(1) };
+        assert_eq!(first, second);
+        assert!(!unsafe { first.contents_eq(&second) });
+    }
+
+    #[test]
+    /// Tests that arenas differing in capacity or usage are not equal.
+    fn test_not_equal() {
+        let arena = ArenaAllocator::new(64);
+        assert_ne!(arena, ArenaAllocator::new(32));
+
+        let other = ArenaAllocator::new(64);
+        other.allocate_one::<u8>().unwrap();
+        assert_ne!(arena, other);
+    }
+
+    #[test]
+    /// Tests that values written with `write` read back correctly and stay independent.
+    fn test_write() {
+        let arena = ArenaAllocator::with_alignment(64, 8);
+        let number = arena.write(0x1234_5678_u32).unwrap();
+        let pair = arena.write((1_u8, 2_u64)).unwrap();
+        *number += 1;
+        pair.1 *= 10;
+
+        assert_eq!(*number, 0x1234_5679);
+        assert_eq!(*pair, (1, 20));
+        assert_eq!(arena.used(), 24);
+        assert!(arena.write([0_u8; 64]).is_err());
+    }
+
+    #[test]
+    /// Tests that `write_slice` copies the values, including an empty slice.
+    fn test_write_slice() {
+        let arena = ArenaAllocator::new(64);
+        let source = [1_u32, 2, 3, 4];
+        let copy = arena.write_slice(&source).unwrap();
+        copy[3] = 40;
+
+        assert_eq!(copy, [1, 2, 3, 40]);
+        assert_eq!(source, [1, 2, 3, 4]);
+        assert!(arena.write_slice::<u64>(&[]).unwrap().is_empty());
+        assert!(arena.write_slice(&[0_u64; 8]).is_err());
+    }
+
+    #[test]
+    /// Tests that `allocate_zeroed` on a view clears bytes left over from before a reset.
+    fn test_view_zeroed_after_reset() {
+        let mut buffer = [MaybeUninit::new(0xAB_u8); 32];
+        let mut view = ArenaAllocator::from_slice(&mut buffer);
+        let layout = Layout::from_size_align(32, 1).unwrap();
+
+        let zeroed = view.allocate_zeroed(layout).unwrap();
+        unsafe { ptr::write_bytes(zeroed.as_mut_ptr(), 0xCD, 32) };
+        view.reset();
+
+        let zeroed = view.allocate_zeroed(layout).unwrap();
+        let bytes = unsafe { core::slice::from_raw_parts(zeroed.as_mut_ptr(), 32) };
+        assert!(bytes.iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    /// Tests that `can_allocate` predicts the out-of-memory condition and recovers after a reset.
+    fn test_can_allocate() {
+        let mut arena = ArenaAllocator::with_alignment(64, 8);
+        let layout = Layout::new::<u64>();
+
+        while arena.can_allocate(layout) {
+            arena.allocate(layout).unwrap();
+        }
+        assert_eq!(arena.used(), 64);
+        assert!(arena.allocate(layout).is_err());
+        assert!(arena.can_allocate(Layout::new::<()>()));
+
+        arena.reset();
+        assert!(arena.can_allocate(layout));
+        assert_eq!(arena.used(), 0);
+    }
+
+    #[test]
+    /// Tests that `can_allocate_all` simulates the padding between consecutive blocks.
+    fn test_can_allocate_all() {
+        let arena = ArenaAllocator::with_alignment(32, 8);
+        let byte = Layout::new::<u8>();
+        let word = Layout::new::<u64>();
+
+        // 1 byte, 7 bytes of padding, 8, 1, 7 more bytes of padding and 8 make exactly 32.
+        assert!(arena.can_allocate_all(&[byte, word, byte, word]));
+        assert!(!arena.can_allocate_all(&[byte, word, byte, word, byte]));
+        assert!(arena.can_allocate_all(&[]));
+        assert_eq!(arena.used(), 0);
+
+        let arena = ArenaAllocator {
+            headers: true,
+            ..ArenaAllocator::with_alignment(32, 8)
+        };
+        assert!(arena.can_allocate_all(&[word, word]));
+        assert!(!arena.can_allocate_all(&[word, word, byte]));
+    }
+
+    #[test]
+    /// Tests that `align_next_allocation` pads the offset so the next block is aligned.
+    fn test_align_next_allocation() {
+        let arena = ArenaAllocator::new(256);
+        let first = arena.allocate_one::<u8>().unwrap();
+        let before = arena.offset();
+        arena.align_next_allocation(64).unwrap();
+        let second = arena.allocate_one::<u8>().unwrap();
+
+        assert!(arena.offset() > before);
+        assert_ne!(first, second);
+        assert_eq!(second.as_ptr() as usize % 64, 0);
+
+        // Already aligned, so nothing changes.
+        arena.align_next_allocation(1).unwrap();
+        assert_eq!(
+            arena.offset(),
+            (second.as_ptr() as usize - arena.base_ptr() as usize) + 1
+        );
+    }
+
+    #[test]
+    /// Tests that `EMPTY` and other zero-capacity arenas refuse every layout.
+    fn test_empty_refuses_everything() {
+        for arena in [
+            ArenaAllocator::EMPTY,
+            ArenaAllocator::new(0),
+            ArenaAllocator::default(),
+        ] {
+            assert_eq!(arena.capacity(), 0);
+            for layout in [
+                Layout::new::<()>(),
+                Layout::new::<u8>(),
+                Layout::from_size_align(0, 64).unwrap(),
+            ] {
+                assert!(arena.allocate(layout).is_err());
+                assert!(!arena.can_allocate(layout));
+            }
+            assert_eq!(arena.write_str_in(""), Err(AllocError));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "c_allocator")]
+    /// Tests that an arena backed by the `CAllocator` allocates, resets and clones like one backed
+    /// by the global allocator.
+    fn test_new_with_allocator() {
+        use crate::c_allocator::CAllocator;
+
+        let mut arena = ArenaAllocator::new_with_allocator(1024, CAllocator);
+        assert_eq!(arena.capacity(), 1024);
+        assert_eq!(arena.allocator(), &CAllocator);
+
+        let mut vector: Vec<u32, &ArenaAllocator<CAllocator>> = Vec::new_in(&arena);
+        vector.extend(0..100);
+        assert!(vector.iter().copied().eq(0..100));
+        drop(vector);
+        assert!(arena.used() >= 400);
+
+        let clone = arena.clone();
+        assert_eq!(clone.capacity(), 1024);
+        assert_eq!(clone.used(), 0);
+
+        arena.reset();
+        assert_eq!(arena.used(), 0);
+        assert_eq!(arena.into_inner().len(), 1024);
+    }
+
+    #[test]
+    /// Tests that `align_next_allocation` rejects invalid alignments and padding beyond capacity.
+    fn test_align_next_allocation_errors() {
+        let arena = ArenaAllocator::with_alignment(64, 128);
+        arena.allocate_one::<u8>().unwrap();
+
+        assert!(arena.align_next_allocation(3).is_err());
+        assert!(arena.align_next_allocation(0).is_err());
+        assert!(arena.align_next_allocation(128).is_err());
+        assert_eq!(arena.offset(), 1);
+        arena.align_next_allocation(64).unwrap();
+        assert!(arena.is_full());
+    }
+
+    #[test]
+    /// Tests that `align_next_allocation` keeps the headers of an arena walkable.
+    fn test_align_next_allocation_with_headers() {
+        let arena = ArenaAllocator::with_headers(512);
+        arena.allocate_one::<u8>().unwrap();
+        arena.align_next_allocation(128).unwrap();
+        let block = arena.allocate_one::<u32>().unwrap();
+
+        assert_eq!(block.as_ptr() as usize % 128, 0);
+        let sizes: Vec<usize> = arena.allocated_regions().map(|(_, size)| size).collect();
+        assert_eq!(sizes, [1, 4]);
+    }
+
+    #[test]
+    /// Tests that `AsRef` and `AsMut` expose exactly the live bytes.
+    fn test_as_ref_and_as_mut() {
+        let mut arena = ArenaAllocator::with_alignment(64, 4);
+        let value = arena.allocate_one::<u32>().unwrap();
+        unsafe { value.as_ptr().write(42) };
+
+        let live: &[MaybeUninit<u8>] = arena.as_ref();
+        assert_eq!(live.len(), 4);
+        let bytes: [MaybeUninit<u8>; 4] = live.try_into().unwrap();
+        let read_back =
+            u32::from_ne_bytes(unsafe { mem::transmute::<[MaybeUninit<u8>; 4], [u8; 4]>(bytes) });
+        assert_eq!(read_back, 42);
+
+        arena
+            .as_mut()
+            .copy_from_slice(&7_u32.to_ne_bytes().map(MaybeUninit::new));
+        assert_eq!(unsafe { value.as_ptr().read() }, 7);
+    }
+
+    #[test]
+    /// Tests that restoring a snapshot rolls back both new allocations and changed contents.
+    fn test_snapshot_rollback() {
+        let mut arena = ArenaAllocator::new(64);
+        arena.write_slice(&[1_u8, 2, 3, 4]).unwrap();
+        let snapshot = arena.snapshot();
+        assert_eq!(snapshot.offset(), 4);
+
+        arena.as_mut().fill(MaybeUninit::new(0xFF));
+        arena.allocate_array::<u8>(32).unwrap();
+        assert_eq!(arena.used(), 36);
+
+        arena.restore_snapshot(snapshot.clone());
+        assert_eq!(arena.used(), 4);
+        let live = arena
+            .as_ref()
+            .iter()
+            .map(|byte| unsafe { byte.assume_init() });
+        assert!(live.eq([1, 2, 3, 4]));
+        assert_eq!(arena.peak_usage(), 36);
+
+        // A clone of the snapshot can roll back again.
+        arena.as_mut()[0] = MaybeUninit::new(9);
+        arena.restore_snapshot(snapshot);
+        assert_eq!(unsafe { arena.as_ref()[0].assume_init() }, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "snapshot of 32 bytes does not fit in an arena of 16 bytes")]
+    /// Tests that a snapshot larger than the arena is rejected.
+    fn test_snapshot_too_large() {
+        let large = ArenaAllocator::new(64);
+        large.allocate_array::<u8>(32).unwrap();
+        let mut small = ArenaAllocator::new(16);
+        small.restore_snapshot(large.snapshot());
+    }
+}
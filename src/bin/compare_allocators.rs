@@ -0,0 +1,143 @@
+//! Runs the same random sequence of allocations and deallocations against several allocators and
+//! prints the timings as a Markdown table.
+//!
+//! ```text
+//! cargo run --release --bin compare_allocators --features arena_allocator,c_allocator
+//! ```
+
+#![feature(allocator_api, slice_ptr_get)]
+
+use std::{
+    alloc::{Allocator, Layout},
+    hint::black_box,
+    ptr::NonNull,
+    time::{Duration, Instant},
+};
+
+use mem_allocs::{
+    arena_allocator::ArenaAllocator,
+    c_allocator::{CAllocator, RawCAllocator},
+};
+
+/// Number of operations in the workload.
+const OPERATIONS: usize = 10_000;
+
+/// Seed of the random number generator, so every allocator sees the same workload.
+const SEED: u64 = 0x2545_F491_4F6C_DD1D;
+
+/// Largest block size in the workload, in bytes.
+const MAX_SIZE: usize = 256;
+
+/// One step of the workload.
+#[derive(Clone, Copy)]
+enum Operation {
+    /// Allocates a block with the given layout.
+    Allocate(Layout),
+    /// Frees the live block selected by this value modulo the number of live blocks.
+    Free(usize),
+}
+
+/// Total time and number of operations of one kind.
+#[derive(Default)]
+struct Timing {
+    total: Duration,
+    count: u32,
+}
+
+impl Timing {
+    /// Adds the time elapsed since `start`.
+    fn record(&mut self, start: Instant) {
+        self.total += start.elapsed();
+        self.count += 1;
+    }
+
+    /// Returns the mean time per operation.
+    fn mean(&self) -> Duration {
+        self.total.checked_div(self.count).unwrap_or_default()
+    }
+}
+
+/// A xorshift64 generator; quality is irrelevant, reproducibility is not.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        usize::try_from(self.0 % (1 << 32)).unwrap_or_default()
+    }
+}
+
+/// Builds the workload: roughly three allocations for every two frees, never freeing more blocks
+/// than are live.
+fn workload() -> Vec<Operation> {
+    let mut rng = Rng(SEED);
+    let mut live = 0_usize;
+    (0..OPERATIONS)
+        .map(|_| {
+            if live == 0 || rng.next() % 5 < 3 {
+                live += 1;
+                let size = rng.next() % MAX_SIZE + 1;
+                Operation::Allocate(Layout::from_size_align(size, 8).unwrap())
+            } else {
+                live -= 1;
+                Operation::Free(rng.next())
+            }
+        })
+        .collect()
+}
+
+/// Runs `operations` against `allocator`, returning the allocation and deallocation timings.
+/// Blocks still live at the end are freed outside the measurement.
+fn run<A: Allocator>(allocator: &A, operations: &[Operation]) -> (Timing, Timing) {
+    let mut live: Vec<(NonNull<u8>, Layout)> = Vec::with_capacity(operations.len());
+    let mut allocations = Timing::default();
+    let mut deallocations = Timing::default();
+
+    for &operation in operations {
+        match operation {
+            Operation::Allocate(layout) => {
+                let start = Instant::now();
+                let block = allocator.allocate(layout);
+                allocations.record(start);
+                live.push((black_box(block).unwrap().as_non_null_ptr(), layout));
+            }
+            Operation::Free(selector) => {
+                let (block, layout) = live.swap_remove(selector % live.len());
+                let start = Instant::now();
+                unsafe { allocator.deallocate(black_box(block), layout) };
+                deallocations.record(start);
+            }
+        }
+    }
+
+    for (block, layout) in live {
+        unsafe { allocator.deallocate(block, layout) };
+    }
+    (allocations, deallocations)
+}
+
+fn main() {
+    let operations = workload();
+    // Arena memory is never reused, so it needs room for every allocation of the workload.
+    let arena = ArenaAllocator::new(OPERATIONS * MAX_SIZE + OPERATIONS * 8);
+
+    let results = [
+        ("ArenaAllocator", run(&arena, &operations)),
+        ("CAllocator", run(&CAllocator, &operations)),
+        ("RawCAllocator", run(&RawCAllocator, &operations)),
+    ];
+
+    println!("| Allocator | Total alloc ns | Mean alloc ns | Total dealloc ns | Mean dealloc ns |");
+    println!("|---|---:|---:|---:|---:|");
+    for (name, (allocations, deallocations)) in results {
+        println!(
+            "| {name} | {} | {} | {} | {} |",
+            allocations.total.as_nanos(),
+            allocations.mean().as_nanos(),
+            deallocations.total.as_nanos(),
+            deallocations.mean().as_nanos(),
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    cell::{Cell, UnsafeCell},
+    ptr::NonNull,
+};
+
+use alloc::{alloc::handle_alloc_error, vec::Vec};
+
+use crate::block_buffer::{block_align, buffer_layout};
+
+/// Number of blocks tracked by each word of the bitmap.
+const BITS: usize = u64::BITS as usize;
+
+/// A fixed-size block allocator that tracks free blocks in a bitmap instead of a linked list.
+///
+/// Every bit records whether one block is in use. Finding a free block scans the bitmap a word at
+/// a time, which touches far less memory than chasing free-list pointers spread over the buffer.
+/// Blocks are aligned to the largest power of two dividing the block size, up to a page.
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct BitmapAllocator {
+    base: NonNull<u8>,
+    block_size: usize,
+    block_count: usize,
+    bitmap: UnsafeCell<Vec<u64>>,
+    /// Index of the first bitmap word that may have a free bit; every word before it is full.
+    first_free_word: Cell<usize>,
+    used_blocks: Cell<usize>,
+}
+
+impl BitmapAllocator {
+    /// Creates a new allocator managing `block_count` blocks of `block_size` bytes each.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_size` or `block_count` is zero, or if the buffer size overflows.
+    #[must_use]
+    pub fn new(block_size: usize, block_count: usize) -> Self {
+        assert!(block_size > 0, "block size must not be zero");
+        assert!(block_count > 0, "block count must not be zero");
+
+        let layout = buffer_layout(block_size, block_count);
+        let base = NonNull::new(unsafe { alloc::alloc::alloc(layout) })
+            .unwrap_or_else(|| handle_alloc_error(layout));
+
+        // Bits past the last block are marked as used so the scan never hands them out.
+        let mut bitmap = alloc::vec![0; block_count.div_ceil(BITS)];
+        let tail_bits = block_count % BITS;
+        if tail_bits != 0 {
+            if let Some(last) = bitmap.last_mut() {
+                *last = u64::MAX << tail_bits;
+            }
+        }
+
+        Self {
+            base,
+            block_size,
+            block_count,
+            bitmap: UnsafeCell::new(bitmap),
+            first_free_word: Cell::new(0),
+            used_blocks: Cell::new(0),
+        }
+    }
+
+    /// Returns the size of every block in bytes.
+    #[must_use]
+    pub const fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Returns the total number of blocks.
+    #[must_use]
+    pub const fn capacity_blocks(&self) -> usize {
+        self.block_count
+    }
+
+    /// Returns the number of blocks currently in use.
+    #[must_use]
+    pub const fn used_blocks(&self) -> usize {
+        self.used_blocks.get()
+    }
+}
+
+unsafe impl Allocator for BitmapAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if layout.size() > self.block_size || layout.align() > block_align(self.block_size) {
+            return Err(AllocError);
+        }
+
+        let bitmap = unsafe { &mut *self.bitmap.get() };
+        let (word_index, word) = bitmap
+            .iter_mut()
+            .enumerate()
+            .skip(self.first_free_word.get())
+            .find(|(_, word)| **word != u64::MAX)
+            .ok_or(AllocError)?;
+        self.first_free_word.set(word_index);
+        let bit = word.trailing_ones() as usize;
+        *word |= 1 << bit;
+        self.used_blocks.set(self.used_blocks.get() + 1);
+
+        let block = unsafe { self.base.add((word_index * BITS + bit) * self.block_size) };
+        Ok(NonNull::slice_from_raw_parts(block, layout.size()))
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, _: Layout) {
+        let index =
+            (allocated_ptr.as_ptr() as usize - self.base.as_ptr() as usize) / self.block_size;
+        let bitmap = &mut *self.bitmap.get();
+        debug_assert!(
+            bitmap[index / BITS] & (1 << (index % BITS)) != 0,
+            "double free"
+        );
+        bitmap[index / BITS] &= !(1 << (index % BITS));
+        self.first_free_word
+            .set(self.first_free_word.get().min(index / BITS));
+        self.used_blocks.set(self.used_blocks.get() - 1);
+    }
+}
+
+impl Drop for BitmapAllocator {
+    fn drop(&mut self) {
+        let layout = buffer_layout(self.block_size, self.block_count);
+        unsafe { alloc::alloc::dealloc(self.base.as_ptr(), layout) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `BitmapAllocator` with a generic vector that fits in a single block.
+    fn test_generic_vector_with_bitmap_allocator() {
+        let allocator = BitmapAllocator::new(1024, 4);
+        let mut vector: Vec<usize, &BitmapAllocator> = Vec::with_capacity_in(100, &allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        assert_eq!(allocator.used_blocks(), 1);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+        assert_eq!(allocator.used_blocks(), 0);
+    }
+
+    #[test]
+    /// Tests that every block is handed out exactly once across several bitmap words, and that
+    /// freed blocks are reused.
+    fn test_exhaust_and_reuse() {
+        let allocator = BitmapAllocator::new(32, 130);
+        let layout = Layout::from_size_align(32, 32).unwrap();
+
+        let mut blocks: Vec<usize> = (0..130)
+            .map(|_| allocator.allocate(layout).unwrap().as_mut_ptr() as usize)
+            .collect();
+        assert!(allocator.allocate(layout).is_err());
+        assert_eq!(allocator.used_blocks(), allocator.capacity_blocks());
+
+        blocks.sort_unstable();
+        blocks.dedup();
+        assert_eq!(blocks.len(), 130);
+        assert!(blocks.iter().all(|block| block % 32 == 0));
+
+        let freed = NonNull::new(blocks[70] as *mut u8).unwrap();
+        unsafe { allocator.deallocate(freed, layout) };
+        assert_eq!(allocator.allocate(layout).unwrap().as_non_null_ptr(), freed);
+    }
+
+    #[test]
+    /// Tests that requests larger or more aligned than a block are rejected.
+    fn test_rejects_unfit_layouts() {
+        let allocator = BitmapAllocator::new(48, 8);
+
+        assert!(allocator
+            .allocate(Layout::from_size_align(49, 1).unwrap())
+            .is_err());
+        assert!(allocator
+            .allocate(Layout::from_size_align(16, 32).unwrap())
+            .is_err());
+        assert!(allocator
+            .allocate(Layout::from_size_align(48, 16).unwrap())
+            .is_ok());
+    }
+}
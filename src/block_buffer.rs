@@ -0,0 +1,33 @@
+//! Layout of the heap buffer shared by the fixed-size block allocators,
+//! [`BitmapAllocator`](crate::bitmap_allocator::BitmapAllocator) and
+//! [`PoolAllocator`](crate::pool_allocator::PoolAllocator).
+
+use core::alloc::Layout;
+
+/// Largest alignment the backing buffer is given, and therefore the largest alignment a block
+/// allocator can satisfy.
+pub const MAX_BASE_ALIGN: usize = 4096;
+
+/// Returns the alignment every block of `block_size` bytes is guaranteed to have in a buffer laid
+/// out by [`buffer_layout`]: the largest power of two dividing the block size, up to
+/// [`MAX_BASE_ALIGN`].
+pub const fn block_align(block_size: usize) -> usize {
+    let align = 1 << block_size.trailing_zeros();
+    if align < MAX_BASE_ALIGN {
+        align
+    } else {
+        MAX_BASE_ALIGN
+    }
+}
+
+/// Returns the layout of a buffer holding `block_count` blocks of `block_size` bytes.
+///
+/// # Panics
+///
+/// Panics if the buffer size overflows.
+pub fn buffer_layout(block_size: usize, block_count: usize) -> Layout {
+    let size = block_size
+        .checked_mul(block_count)
+        .expect("buffer size overflows");
+    Layout::from_size_align(size, block_align(block_size)).expect("buffer size overflows")
+}
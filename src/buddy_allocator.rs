@@ -0,0 +1,313 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    cell::UnsafeCell,
+    mem,
+    ptr::NonNull,
+};
+
+use alloc::{alloc::handle_alloc_error, vec::Vec};
+
+/// Largest alignment the backing buffer is guaranteed to have, and therefore the largest
+/// alignment a [`BuddyAllocator`] can satisfy.
+const MAX_BASE_ALIGN: usize = 4096;
+
+/// Intrusive doubly-linked list node stored inside every free block.
+#[derive(Debug)]
+struct FreeBlock {
+    next: Option<NonNull<Self>>,
+    prev: Option<NonNull<Self>>,
+}
+
+/// The free list holding all free blocks of one order.
+#[derive(Debug, Default, Clone, Copy)]
+struct FreeList {
+    head: Option<NonNull<FreeBlock>>,
+    len: usize,
+}
+
+/// Book-keeping shared by all operations of a [`BuddyAllocator`].
+#[derive(Debug)]
+struct BuddyState {
+    base: NonNull<u8>,
+    min_order: usize,
+    /// Free lists indexed by order, where a block of order `k` is `2^k` bytes.
+    free_lists: Vec<FreeList>,
+    /// For every minimum-size block, `order + 1` if a free block of that order starts there and
+    /// `0` otherwise.
+    block_orders: Vec<u8>,
+}
+
+impl BuddyState {
+    const fn block_at(&self, offset: usize) -> NonNull<FreeBlock> {
+        unsafe { self.base.add(offset).cast::<FreeBlock>() }
+    }
+
+    fn offset_of(&self, block: NonNull<FreeBlock>) -> usize {
+        block.as_ptr() as usize - self.base.as_ptr() as usize
+    }
+
+    fn is_free(&self, offset: usize, order: usize) -> bool {
+        usize::from(self.block_orders[offset >> self.min_order]) == order + 1
+    }
+
+    unsafe fn push(&mut self, order: usize, offset: usize) {
+        let block = self.block_at(offset);
+        let list = &mut self.free_lists[order];
+        block.write(FreeBlock {
+            next: list.head,
+            prev: None,
+        });
+        if let Some(mut head) = list.head {
+            head.as_mut().prev = Some(block);
+        }
+        list.head = Some(block);
+        list.len += 1;
+        // Orders are below `usize::BITS`, so they always fit in a byte.
+        #[allow(clippy::cast_possible_truncation)]
+        let tag = order as u8 + 1;
+        self.block_orders[offset >> self.min_order] = tag;
+    }
+
+    unsafe fn remove(&mut self, order: usize, offset: usize) {
+        let block = self.block_at(offset);
+        let FreeBlock { next, prev } = block.read();
+        let list = &mut self.free_lists[order];
+        match prev {
+            Some(mut prev) => prev.as_mut().next = next,
+            None => list.head = next,
+        }
+        if let Some(mut next) = next {
+            next.as_mut().prev = prev;
+        }
+        list.len -= 1;
+        self.block_orders[offset >> self.min_order] = 0;
+    }
+
+    unsafe fn pop(&mut self, order: usize) -> Option<usize> {
+        let offset = self.offset_of(self.free_lists[order].head?);
+        self.remove(order, offset);
+        Some(offset)
+    }
+}
+
+/// A buddy-system memory allocator managing power-of-two sized blocks.
+///
+/// Requests are rounded up to the next power of two. Larger free blocks are split in halves until
+/// a block of the right size is produced, and freed blocks are merged with their buddy whenever
+/// the buddy is free as well, so both allocation and deallocation take O(log N) steps.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug)]
+pub struct BuddyAllocator {
+    capacity: usize,
+    max_order: usize,
+    state: UnsafeCell<BuddyState>,
+}
+
+impl BuddyAllocator {
+    /// Creates a new buddy allocator managing `capacity` bytes, handing out blocks of at least
+    /// `min_block_size` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` or `min_block_size` is not a power of two, if `min_block_size` is
+    /// smaller than two pointers, or if `min_block_size` is larger than `capacity`.
+    #[must_use]
+    pub fn new(capacity: usize, min_block_size: usize) -> Self {
+        assert!(
+            capacity.is_power_of_two(),
+            "capacity must be a power of two"
+        );
+        assert!(
+            min_block_size.is_power_of_two(),
+            "minimum block size must be a power of two"
+        );
+        assert!(
+            min_block_size >= mem::size_of::<FreeBlock>(),
+            "minimum block size must fit the free-list node"
+        );
+        assert!(
+            min_block_size <= capacity,
+            "minimum block size must not exceed the capacity"
+        );
+
+        let layout = Self::buffer_layout(capacity);
+        let base = NonNull::new(unsafe { alloc::alloc::alloc(layout) })
+            .unwrap_or_else(|| handle_alloc_error(layout));
+
+        let min_order = min_block_size.trailing_zeros() as usize;
+        let max_order = capacity.trailing_zeros() as usize;
+        let mut state = BuddyState {
+            base,
+            min_order,
+            free_lists: alloc::vec![FreeList::default(); max_order + 1],
+            block_orders: alloc::vec![0; capacity >> min_order],
+        };
+        unsafe { state.push(max_order, 0) };
+
+        Self {
+            capacity,
+            max_order,
+            state: UnsafeCell::new(state),
+        }
+    }
+
+    /// Returns the total number of bytes managed by the allocator.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of free blocks of `2^order` bytes.
+    #[must_use]
+    pub fn free_blocks_at_order(&self, order: usize) -> usize {
+        let state = unsafe { &*self.state.get() };
+        state.free_lists.get(order).map_or(0, |list| list.len)
+    }
+
+    fn buffer_layout(capacity: usize) -> Layout {
+        Layout::from_size_align(capacity, capacity.min(MAX_BASE_ALIGN))
+            .expect("capacity must form a valid layout")
+    }
+
+    /// Returns the order of the smallest block able to hold `layout`.
+    fn order_for(&self, layout: Layout) -> Option<usize> {
+        let state = unsafe { &*self.state.get() };
+        let size = layout
+            .size()
+            .max(layout.align())
+            .checked_next_power_of_two()?;
+        let order = (size.trailing_zeros() as usize).max(state.min_order);
+        (order <= self.max_order).then_some(order)
+    }
+}
+
+unsafe impl Allocator for BuddyAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if layout.align() > self.capacity.min(MAX_BASE_ALIGN) {
+            return Err(AllocError);
+        }
+        let order = self.order_for(layout).ok_or(AllocError)?;
+        let state = unsafe { &mut *self.state.get() };
+
+        let mut current_order = (order..=self.max_order)
+            .find(|&candidate| state.free_lists[candidate].len > 0)
+            .ok_or(AllocError)?;
+        let offset = unsafe { state.pop(current_order) }.ok_or(AllocError)?;
+
+        while current_order > order {
+            current_order -= 1;
+            unsafe { state.push(current_order, offset + (1 << current_order)) };
+        }
+
+        let allocated_ptr = unsafe { state.base.add(offset) };
+        Ok(NonNull::slice_from_raw_parts(allocated_ptr, layout.size()))
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        let Some(mut order) = self.order_for(layout) else {
+            return;
+        };
+        let state = &mut *self.state.get();
+        let mut offset = allocated_ptr.as_ptr() as usize - state.base.as_ptr() as usize;
+
+        while order < self.max_order {
+            let buddy = offset ^ (1 << order);
+            if !state.is_free(buddy, order) {
+                break;
+            }
+            state.remove(order, buddy);
+            offset = offset.min(buddy);
+            order += 1;
+        }
+
+        state.push(order, offset);
+    }
+}
+
+impl Drop for BuddyAllocator {
+    fn drop(&mut self) {
+        let base = self.state.get_mut().base;
+        unsafe { alloc::alloc::dealloc(base.as_ptr(), Self::buffer_layout(self.capacity)) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `BuddyAllocator` with a generic vector.
+    fn test_generic_vector_with_buddy_allocator() {
+        let allocator = BuddyAllocator::new(4096, 16);
+        let mut vector: Vec<usize, &BuddyAllocator> = Vec::new_in(&allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+        assert_eq!(allocator.free_blocks_at_order(12), 1);
+    }
+
+    #[test]
+    /// Tests that allocating splits blocks down to the requested order and freeing merges them.
+    fn test_split_and_merge() {
+        let allocator = BuddyAllocator::new(1024, 16);
+        let layout = Layout::from_size_align(10, 1).unwrap();
+        assert_eq!(allocator.free_blocks_at_order(10), 1);
+
+        let block = allocator.allocate(layout).unwrap();
+        assert_eq!(allocator.free_blocks_at_order(10), 0);
+        for order in 4..10 {
+            assert_eq!(allocator.free_blocks_at_order(order), 1);
+        }
+
+        unsafe { allocator.deallocate(block.as_non_null_ptr(), layout) };
+        assert_eq!(allocator.free_blocks_at_order(10), 1);
+        for order in 4..10 {
+            assert_eq!(allocator.free_blocks_at_order(order), 0);
+        }
+    }
+
+    #[test]
+    /// Tests that buddies only merge once both halves are free.
+    fn test_merge_requires_both_buddies() {
+        let allocator = BuddyAllocator::new(64, 16);
+        let layout = Layout::from_size_align(16, 1).unwrap();
+
+        let blocks: Vec<NonNull<[u8]>> = (0..4)
+            .map(|_| allocator.allocate(layout).unwrap())
+            .collect();
+        assert!(allocator.allocate(layout).is_err());
+
+        unsafe { allocator.deallocate(blocks[0].as_non_null_ptr(), layout) };
+        unsafe { allocator.deallocate(blocks[2].as_non_null_ptr(), layout) };
+        assert_eq!(allocator.free_blocks_at_order(4), 2);
+        assert_eq!(allocator.free_blocks_at_order(5), 0);
+
+        unsafe { allocator.deallocate(blocks[1].as_non_null_ptr(), layout) };
+        assert_eq!(allocator.free_blocks_at_order(4), 1);
+        assert_eq!(allocator.free_blocks_at_order(5), 1);
+
+        unsafe { allocator.deallocate(blocks[3].as_non_null_ptr(), layout) };
+        assert_eq!(allocator.free_blocks_at_order(6), 1);
+    }
+
+    #[test]
+    /// Tests that allocations respect the requested alignment.
+    fn test_alignment() {
+        let allocator = BuddyAllocator::new(1024, 16);
+        allocator
+            .allocate(Layout::from_size_align(1, 1).unwrap())
+            .unwrap();
+        let aligned = allocator
+            .allocate(Layout::from_size_align(8, 128).unwrap())
+            .unwrap();
+
+        assert_eq!(aligned.as_mut_ptr() as usize % 128, 0);
+    }
+}
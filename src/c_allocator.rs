@@ -6,11 +6,109 @@ use core::{
     ptr::{self, NonNull},
 };
 
-use libc::{free, malloc};
+use libc::calloc;
 
 /// A custom memory allocator that interfaces with the C standard library's allocation functions.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
 pub struct CAllocator;
 
+impl CAllocator {
+    /// Always returns `false`: the C heap has no bounded region to check `ptr` against.
+    #[must_use]
+    pub const fn contains(&self, _: NonNull<u8>) -> bool {
+        false
+    }
+
+    /// Returns `true` if the block at `ptr`, allocated with `old_layout`, already has room for
+    /// `new_size` bytes, so it can grow without moving.
+    ///
+    /// The C heap often rounds requests up, and on glibc and macOS the real size of a block can be
+    /// queried (`malloc_usable_size` and `malloc_size` respectively). On every other platform this
+    /// only returns `true` when `new_size` does not exceed `old_layout.size()`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must denote a block currently allocated by this allocator with `old_layout`.
+    #[must_use]
+    pub unsafe fn try_grow_in_place(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_size: usize,
+    ) -> bool {
+        new_size <= old_layout.size() || new_size <= usable_size(ptr)
+    }
+
+    /// Allocates `size` bytes starting on a page boundary, as DMA engines and `mmap`-style APIs
+    /// often require.
+    ///
+    /// On Linux, macOS and the BSDs this calls `valloc`, which aligns to the system page size.
+    /// Everywhere else the block is allocated with an alignment of [`PAGE_ALIGN`] bytes. Either
+    /// way, release it with [`Allocator::deallocate`] and a layout of `size` bytes aligned to
+    /// [`PAGE_ALIGN`], like any other block from this allocator.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError` if the C heap cannot provide the block.
+    pub fn allocate_page_aligned(&self, size: usize) -> Result<NonNull<[u8]>, AllocError> {
+        let allocated_ptr = cfg_select! {
+            any(
+                target_os = "linux",
+                target_os = "macos",
+                target_os = "freebsd",
+                target_os = "netbsd",
+                target_os = "openbsd",
+                target_os = "dragonfly",
+            ) => {
+                unsafe { valloc(size.max(1)).cast::<u8>() }
+            }
+            _ => {
+                allocate_memory(size, PAGE_ALIGN)?
+            }
+        };
+
+        NonNull::new(ptr::slice_from_raw_parts_mut(allocated_ptr, size)).ok_or(AllocError)
+    }
+}
+
+/// The smallest alignment of blocks from [`CAllocator::allocate_page_aligned`], which is the page
+/// size on most platforms.
+pub const PAGE_ALIGN: usize = 4096;
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "dragonfly",
+))]
+unsafe extern "C" {
+    /// Allocates `size` bytes aligned to the page size. Obsolete in POSIX but provided by every C
+    /// library of these platforms; the `libc` crate does not declare it for them.
+    fn valloc(size: usize) -> *mut c_void;
+}
+
+/// Returns the number of bytes actually usable in the block at `ptr`.
+#[cfg_attr(
+    not(any(all(target_os = "linux", target_env = "gnu"), target_os = "macos")),
+    allow(clippy::missing_const_for_fn)
+)]
+unsafe fn usable_size(ptr: NonNull<u8>) -> usize {
+    cfg_select! {
+        all(target_os = "linux", target_env = "gnu") => {
+            libc::malloc_usable_size(ptr.as_ptr().cast::<c_void>())
+        }
+        target_os = "macos" => {
+            libc::malloc_size(ptr.as_ptr().cast::<c_void>().cast_const())
+        }
+        _ => {
+            let _ = ptr;
+            0
+        }
+    }
+}
+
 unsafe impl Allocator for CAllocator {
     fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
         let alignment = layout.align().max(mem::size_of::<usize>());
@@ -20,8 +118,41 @@ unsafe impl Allocator for CAllocator {
         NonNull::new(ptr::slice_from_raw_parts_mut(allocated_ptr, size)).ok_or(AllocError)
     }
 
+    /// Uses `calloc` for the same layouts as [`GlobalAlloc::alloc_zeroed`] and falls back to an
+    /// aligned allocation cleared by hand otherwise. Zero-sized requests ask `calloc` for one
+    /// byte so the returned pointer is never null.
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let size = layout.size();
+        if !cfg!(windows) && layout.align() <= mem::size_of::<usize>() {
+            let allocated_ptr = unsafe { calloc(1, size.max(1)) }.cast::<u8>();
+            return NonNull::new(ptr::slice_from_raw_parts_mut(allocated_ptr, size))
+                .ok_or(AllocError);
+        }
+
+        let block = self.allocate(layout)?;
+        unsafe { ptr::write_bytes(block.as_mut_ptr(), 0, size) };
+        Ok(block)
+    }
+
     unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, _: Layout) {
-        free(allocated_ptr.as_ptr().cast::<c_void>());
+        free_memory(allocated_ptr.as_ptr());
+    }
+
+    unsafe fn grow(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let is_aligned = (old_ptr.as_ptr() as usize).is_multiple_of(new_layout.align());
+        if is_aligned && self.try_grow_in_place(old_ptr, old_layout, new_layout.size()) {
+            return Ok(NonNull::slice_from_raw_parts(old_ptr, new_layout.size()));
+        }
+
+        let new_ptr = self.allocate(new_layout)?;
+        ptr::copy_nonoverlapping(old_ptr.as_ptr(), new_ptr.as_mut_ptr(), old_layout.size());
+        self.deallocate(old_ptr, old_layout);
+        Ok(new_ptr)
     }
 }
 
@@ -31,7 +162,15 @@ unsafe impl GlobalAlloc for CAllocator {
         allocate_memory(layout.size(), alignment).unwrap_or(ptr::null_mut())
     }
 
+    /// Requests with at most pointer alignment go straight to `calloc`, which can hand out pages
+    /// the OS has already zeroed instead of clearing them again. `calloc` makes no promises about
+    /// larger alignments, so those are allocated aligned and cleared by hand. On Windows every
+    /// block has to come from `_aligned_malloc`, so `calloc` is never used there.
     unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        if !cfg!(windows) && layout.align() <= mem::size_of::<usize>() {
+            return calloc(1, layout.size()).cast::<u8>();
+        }
+
         let allocated_ptr = self.alloc(layout);
         if !allocated_ptr.is_null() {
             ptr::write_bytes(allocated_ptr, 0, layout.size());
@@ -40,7 +179,7 @@ unsafe impl GlobalAlloc for CAllocator {
     }
 
     unsafe fn dealloc(&self, allocated_ptr: *mut u8, _: Layout) {
-        free(allocated_ptr.cast::<c_void>());
+        free_memory(allocated_ptr);
     }
 
     unsafe fn realloc(&self, old_ptr: *mut u8, old_layout: Layout, new_size: usize) -> *mut u8 {
@@ -57,12 +196,21 @@ unsafe impl GlobalAlloc for CAllocator {
 
 /// Allocates memory with the specified size and alignment.
 ///
+/// Zero-sized requests are rounded up to one byte. `posix_memalign` and friends may return null
+/// for a size of zero, which the `Allocator` contract does not allow for a successful allocation,
+/// and a real block, unlike a dangling pointer, can be released like any other.
+///
+/// Neither the MSVC nor the MinGW C runtime provides `posix_memalign` or `memalign`, so Windows
+/// uses `_aligned_malloc`, whose blocks must be released with `_aligned_free` through
+/// [`free_memory`].
+///
 /// # Errors
 ///
 /// Returns an `AllocError` if the allocation fails.
 fn allocate_memory(size: usize, alignment: usize) -> Result<*mut u8, AllocError> {
-    cfg_match! {
-        cfg(any(
+    let size = size.max(1);
+    cfg_select! {
+        any(
         target_os = "dragonfly",
         target_os = "netbsd",
         target_os = "freebsd",
@@ -70,7 +218,7 @@ fn allocate_memory(size: usize, alignment: usize) -> Result<*mut u8, AllocError>
         target_os = "openbsd",
         target_os = "linux",
         target_os = "macos",
-        )) => {
+        ) => {
             let ptr = {
             let mut temp_ptr: *mut u8 = ptr::null_mut();
             let result = unsafe {
@@ -82,6 +230,9 @@ fn allocate_memory(size: usize, alignment: usize) -> Result<*mut u8, AllocError>
             temp_ptr
             };
         }
+        target_os = "windows" => {
+            let ptr = unsafe { libc::aligned_malloc(size, alignment).cast::<u8>() };
+        }
     _ => {
         let ptr = unsafe { libc::memalign(alignment, size) as *mut u8 };
     }
@@ -94,28 +245,131 @@ fn allocate_memory(size: usize, alignment: usize) -> Result<*mut u8, AllocError>
     }
 }
 
+/// Releases a block returned by [`allocate_memory`].
+unsafe fn free_memory(allocated_ptr: *mut u8) {
+    cfg_select! {
+        target_os = "windows" => {
+            libc::aligned_free(allocated_ptr.cast::<c_void>());
+        }
+        _ => {
+            libc::free(allocated_ptr.cast::<c_void>());
+        }
+    }
+}
+
+/// Alignment every block from the C heap's `malloc` and `realloc` is guaranteed to have, even on
+/// platforms whose `max_align_t` is smaller than two words.
+const MALLOC_ALIGN: usize = mem::size_of::<usize>();
+
 /// A custom memory allocator that interfaces with the C standard library's basic allocation functions (`malloc`/`free`).
+///
+/// The "raw" refers to plain `malloc` semantics: memory comes straight from the C heap and is
+/// released with `free`. Allocations still honour the full `layout.align()`, exactly like
+/// [`CAllocator`].
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
 #[allow(clippy::module_name_repetitions)]
 pub struct RawCAllocator;
 
 unsafe impl Allocator for RawCAllocator {
     fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let alignment = layout.align().max(mem::size_of::<usize>());
         let size = layout.size();
-        NonNull::new(ptr::slice_from_raw_parts_mut(
-            unsafe { malloc(size).cast::<u8>() },
-            size,
-        ))
-        .ok_or(AllocError)
+        let allocated_ptr = allocate_memory(size, alignment)?;
+
+        NonNull::new(ptr::slice_from_raw_parts_mut(allocated_ptr, size)).ok_or(AllocError)
     }
 
     unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, _: Layout) {
-        free(allocated_ptr.as_ptr().cast::<c_void>());
+        free_memory(allocated_ptr.as_ptr());
+    }
+
+    unsafe fn grow(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.resize(old_ptr, old_layout, new_layout)
+    }
+
+    unsafe fn shrink(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.resize(old_ptr, old_layout, new_layout)
+    }
+}
+
+impl RawCAllocator {
+    /// Resizes the block at `old_ptr` to `new_layout` through [`RawCAllocator::reallocate`].
+    unsafe fn resize(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let layout = Layout::from_size_align(old_layout.size(), new_layout.align())
+            .map_err(|_| AllocError)?;
+        let new_ptr = self.reallocate(old_ptr.as_ptr(), layout, new_layout.size());
+        NonNull::new(ptr::slice_from_raw_parts_mut(new_ptr, new_layout.size())).ok_or(AllocError)
+    }
+
+    /// Resizes the block at `old_ptr` to `new_size` bytes aligned to `layout.align()`, returning
+    /// null and leaving the block untouched on failure.
+    ///
+    /// `realloc` can resize a block in place, but only guarantees [`MALLOC_ALIGN`]. Blocks that
+    /// need more, and zero-sized results, which `realloc` may turn into a free, are allocated
+    /// afresh, copied and freed instead. On Windows, `_aligned_realloc` keeps any alignment, so
+    /// only zero-sized results are copied.
+    unsafe fn reallocate(&self, old_ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        cfg_select! {
+            target_os = "windows" => {
+                if new_size == 0 {
+                    return self.reallocate_by_copy(old_ptr, layout, new_size);
+                }
+
+                let alignment = layout.align().max(MALLOC_ALIGN);
+                libc::aligned_realloc(old_ptr.cast::<c_void>(), new_size, alignment).cast::<u8>()
+            }
+            _ => {
+                if new_size == 0 || layout.align() > MALLOC_ALIGN {
+                    return self.reallocate_by_copy(old_ptr, layout, new_size);
+                }
+
+                let new_ptr = libc::realloc(old_ptr.cast::<c_void>(), new_size).cast::<u8>();
+                debug_assert!(
+                    (new_ptr as usize).is_multiple_of(layout.align()),
+                    "realloc returned a block aligned to less than {MALLOC_ALIGN} bytes"
+                );
+                new_ptr
+            }
+        }
+    }
+
+    /// Moves the block at `old_ptr` into a fresh allocation of `new_size` bytes.
+    unsafe fn reallocate_by_copy(
+        &self,
+        old_ptr: *mut u8,
+        layout: Layout,
+        new_size: usize,
+    ) -> *mut u8 {
+        let new_layout = Layout::from_size_align_unchecked(new_size, layout.align());
+        let new_ptr = self.alloc(new_layout);
+        if !new_ptr.is_null() {
+            let copy_size = cmp::min(layout.size(), new_size);
+            ptr::copy_nonoverlapping(old_ptr, new_ptr, copy_size);
+            self.dealloc(old_ptr, layout);
+        }
+        new_ptr
     }
 }
 
 unsafe impl GlobalAlloc for RawCAllocator {
     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
-        unsafe { malloc(layout.size()).cast::<u8>() }
+        let alignment = layout.align().max(mem::size_of::<usize>());
+        allocate_memory(layout.size(), alignment).unwrap_or(ptr::null_mut())
     }
 
     unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
@@ -127,18 +381,12 @@ unsafe impl GlobalAlloc for RawCAllocator {
     }
 
     unsafe fn dealloc(&self, allocated_ptr: *mut u8, _: Layout) {
-        free(allocated_ptr.cast::<c_void>());
+        free_memory(allocated_ptr);
     }
 
+    /// Calls `realloc` directly, which can resize in place, whenever its alignment suffices.
     unsafe fn realloc(&self, old_ptr: *mut u8, old_layout: Layout, new_size: usize) -> *mut u8 {
-        let new_layout = Layout::from_size_align_unchecked(new_size, old_layout.align());
-        let new_ptr = self.alloc(new_layout);
-        if !new_ptr.is_null() {
-            let copy_size = cmp::min(old_layout.size(), new_size);
-            ptr::copy_nonoverlapping(old_ptr, new_ptr, copy_size);
-            self.dealloc(old_ptr, old_layout);
-        }
-        new_ptr
+        self.reallocate(old_ptr, old_layout, new_size)
     }
 }
 
@@ -165,6 +413,82 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(unix)]
+    /// Tests that `allocate_page_aligned` returns a page-aligned, writable block.
+    fn test_allocate_page_aligned() {
+        for size in [1, 100, 3 * PAGE_ALIGN] {
+            let block = CAllocator.allocate_page_aligned(size).unwrap();
+            assert_eq!(block.as_mut_ptr() as usize % PAGE_ALIGN, 0);
+            assert_eq!(block.len(), size);
+            unsafe {
+                ptr::write_bytes(block.as_mut_ptr(), 0x5A, size);
+                let layout = Layout::from_size_align(size, PAGE_ALIGN).unwrap();
+                CAllocator.deallocate(block.as_non_null_ptr(), layout);
+            }
+        }
+    }
+
+    #[test]
+    /// Tests that `allocate_zeroed` clears reused memory on both the `calloc` and aligned paths.
+    fn test_allocate_zeroed() {
+        for align in [1, 8, 64, 4096] {
+            let layout = Layout::from_size_align(4096, align).unwrap();
+            unsafe {
+                let dirty = CAllocator.allocate(layout).unwrap();
+                ptr::write_bytes(dirty.as_mut_ptr(), 0xFF, layout.size());
+                CAllocator.deallocate(dirty.as_non_null_ptr(), layout);
+
+                let block = CAllocator.allocate_zeroed(layout).unwrap();
+                assert_eq!(block.len(), layout.size());
+                assert!((block.as_mut_ptr() as usize).is_multiple_of(align));
+                assert!(block.as_ref().iter().all(|&byte| byte == 0));
+                CAllocator.deallocate(block.as_non_null_ptr(), layout);
+            }
+        }
+
+        let layout = Layout::new::<()>();
+        let block = CAllocator.allocate_zeroed(layout).unwrap();
+        assert_eq!(block.len(), 0);
+        unsafe { CAllocator.deallocate(block.as_non_null_ptr(), layout) };
+    }
+
+    #[test]
+    /// Tests that `alloc_zeroed` returns zeroed memory through `calloc` and for large alignments.
+    fn test_alloc_zeroed() {
+        for align in [1, 8, 64] {
+            let layout = Layout::from_size_align(4096, align).unwrap();
+            unsafe {
+                let allocated_ptr = GlobalAlloc::alloc_zeroed(&CAllocator, layout);
+                assert!(!allocated_ptr.is_null());
+                assert_eq!(allocated_ptr as usize % align, 0);
+                let bytes = core::slice::from_raw_parts(allocated_ptr, layout.size());
+                assert!(bytes.iter().all(|&byte| byte == 0));
+                GlobalAlloc::dealloc(&CAllocator, allocated_ptr, layout);
+            }
+        }
+    }
+
+    #[test]
+    /// Tests that `grow` keeps the block in place whenever the C heap reports enough room.
+    fn test_grow_in_place() {
+        let old_layout = Layout::from_size_align(20, 8).unwrap();
+        let block = CAllocator.allocate(old_layout).unwrap();
+        unsafe { ptr::write_bytes(block.as_mut_ptr(), 0xAB, 20) };
+
+        let usable = unsafe { usable_size(block.as_non_null_ptr()) };
+        let fits = unsafe { CAllocator.try_grow_in_place(block.as_non_null_ptr(), old_layout, 24) };
+        assert_eq!(fits, usable >= 24);
+        assert!(unsafe { CAllocator.try_grow_in_place(block.as_non_null_ptr(), old_layout, 20) });
+
+        let new_layout = Layout::from_size_align(4096, 8).unwrap();
+        let grown = unsafe { CAllocator.grow(block.as_non_null_ptr(), old_layout, new_layout) };
+        let grown = grown.unwrap();
+        let bytes = unsafe { core::slice::from_raw_parts(grown.as_mut_ptr(), 20) };
+        assert!(bytes.iter().all(|&byte| byte == 0xAB));
+        unsafe { CAllocator.deallocate(grown.as_non_null_ptr(), new_layout) };
+    }
+
     #[test]
     /// Tests the `RawCAllocator` with a generic vector.
     fn test_generic_vector_with_raw_c_allocator() {
@@ -180,4 +504,90 @@ mod tests {
             assert_eq!(actual_value, expected_index);
         }
     }
+
+    #[test]
+    /// Tests that the `RawCAllocator` honours large alignments.
+    fn test_raw_c_allocator_alignment() {
+        let layout = Layout::from_size_align(100, 64).unwrap();
+
+        let allocated_ptr = RawCAllocator.allocate(layout).unwrap();
+        assert_eq!(allocated_ptr.as_mut_ptr() as usize % 64, 0);
+        unsafe { RawCAllocator.deallocate(allocated_ptr.as_non_null_ptr(), layout) };
+
+        let allocated_ptr = unsafe { GlobalAlloc::alloc(&RawCAllocator, layout) };
+        assert_eq!(allocated_ptr as usize % 64, 0);
+        unsafe { GlobalAlloc::dealloc(&RawCAllocator, allocated_ptr, layout) };
+    }
+
+    #[test]
+    /// Tests that the C allocators are `Copy` and equal to their `Default`.
+    fn test_derived_traits() {
+        fn default_of<T: Default>() -> T {
+            T::default()
+        }
+
+        let allocator = CAllocator;
+        let copy = allocator;
+        assert_eq!(allocator, copy);
+        assert_eq!(default_of::<CAllocator>(), CAllocator);
+        assert_eq!(default_of::<RawCAllocator>(), RawCAllocator);
+    }
+
+    #[test]
+    /// Tests that `realloc` resizes a block in place while it fits in the block's usable size.
+    #[cfg(any(all(target_os = "linux", target_env = "gnu"), target_os = "macos"))]
+    fn test_raw_realloc_in_place() {
+        let layout = Layout::from_size_align(20, 8).unwrap();
+        unsafe {
+            let block = GlobalAlloc::alloc(&RawCAllocator, layout);
+            ptr::write_bytes(block, 0xA5, 20);
+            let usable = usable_size(NonNull::new(block).unwrap());
+
+            let grown = GlobalAlloc::realloc(&RawCAllocator, block, layout, usable);
+            assert_eq!(grown, block);
+            assert!(core::slice::from_raw_parts(grown, 20)
+                .iter()
+                .all(|&byte| byte == 0xA5));
+
+            let shrunk = RawCAllocator
+                .shrink(
+                    NonNull::new(grown).unwrap(),
+                    Layout::from_size_align(usable, 8).unwrap(),
+                    Layout::from_size_align(8, 8).unwrap(),
+                )
+                .unwrap();
+            assert_eq!(shrunk.as_mut_ptr(), block);
+            RawCAllocator.deallocate(
+                shrunk.as_non_null_ptr(),
+                Layout::from_size_align(8, 8).unwrap(),
+            );
+        }
+    }
+
+    #[test]
+    /// Tests that blocks aligned beyond what `realloc` guarantees are moved by copying.
+    fn test_raw_realloc_over_aligned() {
+        let old_layout = Layout::from_size_align(64, 4096).unwrap();
+        let new_layout = Layout::from_size_align(8192, 4096).unwrap();
+        unsafe {
+            let block = RawCAllocator.allocate(old_layout).unwrap();
+            ptr::write_bytes(block.as_mut_ptr(), 0x5A, 64);
+
+            let grown = RawCAllocator
+                .grow(block.as_non_null_ptr(), old_layout, new_layout)
+                .unwrap();
+            assert_eq!(grown.as_mut_ptr() as usize % 4096, 0);
+            assert!(core::slice::from_raw_parts(grown.as_mut_ptr(), 64)
+                .iter()
+                .all(|&byte| byte == 0x5A));
+
+            let reallocated =
+                GlobalAlloc::realloc(&RawCAllocator, grown.as_mut_ptr(), new_layout, 16);
+            assert_eq!(reallocated as usize % 4096, 0);
+            assert!(core::slice::from_raw_parts(reallocated, 16)
+                .iter()
+                .all(|&byte| byte == 0x5A));
+            GlobalAlloc::dealloc(&RawCAllocator, reallocated, old_layout);
+        }
+    }
 }
@@ -0,0 +1,217 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    ptr::NonNull,
+};
+
+#[cfg(debug_assertions)]
+use core::cell::RefCell;
+
+#[cfg(debug_assertions)]
+use alloc::vec::Vec;
+
+use alloc::alloc::Global;
+
+use crate::arena_allocator::ArenaAllocator;
+
+/// An [`ArenaAllocator`] that panics when a pointer it never handed out is deallocated.
+///
+/// In debug builds every live allocation is logged as an `(address, layout)` pair; deallocating
+/// removes the entry again, so freeing a foreign pointer or freeing the same block twice panics
+/// with a message naming the pointer. The log, and with it all overhead, is compiled out when
+/// `debug_assertions` are off, leaving a plain arena. This is a testing aid, not a production
+/// allocator.
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct CheckedArenaAllocator<A: Allocator = Global> {
+    arena: ArenaAllocator<A>,
+    #[cfg(debug_assertions)]
+    log: RefCell<Vec<(usize, Layout)>>,
+}
+
+impl CheckedArenaAllocator {
+    /// Creates a new checked arena with a backing buffer of `bytes` bytes.
+    #[must_use]
+    pub fn new(bytes: usize) -> Self {
+        Self::new_with_allocator(bytes, Global)
+    }
+}
+
+impl<A: Allocator> CheckedArenaAllocator<A> {
+    /// Creates a new checked arena whose backing buffer of `bytes` bytes is allocated from `alloc`.
+    #[must_use]
+    pub fn new_with_allocator(bytes: usize, alloc: A) -> Self {
+        Self {
+            arena: ArenaAllocator::new_with_allocator(bytes, alloc),
+            #[cfg(debug_assertions)]
+            log: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Returns the size of the backing buffer in bytes.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.arena.capacity()
+    }
+
+    /// Returns the number of bytes consumed so far, including alignment padding.
+    #[must_use]
+    pub fn used(&self) -> usize {
+        self.arena.used()
+    }
+
+    /// Returns the number of allocations that have not been deallocated, or zero when
+    /// `debug_assertions` are off.
+    #[must_use]
+    #[cfg_attr(
+        not(debug_assertions),
+        allow(clippy::missing_const_for_fn, clippy::unused_self)
+    )]
+    pub fn live_allocations(&self) -> usize {
+        #[cfg(debug_assertions)]
+        return self.log.borrow().len();
+        #[cfg(not(debug_assertions))]
+        0
+    }
+
+    /// Resets the arena and forgets every logged allocation.
+    ///
+    /// Taking `&mut self` ensures nothing borrowing the arena outlives the reset. Raw pointers
+    /// handed out before the reset must no longer be used.
+    #[cfg_attr(not(debug_assertions), allow(clippy::missing_const_for_fn))]
+    pub fn reset(&mut self) {
+        #[cfg(debug_assertions)]
+        self.log.get_mut().clear();
+        self.arena.reset();
+    }
+
+    /// Logs a block handed out for `layout`.
+    #[cfg_attr(
+        not(debug_assertions),
+        allow(clippy::missing_const_for_fn, clippy::unused_self)
+    )]
+    fn record(&self, block: NonNull<[u8]>, layout: Layout) {
+        #[cfg(debug_assertions)]
+        self.log
+            .borrow_mut()
+            .push((block.as_mut_ptr() as usize, layout));
+        #[cfg(not(debug_assertions))]
+        let _ = (block, layout);
+    }
+
+    /// Removes `ptr` from the log, panicking if it is not a live allocation of this arena.
+    #[track_caller]
+    #[cfg_attr(
+        not(debug_assertions),
+        allow(clippy::missing_const_for_fn, clippy::unused_self)
+    )]
+    fn forget(&self, ptr: NonNull<u8>, operation: &str) {
+        #[cfg(debug_assertions)]
+        {
+            let mut log = self.log.borrow_mut();
+            let address = ptr.as_ptr() as usize;
+            let Some(index) = log.iter().position(|&(logged, _)| logged == address) else {
+                panic!("{operation}: pointer {ptr:p} was not allocated by this arena or was already freed");
+            };
+            log.swap_remove(index);
+        }
+        #[cfg(not(debug_assertions))]
+        let _ = (ptr, operation);
+    }
+}
+
+unsafe impl<A: Allocator> Allocator for CheckedArenaAllocator<A> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let block = self.arena.allocate(layout)?;
+        self.record(block, layout);
+        Ok(block)
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let block = self.arena.allocate_zeroed(layout)?;
+        self.record(block, layout);
+        Ok(block)
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        self.forget(allocated_ptr, "deallocate");
+        self.arena.deallocate(allocated_ptr, layout);
+    }
+
+    unsafe fn grow(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.forget(old_ptr, "grow");
+        let block = self
+            .arena
+            .grow(old_ptr, old_layout, new_layout)
+            .inspect_err(|_| self.record(NonNull::slice_from_raw_parts(old_ptr, 0), old_layout))?;
+        self.record(block, new_layout);
+        Ok(block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `CheckedArenaAllocator` with a generic vector.
+    fn test_generic_vector_with_checked_arena() {
+        let allocator = CheckedArenaAllocator::new(4096);
+        let mut vector: Vec<usize, &CheckedArenaAllocator> = Vec::with_capacity_in(4, &allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+        assert_eq!(allocator.live_allocations(), 0);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(
+        expected = "deallocate: pointer 0x10 was not allocated by this arena or was already freed"
+    )]
+    /// Tests that deallocating a foreign pointer panics with a descriptive message.
+    fn test_foreign_pointer_panics() {
+        let allocator = CheckedArenaAllocator::new(64);
+        let foreign = NonNull::new(core::ptr::without_provenance_mut::<u8>(0x10)).unwrap();
+        unsafe { allocator.deallocate(foreign, Layout::new::<u8>()) };
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "was already freed")]
+    /// Tests that freeing the same block twice panics.
+    fn test_double_free_panics() {
+        let allocator = CheckedArenaAllocator::new(64);
+        let layout = Layout::new::<u64>();
+        let block = allocator.allocate(layout).unwrap();
+        unsafe {
+            allocator.deallocate(block.as_non_null_ptr(), layout);
+            allocator.deallocate(block.as_non_null_ptr(), layout);
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    /// Tests that a reset forgets every logged allocation.
+    fn test_reset_clears_log() {
+        let mut allocator = CheckedArenaAllocator::new(64);
+        allocator.allocate(Layout::new::<u64>()).unwrap();
+        assert_eq!(allocator.live_allocations(), 1);
+
+        allocator.reset();
+        assert_eq!(allocator.live_allocations(), 0);
+        assert_eq!(allocator.used(), 0);
+        assert_eq!(allocator.capacity(), 64);
+    }
+}
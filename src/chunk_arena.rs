@@ -0,0 +1,195 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    cell::{Cell, UnsafeCell},
+    mem::MaybeUninit,
+    ptr::{self, NonNull},
+};
+
+use alloc::boxed::Box;
+
+/// The most chunks a [`ChunkArenaAllocator`] can activate.
+pub const MAX_CHUNKS: usize = 16;
+
+/// The buffer of one chunk.
+type Chunk = Box<[MaybeUninit<u8>]>;
+
+/// A bump-pointer memory allocator that spreads its allocations over at most [`MAX_CHUNKS`]
+/// equally sized chunks.
+///
+/// The chunk table is a fixed array, so no `Vec` has to grow while allocating, and each chunk's
+/// buffer is only allocated the first time the chunk is activated. When the active chunk runs out
+/// of space, the next one is activated. Unlike
+/// [`LinkedArenaAllocator`](crate::linked_arena::LinkedArenaAllocator), the total capacity is
+/// bounded: once every chunk is active, allocations fail. Individual deallocations are ignored.
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct ChunkArenaAllocator {
+    chunks: UnsafeCell<[Option<Chunk>; MAX_CHUNKS]>,
+    chunk_size: usize,
+    /// Number of chunks activated since creation or the last reset; the last one is bumped.
+    active: Cell<usize>,
+    /// Offset of the next allocation within the last active chunk.
+    offset: Cell<usize>,
+}
+
+impl ChunkArenaAllocator {
+    /// Creates a new arena whose chunks hold `chunk_size` bytes each. No memory is allocated
+    /// until the first allocation.
+    #[must_use]
+    pub const fn new(chunk_size: usize) -> Self {
+        Self {
+            chunks: UnsafeCell::new([const { None }; MAX_CHUNKS]),
+            chunk_size,
+            active: Cell::new(0),
+            offset: Cell::new(0),
+        }
+    }
+
+    /// Returns the size of each chunk in bytes.
+    #[must_use]
+    pub const fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Returns the number of chunks activated since the arena was created or last reset.
+    #[must_use]
+    pub const fn active_chunks(&self) -> usize {
+        self.active.get()
+    }
+
+    /// Resets the arena, making every chunk available again while keeping their memory.
+    ///
+    /// Taking `&mut self` ensures no collection still allocates from the chunks. Raw pointers
+    /// handed out before the reset must no longer be used.
+    pub const fn reset(&mut self) {
+        *self.active.get_mut() = 0;
+        *self.offset.get_mut() = 0;
+    }
+
+    /// Bumps `layout` within `chunk` starting at `offset`, returning the block and the new offset.
+    ///
+    /// `chunk` is a raw pointer because blocks handed out earlier point into the same buffer, and
+    /// a reference to the whole chunk would invalidate them.
+    fn bump(
+        chunk: *mut [MaybeUninit<u8>],
+        offset: usize,
+        layout: Layout,
+    ) -> Option<(*mut u8, usize)> {
+        let base = chunk.as_mut_ptr().cast::<u8>();
+        let start =
+            (base as usize + offset).checked_next_multiple_of(layout.align())? - base as usize;
+        let end = start.checked_add(layout.size())?;
+        if end > chunk.len() {
+            return None;
+        }
+        Some((unsafe { base.add(start) }, end))
+    }
+}
+
+unsafe impl Allocator for ChunkArenaAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        // SAFETY: the arena is `!Sync` and nothing else borrows the chunk table, so this unique
+        // borrow of the table is sound. It covers the `Box`es only; the buffers behind them are
+        // reached solely through `&raw mut`.
+        let chunks = unsafe { &mut *self.chunks.get() };
+        let active = self.active.get();
+
+        let bumped = active
+            .checked_sub(1)
+            .and_then(|last| chunks[last].as_mut())
+            .and_then(|chunk| Self::bump(&raw mut **chunk, self.offset.get(), layout));
+        let (allocated_ptr, end) = if let Some(bumped) = bumped {
+            bumped
+        } else {
+            // Only move on if the request fits in a fresh chunk, so an oversized request does not
+            // use up the remaining chunks.
+            let next = chunks.get_mut(active).ok_or(AllocError)?;
+            let chunk = next.get_or_insert_with(|| Box::new_uninit_slice(self.chunk_size));
+            let bumped = Self::bump(&raw mut **chunk, 0, layout).ok_or(AllocError)?;
+            self.active.set(active + 1);
+            bumped
+        };
+
+        self.offset.set(end);
+        NonNull::new(ptr::slice_from_raw_parts_mut(allocated_ptr, layout.size())).ok_or(AllocError)
+    }
+
+    unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `ChunkArenaAllocator` with a generic vector.
+    fn test_generic_vector_with_chunk_arena() {
+        let allocator = ChunkArenaAllocator::new(1024);
+        let mut vector: Vec<usize, &ChunkArenaAllocator> = Vec::with_capacity_in(100, &allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    /// Tests that overflowing a chunk activates the next one until every chunk is used up.
+    fn test_exhausting_all_chunks() {
+        let allocator = ChunkArenaAllocator::new(64);
+        let layout = Layout::from_size_align(48, 1).unwrap();
+        assert_eq!(allocator.active_chunks(), 0);
+
+        let mut blocks = Vec::new();
+        for expected in 1..=MAX_CHUNKS {
+            blocks.push(allocator.allocate(layout).unwrap().as_mut_ptr() as usize);
+            assert_eq!(allocator.active_chunks(), expected);
+        }
+
+        assert_eq!(allocator.allocate(layout), Err(AllocError));
+        assert_eq!(allocator.active_chunks(), MAX_CHUNKS);
+        // The last chunk still has room for small requests.
+        assert!(allocator.allocate(Layout::new::<[u8; 16]>()).is_ok());
+
+        blocks.sort_unstable();
+        blocks.dedup();
+        assert_eq!(blocks.len(), MAX_CHUNKS);
+    }
+
+    #[test]
+    /// Tests that a request larger than a chunk fails without activating a chunk.
+    fn test_oversized_allocation() {
+        let allocator = ChunkArenaAllocator::new(64);
+        let layout = Layout::from_size_align(65, 1).unwrap();
+
+        assert_eq!(allocator.allocate(layout), Err(AllocError));
+        assert_eq!(allocator.active_chunks(), 0);
+    }
+
+    #[test]
+    /// Tests that a reset reuses the memory of the chunks activated before it.
+    fn test_reset_keeps_chunks() {
+        let mut allocator = ChunkArenaAllocator::new(64);
+        let layout = Layout::from_size_align(64, 1).unwrap();
+
+        let first = allocator.allocate(layout).unwrap();
+        let second = allocator.allocate(layout).unwrap();
+        assert_eq!(allocator.active_chunks(), 2);
+
+        allocator.reset();
+        assert_eq!(allocator.active_chunks(), 0);
+        assert_eq!(
+            allocator.allocate(layout).unwrap().as_mut_ptr(),
+            first.as_mut_ptr()
+        );
+        assert_eq!(
+            allocator.allocate(layout).unwrap().as_mut_ptr(),
+            second.as_mut_ptr()
+        );
+    }
+}
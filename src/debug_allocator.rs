@@ -0,0 +1,207 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    ptr::NonNull,
+};
+
+/// A wrapper allocator that checks at runtime that the inner allocator honours every layout.
+///
+/// Each pointer coming out of or going back into the inner allocator is checked for the alignment
+/// its layout requires, and a violation panics with a message naming the operation. The checks
+/// are `debug_assert!`s, so they disappear entirely in release builds.
+#[derive(Debug, Default, Clone, Copy)]
+#[allow(clippy::module_name_repetitions)]
+pub struct DebugAllocator<A: Allocator> {
+    inner: A,
+}
+
+impl<A: Allocator> DebugAllocator<A> {
+    /// Wraps `inner` so that its pointers are validated in debug builds.
+    pub const fn new(inner: A) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the wrapped allocator.
+    pub const fn inner(&self) -> &A {
+        &self.inner
+    }
+}
+
+/// Panics in debug builds if `ptr` is not aligned to `layout.align()`.
+#[track_caller]
+fn check_aligned(ptr: NonNull<u8>, layout: Layout, operation: &str) {
+    debug_assert!(
+        (ptr.as_ptr() as usize).is_multiple_of(layout.align()),
+        "{operation}: pointer {ptr:p} is not aligned to {} bytes",
+        layout.align()
+    );
+}
+
+/// Panics in debug builds if a block returned by the inner allocator does not satisfy `layout`.
+#[track_caller]
+fn check_block(
+    block: Result<NonNull<[u8]>, AllocError>,
+    layout: Layout,
+    operation: &str,
+) -> Result<NonNull<[u8]>, AllocError> {
+    if let Ok(block) = block {
+        check_aligned(block.as_non_null_ptr(), layout, operation);
+        debug_assert!(
+            block.len() >= layout.size(),
+            "{operation}: block of {} bytes is smaller than the requested {} bytes",
+            block.len(),
+            layout.size()
+        );
+    }
+    block
+}
+
+unsafe impl<A: Allocator> Allocator for DebugAllocator<A> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        check_block(self.inner.allocate(layout), layout, "allocate")
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        check_block(
+            self.inner.allocate_zeroed(layout),
+            layout,
+            "allocate_zeroed",
+        )
+    }
+
+    /// `NonNull` already rules out null pointers, so only the alignment is checked.
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        check_aligned(allocated_ptr, layout, "deallocate");
+        self.inner.deallocate(allocated_ptr, layout);
+    }
+
+    unsafe fn grow(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        check_aligned(old_ptr, old_layout, "grow");
+        check_block(
+            self.inner.grow(old_ptr, old_layout, new_layout),
+            new_layout,
+            "grow",
+        )
+    }
+
+    unsafe fn grow_zeroed(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        check_aligned(old_ptr, old_layout, "grow_zeroed");
+        check_block(
+            self.inner.grow_zeroed(old_ptr, old_layout, new_layout),
+            new_layout,
+            "grow_zeroed",
+        )
+    }
+
+    unsafe fn shrink(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        check_aligned(old_ptr, old_layout, "shrink");
+        check_block(
+            self.inner.shrink(old_ptr, old_layout, new_layout),
+            new_layout,
+            "shrink",
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{alloc::Global, vec::Vec};
+
+    /// An allocator that deliberately returns pointers one byte past a properly aligned address.
+    struct MisalignedAllocator;
+
+    unsafe impl Allocator for MisalignedAllocator {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            let padded = Layout::from_size_align(layout.size() + 1, layout.align()).unwrap();
+            let block = Global.allocate(padded)?;
+            let misaligned = unsafe { block.as_non_null_ptr().add(1) };
+            Ok(NonNull::slice_from_raw_parts(misaligned, layout.size()))
+        }
+
+        unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+            let padded = Layout::from_size_align(layout.size() + 1, layout.align()).unwrap();
+            Global.deallocate(allocated_ptr.sub(1), padded);
+        }
+    }
+
+    #[test]
+    /// Tests the `DebugAllocator` with a generic vector.
+    fn test_generic_vector_with_debug_allocator() {
+        let allocator = DebugAllocator::new(Global);
+        let mut vector: Vec<usize, DebugAllocator<Global>> = Vec::new_in(allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(
+        not(debug_assertions),
+        ignore = "the checks only run with debug assertions"
+    )]
+    #[should_panic(expected = "allocate: pointer")]
+    /// Tests that a misaligned pointer from the inner allocator panics.
+    fn test_misaligned_allocation_panics() {
+        let allocator = DebugAllocator::new(MisalignedAllocator);
+        let _ = allocator.allocate(Layout::from_size_align(16, 8).unwrap());
+    }
+
+    #[test]
+    #[cfg_attr(
+        not(debug_assertions),
+        ignore = "the checks only run with debug assertions"
+    )]
+    #[should_panic(expected = "deallocate: pointer")]
+    /// Tests that deallocating a misaligned pointer panics.
+    fn test_misaligned_deallocation_panics() {
+        let allocator = DebugAllocator::new(Global);
+        let layout = Layout::from_size_align(16, 8).unwrap();
+        let block = allocator.allocate(layout).unwrap();
+        unsafe { allocator.deallocate(block.as_non_null_ptr().add(1), layout) };
+    }
+
+    #[test]
+    #[cfg_attr(
+        not(debug_assertions),
+        ignore = "the checks only run with debug assertions"
+    )]
+    #[should_panic(expected = "grow: pointer")]
+    /// Tests that a misaligned pointer returned by `grow` panics.
+    fn test_misaligned_grow_panics() {
+        let allocator = DebugAllocator::new(MisalignedAllocator);
+        let old_layout = Layout::from_size_align(16, 1).unwrap();
+        let new_layout = Layout::from_size_align(32, 8).unwrap();
+        let block = allocator.allocate(old_layout).unwrap();
+        let _ = unsafe { allocator.grow(block.as_non_null_ptr(), old_layout, new_layout) };
+    }
+
+    #[test]
+    /// Tests that byte-aligned requests pass even through the misaligning allocator.
+    fn test_byte_alignment_passes() {
+        let allocator = DebugAllocator::new(MisalignedAllocator);
+        let layout = Layout::from_size_align(16, 1).unwrap();
+        let block = allocator.allocate(layout).unwrap();
+        unsafe { allocator.deallocate(block.as_non_null_ptr(), layout) };
+    }
+}
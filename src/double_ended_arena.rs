@@ -0,0 +1,198 @@
+use core::{
+    alloc::{AllocError, Layout},
+    cell::UnsafeCell,
+    mem::MaybeUninit,
+    ptr::NonNull,
+};
+
+use alloc::vec::Vec;
+
+use crate::align::align_up;
+
+/// A bump-pointer memory allocator that hands out memory from both ends of a single buffer.
+///
+/// The low end grows upward and suits long-lived data, while the high end grows downward and
+/// suits short-lived scratch data that is thrown away with [`DoubleEndedArena::reset_hi`]. The two
+/// ends share the free space between them, so neither has a fixed share of the buffer.
+///
+/// Since there are two allocation streams, this type does not implement the `Allocator` trait;
+/// apart from that it follows the protocol of an
+/// [`ArenaAllocator`](crate::arena_allocator::ArenaAllocator).
+#[derive(Debug, Default)]
+pub struct DoubleEndedArena {
+    buffer: UnsafeCell<Vec<MaybeUninit<u8>>>,
+    lo: UnsafeCell<usize>,
+    hi: UnsafeCell<usize>,
+}
+
+impl DoubleEndedArena {
+    /// Creates a new arena with a backing buffer of `bytes` bytes.
+    #[must_use]
+    pub fn new(bytes: usize) -> Self {
+        Self {
+            buffer: UnsafeCell::new(alloc::vec![MaybeUninit::uninit(); bytes]),
+            lo: UnsafeCell::new(0),
+            hi: UnsafeCell::new(bytes),
+        }
+    }
+
+    /// Returns the total size of the backing buffer in bytes.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        unsafe { (*self.buffer.get()).len() }
+    }
+
+    /// Returns the number of bytes consumed by the low end, including alignment padding.
+    #[must_use]
+    pub fn used_lo(&self) -> usize {
+        unsafe { *self.lo.get() }
+    }
+
+    /// Returns the number of bytes consumed by the high end, including alignment padding.
+    #[must_use]
+    pub fn used_hi(&self) -> usize {
+        self.capacity() - unsafe { *self.hi.get() }
+    }
+
+    /// Returns the number of bytes consumed by both ends together.
+    #[must_use]
+    pub fn used(&self) -> usize {
+        self.used_lo() + self.used_hi()
+    }
+
+    /// Returns the number of bytes still free between the two ends.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.capacity() - self.used()
+    }
+
+    /// Allocates a block from the low end of the buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError` if the block would run into the high end.
+    pub fn allocate_lo(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let base = self.base_ptr() as usize;
+        let lo = self.used_lo();
+        let hi = unsafe { *self.hi.get() };
+
+        let start = align_up(base + lo, layout.align()).ok_or(AllocError)? - base;
+        let end = start.checked_add(layout.size()).ok_or(AllocError)?;
+        if end > hi {
+            return Err(AllocError);
+        }
+
+        unsafe { *self.lo.get() = end };
+        Ok(self.block(start, layout.size()))
+    }
+
+    /// Allocates a block from the high end of the buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError` if the block would run into the low end.
+    pub fn allocate_hi(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let base = self.base_ptr() as usize;
+        let lo = self.used_lo();
+        let hi = unsafe { *self.hi.get() };
+
+        let unaligned = (base + hi).checked_sub(layout.size()).ok_or(AllocError)?;
+        let start = (unaligned & !(layout.align() - 1))
+            .checked_sub(base)
+            .ok_or(AllocError)?;
+        if start < lo {
+            return Err(AllocError);
+        }
+
+        unsafe { *self.hi.get() = start };
+        Ok(self.block(start, layout.size()))
+    }
+
+    /// Resets the high end only, making its memory available again.
+    ///
+    /// The unique borrow ensures that nothing borrowing the arena is still alive. Raw pointers
+    /// returned by [`DoubleEndedArena::allocate_hi`] before the reset must no longer be used;
+    /// blocks from the low end stay valid.
+    pub fn reset_hi(&mut self) {
+        *self.hi.get_mut() = self.capacity();
+    }
+
+    /// Resets both ends, making the whole buffer available again.
+    ///
+    /// Like [`DoubleEndedArena::reset_hi`], this needs a unique borrow, and raw pointers from
+    /// either end must no longer be used afterwards.
+    pub fn reset(&mut self) {
+        *self.lo.get_mut() = 0;
+        self.reset_hi();
+    }
+
+    /// Returns a pointer to the start of the backing buffer.
+    fn base_ptr(&self) -> *mut u8 {
+        unsafe { (*self.buffer.get()).as_mut_ptr().cast::<u8>() }
+    }
+
+    /// Returns the block of `size` bytes starting `start` bytes into the buffer.
+    fn block(&self, start: usize, size: usize) -> NonNull<[u8]> {
+        let allocated_ptr = unsafe { NonNull::new_unchecked(self.base_ptr().add(start)) };
+        NonNull::slice_from_raw_parts(allocated_ptr, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    /// Returns the address range covered by `block`.
+    fn range(block: NonNull<[u8]>) -> core::ops::Range<usize> {
+        let start = block.as_mut_ptr() as usize;
+        start..start + block.len()
+    }
+
+    #[test]
+    /// Tests that blocks from the two ends never overlap and meet in the middle.
+    fn test_regions_do_not_overlap() {
+        let arena = DoubleEndedArena::new(256);
+        let layout = Layout::from_size_align(24, 8).unwrap();
+
+        let mut lo_blocks = Vec::new();
+        let mut hi_blocks = Vec::new();
+        while let Ok(block) = arena.allocate_lo(layout) {
+            lo_blocks.push(range(block));
+            let Ok(block) = arena.allocate_hi(layout) else {
+                break;
+            };
+            hi_blocks.push(range(block));
+        }
+
+        assert!(!lo_blocks.is_empty() && !hi_blocks.is_empty());
+        let highest_lo = lo_blocks.iter().map(|block| block.end).max().unwrap();
+        let lowest_hi = hi_blocks.iter().map(|block| block.start).min().unwrap();
+        assert!(highest_lo <= lowest_hi);
+        for block in lo_blocks.iter().chain(&hi_blocks) {
+            assert!(block.start.is_multiple_of(8));
+        }
+        assert!(arena.remaining() < 2 * layout.size());
+    }
+
+    #[test]
+    /// Tests that the ends fail when they would cross and that each reset frees the right end.
+    fn test_reset() {
+        let mut arena = DoubleEndedArena::new(64);
+        let layout = Layout::from_size_align(32, 1).unwrap();
+
+        let lo = arena.allocate_lo(layout).unwrap();
+        arena.allocate_hi(layout).unwrap();
+        assert!(arena.allocate_lo(Layout::new::<u8>()).is_err());
+        assert!(arena.allocate_hi(Layout::new::<u8>()).is_err());
+
+        arena.reset_hi();
+        assert_eq!(arena.used_lo(), 32);
+        assert_eq!(arena.used_hi(), 0);
+        arena.allocate_hi(layout).unwrap();
+
+        arena.reset();
+        assert_eq!(arena.used(), 0);
+        assert_eq!(arena.allocate_lo(layout).unwrap(), lo);
+    }
+}
@@ -0,0 +1,197 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    fmt,
+    ptr::NonNull,
+};
+
+#[cfg(feature = "arena_allocator")]
+use crate::arena_allocator::ArenaAllocator;
+#[cfg(feature = "c_allocator")]
+use crate::c_allocator::CAllocator;
+
+/// The reason an allocation failed, as reported by [`AllocatorExt::allocate_ext`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AllocFailure {
+    /// There is not enough free memory right now; freeing or resetting may help.
+    OutOfMemory,
+    /// There is enough free memory, but not at an address with the requested alignment.
+    AlignmentTooLarge,
+    /// Computing the size of the request overflowed.
+    SizeOverflow,
+    /// The allocator can never serve this request, whatever its state.
+    Unsupported,
+}
+
+impl fmt::Display for AllocFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::OutOfMemory => "out of memory",
+            Self::AlignmentTooLarge => "alignment cannot be satisfied",
+            Self::SizeOverflow => "allocation size overflows",
+            Self::Unsupported => "allocation not supported by this allocator",
+        })
+    }
+}
+
+impl core::error::Error for AllocFailure {}
+
+impl From<AllocFailure> for AllocError {
+    fn from(_: AllocFailure) -> Self {
+        Self
+    }
+}
+
+/// Allocators that can explain why an allocation failed.
+pub trait AllocatorExt: Allocator {
+    /// Attempts to allocate a block of memory, like [`Allocator::allocate`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`AllocFailure`] describing why the block could not be allocated.
+    fn allocate_ext(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocFailure>;
+
+    /// Attempts to allocate memory for `count` values of type `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AllocFailure::SizeOverflow`] if the size of the array overflows, and otherwise
+    /// whatever [`AllocatorExt::allocate_ext`] reports.
+    fn allocate_array_ext<T>(&self, count: usize) -> Result<NonNull<[T]>, AllocFailure> {
+        let layout = Layout::array::<T>(count).map_err(|_| AllocFailure::SizeOverflow)?;
+        let allocated_ptr = self.allocate_ext(layout)?;
+        Ok(NonNull::slice_from_raw_parts(
+            allocated_ptr.as_non_null_ptr().cast::<T>(),
+            count,
+        ))
+    }
+}
+
+impl<A: AllocatorExt + ?Sized> AllocatorExt for &A {
+    fn allocate_ext(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocFailure> {
+        (**self).allocate_ext(layout)
+    }
+}
+
+#[cfg(feature = "arena_allocator")]
+impl<A: Allocator> AllocatorExt for ArenaAllocator<A> {
+    /// Failures are classified with the same placement the allocation uses, so headers count
+    /// towards [`AllocFailure::OutOfMemory`] and only padding for `layout.align()` is reported as
+    /// [`AllocFailure::AlignmentTooLarge`].
+    fn allocate_ext(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocFailure> {
+        if let Ok(allocated_ptr) = self.allocate(layout) {
+            return Ok(allocated_ptr);
+        }
+        if self.place(0, layout).is_none() {
+            return Err(AllocFailure::Unsupported);
+        }
+        // Placing the same block without its alignment shows whether the bytes themselves fit.
+        let unaligned =
+            Layout::from_size_align(layout.size(), 1).map_err(|_| AllocFailure::SizeOverflow)?;
+        Err(if self.can_allocate(unaligned) {
+            AllocFailure::AlignmentTooLarge
+        } else {
+            AllocFailure::OutOfMemory
+        })
+    }
+}
+
+#[cfg(feature = "c_allocator")]
+impl AllocatorExt for CAllocator {
+    /// Every `Layout` has an alignment the C heap accepts, so failures are always reported as
+    /// [`AllocFailure::OutOfMemory`].
+    fn allocate_ext(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocFailure> {
+        self.allocate(layout).map_err(|_| AllocFailure::OutOfMemory)
+    }
+}
+
+#[cfg(all(test, feature = "arena_allocator", feature = "c_allocator"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Tests that an exhausted arena reports `OutOfMemory` and recovers after a reset.
+    fn test_out_of_memory() {
+        let mut arena = ArenaAllocator::new(64);
+        let layout = Layout::from_size_align(48, 1).unwrap();
+
+        arena.allocate_ext(layout).unwrap();
+        assert_eq!(arena.allocate_ext(layout), Err(AllocFailure::OutOfMemory));
+
+        arena.reset();
+        assert!(arena.allocate_ext(layout).is_ok());
+    }
+
+    #[test]
+    /// Tests that padding which does not fit is reported as `AlignmentTooLarge`.
+    fn test_alignment_too_large() {
+        let arena = ArenaAllocator::with_alignment(64, 64);
+        arena
+            .allocate_ext(Layout::from_size_align(1, 1).unwrap())
+            .unwrap();
+
+        let aligned = Layout::from_size_align(16, 64).unwrap();
+        assert_eq!(
+            arena.allocate_ext(aligned),
+            Err(AllocFailure::AlignmentTooLarge)
+        );
+    }
+
+    #[test]
+    /// Tests that running out of room for a header is reported as `OutOfMemory`, even though the
+    /// block alone would fit.
+    fn test_header_overhead_is_out_of_memory() {
+        let arena = ArenaAllocator::with_headers(64);
+        arena
+            .allocate_ext(Layout::from_size_align(24, 1).unwrap())
+            .unwrap();
+
+        let rest = Layout::from_size_align(arena.remaining(), 1).unwrap();
+        assert_eq!(arena.allocate_ext(rest), Err(AllocFailure::OutOfMemory));
+    }
+
+    #[test]
+    /// Tests that an overflowing array size is reported as `SizeOverflow`.
+    fn test_size_overflow() {
+        let arena = ArenaAllocator::new(64);
+        assert_eq!(
+            arena.allocate_array_ext::<u64>(usize::MAX).map(|_| ()),
+            Err(AllocFailure::SizeOverflow)
+        );
+        assert_eq!(arena.allocate_array_ext::<u64>(4).unwrap().len(), 4);
+    }
+
+    #[test]
+    /// Tests that a request larger than the whole arena is reported as `Unsupported`.
+    fn test_unsupported() {
+        let arena = ArenaAllocator::new(64);
+        let layout = Layout::from_size_align(65, 1).unwrap();
+        assert_eq!(arena.allocate_ext(layout), Err(AllocFailure::Unsupported));
+    }
+
+    #[test]
+    /// Tests that `ArenaAllocator::EMPTY` reports every request, even a zero-sized one, as
+    /// `Unsupported`.
+    fn test_empty_arena_is_unsupported() {
+        let arena = ArenaAllocator::EMPTY;
+        for layout in [
+            Layout::new::<()>(),
+            Layout::new::<u8>(),
+            Layout::from_size_align(0, 64).unwrap(),
+        ] {
+            assert_eq!(arena.allocate_ext(layout), Err(AllocFailure::Unsupported));
+        }
+    }
+
+    #[test]
+    /// Tests the `CAllocator` implementation and the conversion into `AllocError`.
+    fn test_c_allocator() {
+        let layout = Layout::from_size_align(64, 16).unwrap();
+        let allocated_ptr = CAllocator.allocate_ext(layout).unwrap();
+        unsafe { CAllocator.deallocate(allocated_ptr.as_non_null_ptr(), layout) };
+
+        let huge = Layout::from_size_align(isize::MAX as usize - 15, 16).unwrap();
+        let failure = CAllocator.allocate_ext(huge).unwrap_err();
+        assert_eq!(failure, AllocFailure::OutOfMemory);
+        assert_eq!(AllocError::from(failure), AllocError);
+    }
+}
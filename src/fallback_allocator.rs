@@ -0,0 +1,156 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    ptr::NonNull,
+};
+
+#[cfg(feature = "arena_allocator")]
+use crate::arena_allocator::ArenaAllocator;
+#[cfg(feature = "c_allocator")]
+use crate::c_allocator::CAllocator;
+#[cfg(feature = "noop_allocator")]
+use crate::noop_allocator::NoopAllocator;
+
+/// Allocators that can tell whether a pointer was handed out by them.
+pub trait AllocatorOwnership {
+    /// Returns `true` if the block at `ptr`, allocated with `layout`, lies in memory managed by
+    /// this allocator.
+    fn contains(&self, ptr: NonNull<u8>, layout: Layout) -> bool;
+}
+
+impl<A: AllocatorOwnership + ?Sized> AllocatorOwnership for &A {
+    fn contains(&self, ptr: NonNull<u8>, layout: Layout) -> bool {
+        (**self).contains(ptr, layout)
+    }
+}
+
+#[cfg(feature = "arena_allocator")]
+impl<A: Allocator> AllocatorOwnership for ArenaAllocator<A> {
+    fn contains(&self, ptr: NonNull<u8>, layout: Layout) -> bool {
+        // Zero-sized allocations may sit exactly at the end of the buffer, so the end is inclusive
+        // for them; any other block starting there belongs to someone else.
+        let end = self.base_ptr() as usize + self.capacity();
+        Self::contains(self, ptr) || (layout.size() == 0 && ptr.as_ptr() as usize == end)
+    }
+}
+
+#[cfg(feature = "c_allocator")]
+impl AllocatorOwnership for CAllocator {
+    fn contains(&self, ptr: NonNull<u8>, _: Layout) -> bool {
+        Self::contains(self, ptr)
+    }
+}
+
+#[cfg(feature = "noop_allocator")]
+impl AllocatorOwnership for NoopAllocator {
+    fn contains(&self, ptr: NonNull<u8>, _: Layout) -> bool {
+        Self::contains(self, ptr)
+    }
+}
+
+/// A composite allocator that serves allocations from `primary` and falls back to `secondary`
+/// when `primary` fails.
+///
+/// Deallocations are routed back to `primary` if it reports owning the pointer through
+/// [`AllocatorOwnership`], and to `secondary` otherwise.
+#[derive(Debug, Default)]
+#[allow(clippy::module_name_repetitions)]
+pub struct FallbackAllocator<P, S> {
+    primary: P,
+    secondary: S,
+}
+
+impl<P, S> FallbackAllocator<P, S> {
+    /// Creates a new allocator that tries `primary` first and `secondary` on failure.
+    pub const fn new(primary: P, secondary: S) -> Self {
+        Self { primary, secondary }
+    }
+
+    /// Returns a reference to the primary allocator.
+    pub const fn primary(&self) -> &P {
+        &self.primary
+    }
+
+    /// Returns a reference to the secondary allocator.
+    pub const fn secondary(&self) -> &S {
+        &self.secondary
+    }
+}
+
+unsafe impl<P, S> Allocator for FallbackAllocator<P, S>
+where
+    P: Allocator + AllocatorOwnership,
+    S: Allocator,
+{
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.primary
+            .allocate(layout)
+            .or_else(|_| self.secondary.allocate(layout))
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        if self.primary.contains(allocated_ptr, layout) {
+            self.primary.deallocate(allocated_ptr, layout);
+        } else {
+            self.secondary.deallocate(allocated_ptr, layout);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "arena_allocator", feature = "c_allocator"))]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests that allocations overflow from the arena into the C allocator.
+    fn test_fallback_to_secondary() {
+        let allocator = FallbackAllocator::new(ArenaAllocator::new(64), CAllocator);
+        let layout = Layout::from_size_align(48, 8).unwrap();
+
+        let first = allocator.allocate(layout).unwrap();
+        let second = allocator.allocate(layout).unwrap();
+
+        assert!(allocator.primary().contains(first.as_non_null_ptr()));
+        assert!(!allocator.primary().contains(second.as_non_null_ptr()));
+
+        unsafe {
+            allocator.deallocate(first.as_non_null_ptr(), layout);
+            allocator.deallocate(second.as_non_null_ptr(), layout);
+        }
+    }
+
+    #[test]
+    /// Tests that only zero-sized blocks are owned at the end of the arena's buffer.
+    fn test_ownership_at_buffer_end() {
+        let arena = ArenaAllocator::new(64);
+        let end = unsafe { NonNull::new_unchecked(arena.base_ptr().add(arena.capacity())) };
+
+        assert!(AllocatorOwnership::contains(
+            &arena,
+            end,
+            Layout::from_size_align(0, 1).unwrap()
+        ));
+        assert!(!AllocatorOwnership::contains(
+            &arena,
+            end,
+            Layout::from_size_align(8, 8).unwrap()
+        ));
+    }
+
+    #[test]
+    /// Tests the `FallbackAllocator` with a generic vector that outgrows the arena.
+    fn test_generic_vector_with_fallback_allocator() {
+        let allocator = FallbackAllocator::new(ArenaAllocator::new(64), CAllocator);
+        let mut vector: Vec<usize, &FallbackAllocator<ArenaAllocator, CAllocator>> =
+            Vec::new_in(&allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+}
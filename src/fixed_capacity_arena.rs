@@ -0,0 +1,171 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    cell::UnsafeCell,
+    mem::MaybeUninit,
+    ptr::{self, NonNull},
+};
+
+use crate::align::align_up;
+
+/// A bump-pointer memory allocator whose `N`-byte backing buffer is stored inline, without any
+/// heap allocation.
+///
+/// Because the buffer lives inside the struct, every pointer handed out points into the struct
+/// itself. The arena must therefore not be moved while any of its allocations are in use, so
+/// [`Allocator`] is only implemented for `&FixedCapacityArena<N>`: collections borrow the arena,
+/// and the borrow checker keeps it in place for as long as they live.
+///
+/// The offset is a plain cell, so the arena is not `Sync` and cannot be a `#[global_allocator]`;
+/// use [`StaticArena`](crate::static_arena::StaticArena) or
+/// [`SyncArenaAllocator`](crate::sync_arena::SyncArenaAllocator) for that.
+///
+/// ```compile_fail
+/// #![feature(allocator_api)]
+///
+/// use mem_allocs::fixed_capacity_arena::FixedCapacityArena;
+///
+/// let arena = FixedCapacityArena::<256>::new();
+/// let values = Vec::<u32, _>::with_capacity_in(4, &arena);
+/// let moved = arena;
+/// drop(values);
+/// ```
+#[repr(C)]
+#[derive(Debug)]
+pub struct FixedCapacityArena<const N: usize> {
+    buffer: UnsafeCell<[MaybeUninit<u8>; N]>,
+    offset: UnsafeCell<usize>,
+}
+
+impl<const N: usize> FixedCapacityArena<N> {
+    /// Creates a new, empty arena.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            buffer: UnsafeCell::new([MaybeUninit::uninit(); N]),
+            offset: UnsafeCell::new(0),
+        }
+    }
+
+    /// Returns the total size of the backing buffer in bytes.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of bytes consumed so far, including alignment padding.
+    #[must_use]
+    pub fn used(&self) -> usize {
+        unsafe { *self.offset.get() }
+    }
+
+    /// Returns the number of bytes still available for allocation.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        N - self.used()
+    }
+
+    /// Resets the arena, making its whole buffer available again.
+    ///
+    /// Taking `&mut self` ensures no collection still borrows the arena. Raw pointers handed out
+    /// before the reset must no longer be used.
+    pub const fn reset(&mut self) {
+        *self.offset.get_mut() = 0;
+    }
+
+    /// Returns a pointer to the start of the backing buffer.
+    const fn base_ptr(&self) -> *mut u8 {
+        self.buffer.get().cast::<u8>()
+    }
+
+    /// Bumps the offset past a block for `layout` and returns the block.
+    fn bump(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let base = self.base_ptr();
+        let offset = self.used();
+        let start =
+            align_up(base as usize + offset, layout.align()).ok_or(AllocError)? - base as usize;
+        let end = start.checked_add(layout.size()).ok_or(AllocError)?;
+
+        if end > N {
+            return Err(AllocError);
+        }
+
+        unsafe { *self.offset.get() = end };
+        let allocated_ptr = unsafe { base.add(start) };
+
+        NonNull::new(ptr::slice_from_raw_parts_mut(allocated_ptr, layout.size())).ok_or(AllocError)
+    }
+}
+
+impl<const N: usize> Default for FixedCapacityArena<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// SAFETY: the returned pointers point into the inline buffer, and the allocator borrows the arena,
+// so the arena cannot move while a collection that allocated from it is alive.
+unsafe impl<const N: usize> Allocator for &FixedCapacityArena<N> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.bump(layout)
+    }
+
+    unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {}
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `FixedCapacityArena` with a generic vector.
+    fn test_generic_vector_with_fixed_capacity_arena() {
+        let allocator = FixedCapacityArena::<1024>::new();
+        let mut vector: Vec<usize, &FixedCapacityArena<1024>> =
+            Vec::with_capacity_in(100, &allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    /// Tests that allocations stay inside the inline buffer and fail once it is exhausted.
+    fn test_out_of_memory_and_reset() {
+        let mut arena = FixedCapacityArena::<64>::new();
+        let layout = Layout::from_size_align(32, 8).unwrap();
+
+        let first = (&arena).allocate(layout).unwrap();
+        assert_eq!(
+            first.as_mut_ptr(),
+            core::ptr::from_ref(&arena).cast_mut().cast::<u8>()
+        );
+        assert!((&arena).allocate(layout).is_ok());
+        assert!((&arena).allocate(layout).is_err());
+
+        arena.reset();
+        assert_eq!(arena.used(), 0);
+        assert_eq!(arena.remaining(), 64);
+    }
+
+    #[test]
+    /// Tests that allocations respect the requested alignment.
+    fn test_alignment() {
+        let arena = FixedCapacityArena::<256>::new();
+        (&arena)
+            .allocate(Layout::from_size_align(1, 1).unwrap())
+            .unwrap();
+        let aligned = (&arena)
+            .allocate(Layout::from_size_align(8, 64).unwrap())
+            .unwrap();
+
+        assert_eq!(aligned.as_mut_ptr() as usize % 64, 0);
+    }
+}
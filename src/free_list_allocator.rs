@@ -0,0 +1,441 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    cell::Cell,
+    mem::{self, MaybeUninit},
+    ptr::NonNull,
+};
+
+use alloc::vec::Vec;
+
+use crate::align::align_up;
+
+/// Node stored at the start of every free block, linking free blocks in address order.
+#[derive(Debug)]
+struct FreeBlock {
+    size: usize,
+    next: Option<NonNull<Self>>,
+}
+
+/// Header stored right in front of every allocation, describing the block it was carved from.
+#[derive(Debug)]
+struct AllocHeader {
+    block_start: NonNull<u8>,
+    block_size: usize,
+}
+
+/// Granularity of all blocks; large enough and aligned for both a [`FreeBlock`] and an
+/// [`AllocHeader`].
+const UNIT: usize = mem::size_of::<FreeBlock>();
+
+/// A cell of the backing storage, aligned to at least `UNIT` bytes.
+///
+/// Blocks are laid out on absolute multiples of `UNIT`, so the buffer must start on one; a plain
+/// `[usize; 2]` would only guarantee the alignment of a `usize`.
+#[derive(Debug, Clone, Copy)]
+#[repr(align(16))]
+#[allow(dead_code)] // The field is only storage, accessed through raw pointers.
+struct Unit(MaybeUninit<[usize; 2]>);
+
+const _: () = assert!(mem::align_of::<Unit>().is_multiple_of(UNIT));
+
+/// How a [`FreeListAllocator`] picks a free block among those large enough for a request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SearchStrategy {
+    /// Uses the first block that fits, searching from the head of the list every time.
+    #[default]
+    FirstFit,
+    /// Walks the whole list and uses the smallest block that fits, keeping large blocks intact.
+    BestFit,
+    /// Uses the largest block, leaving the largest possible remainder.
+    WorstFit,
+    /// Uses the first block that fits at or after the point where the previous allocation was
+    /// carved off, wrapping around to the head. This spreads allocations over the buffer instead
+    /// of piling small fragments up at its start.
+    NextFit,
+}
+
+/// A general-purpose memory allocator that tracks free regions of a fixed buffer in an intrusive
+/// linked list.
+///
+/// Allocation searches the list according to the chosen [`SearchStrategy`] and splits the
+/// chosen block; deallocation puts the block back and coalesces it with free neighbours.
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct FreeListAllocator {
+    // Only kept to own the memory; all accesses go through the free list.
+    _buffer: Vec<Unit>,
+    capacity: usize,
+    head: Cell<Option<NonNull<FreeBlock>>>,
+    strategy: SearchStrategy,
+    /// Address where the previous allocation ended, at which [`SearchStrategy::NextFit`] resumes.
+    /// It is only compared against, never dereferenced, so it stays valid whatever happens to the
+    /// block it pointed into.
+    cursor: Cell<usize>,
+}
+
+impl FreeListAllocator {
+    /// Creates a new allocator managing `bytes` bytes with the [`SearchStrategy::FirstFit`]
+    /// strategy.
+    #[must_use]
+    pub fn new(bytes: usize) -> Self {
+        Self::with_strategy(bytes, SearchStrategy::default())
+    }
+
+    /// Creates a new allocator managing `bytes` bytes, rounded down to the block granularity, and
+    /// searching for free blocks with `strategy`.
+    #[must_use]
+    pub fn with_strategy(bytes: usize, strategy: SearchStrategy) -> Self {
+        let unit = mem::size_of::<Unit>();
+        let mut buffer: Vec<Unit> = alloc::vec![Unit(MaybeUninit::uninit()); bytes / unit];
+        let base = NonNull::new(buffer.as_mut_ptr().cast::<u8>()).unwrap_or(NonNull::dangling());
+        let capacity = buffer.len() * unit;
+
+        let head = (capacity > 0).then(|| {
+            let block = base.cast::<FreeBlock>();
+            unsafe {
+                block.write(FreeBlock {
+                    size: capacity,
+                    next: None,
+                });
+            }
+            block
+        });
+
+        Self {
+            _buffer: buffer,
+            capacity,
+            head: Cell::new(head),
+            strategy,
+            cursor: Cell::new(0),
+        }
+    }
+
+    /// Returns the total number of bytes managed by the allocator.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the search strategy used by the allocator.
+    #[must_use]
+    pub const fn strategy(&self) -> SearchStrategy {
+        self.strategy
+    }
+
+    /// Returns the total number of free bytes, including space later used for headers.
+    #[must_use]
+    pub fn free_bytes(&self) -> usize {
+        self.free_blocks()
+            .map(|block| unsafe { block.as_ref().size })
+            .sum()
+    }
+
+    /// Returns the number of separate free blocks.
+    #[must_use]
+    pub fn free_block_count(&self) -> usize {
+        self.free_blocks().count()
+    }
+
+    /// Iterates over the free blocks in address order.
+    fn free_blocks(&self) -> impl Iterator<Item = NonNull<FreeBlock>> + '_ {
+        core::iter::successors(self.head.get(), |block| unsafe { block.as_ref().next })
+    }
+
+    /// Returns the offset of the user allocation and the number of bytes needed from a free
+    /// block at `block` to serve `layout`, or `None` if the block is too small.
+    fn fit(block: NonNull<FreeBlock>, layout: Layout) -> Option<(usize, usize)> {
+        let block_start = block.as_ptr() as usize;
+        let align = layout.align().max(UNIT);
+        let user_start = align_up(block_start.checked_add(UNIT)?, align)?;
+        let needed = align_up(user_start.checked_add(layout.size())?, UNIT)? - block_start;
+        let size = unsafe { block.as_ref().size };
+        (needed <= size).then_some((user_start - block_start, needed))
+    }
+
+    /// Finds a free block for `layout`, returning it together with its predecessor in the list.
+    fn find(&self, layout: Layout) -> Option<(Option<NonNull<FreeBlock>>, NonNull<FreeBlock>)> {
+        let mut previous = None;
+        let mut chosen: Option<(Option<NonNull<FreeBlock>>, NonNull<FreeBlock>)> = None;
+
+        for block in self.free_blocks() {
+            if Self::fit(block, layout).is_some() {
+                let size = unsafe { block.as_ref().size };
+                let better = chosen.is_none_or(|(_, current)| {
+                    let current_size = unsafe { current.as_ref().size };
+                    match self.strategy {
+                        SearchStrategy::FirstFit => false,
+                        SearchStrategy::BestFit => size < current_size,
+                        SearchStrategy::WorstFit => size > current_size,
+                        // Blocks before the cursor only serve as the wrap-around fallback.
+                        SearchStrategy::NextFit => block.as_ptr() as usize >= self.cursor.get(),
+                    }
+                });
+                if better {
+                    chosen = Some((previous, block));
+                    let done = match self.strategy {
+                        SearchStrategy::FirstFit => true,
+                        SearchStrategy::NextFit => block.as_ptr() as usize >= self.cursor.get(),
+                        SearchStrategy::BestFit | SearchStrategy::WorstFit => false,
+                    };
+                    if done {
+                        break;
+                    }
+                }
+            }
+            previous = Some(block);
+        }
+
+        chosen
+    }
+
+    /// Replaces the link from `previous` (or the list head) to `next`.
+    fn link(&self, previous: Option<NonNull<FreeBlock>>, next: Option<NonNull<FreeBlock>>) {
+        match previous {
+            Some(mut previous) => unsafe { previous.as_mut().next = next },
+            None => self.head.set(next),
+        }
+    }
+}
+
+unsafe impl Allocator for FreeListAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let (previous, block) = self.find(layout).ok_or(AllocError)?;
+        let (user_offset, mut needed) = Self::fit(block, layout).ok_or(AllocError)?;
+        let FreeBlock { size, next } = unsafe { block.read() };
+
+        // Split off the tail if it is large enough to hold a free block of its own.
+        let remainder = size - needed;
+        if remainder >= UNIT {
+            let tail = unsafe { block.byte_add(needed) };
+            unsafe {
+                tail.write(FreeBlock {
+                    size: remainder,
+                    next,
+                });
+            }
+            self.link(previous, Some(tail));
+        } else {
+            needed = size;
+            self.link(previous, next);
+        }
+
+        self.cursor.set(block.as_ptr() as usize + needed);
+        unsafe {
+            let block_start = block.cast::<u8>();
+            let user_ptr = block_start.add(user_offset);
+            user_ptr.sub(UNIT).cast::<AllocHeader>().write(AllocHeader {
+                block_start,
+                block_size: needed,
+            });
+            Ok(NonNull::slice_from_raw_parts(user_ptr, layout.size()))
+        }
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, _: Layout) {
+        let AllocHeader {
+            block_start,
+            block_size,
+        } = allocated_ptr.sub(UNIT).cast::<AllocHeader>().read();
+        let block = block_start.cast::<FreeBlock>();
+        let mut size = block_size;
+
+        // Find the free neighbours on either side, keeping the list sorted by address.
+        let mut previous: Option<NonNull<FreeBlock>> = None;
+        let mut next = self.head.get();
+        while let Some(candidate) = next {
+            if candidate > block {
+                break;
+            }
+            previous = Some(candidate);
+            next = candidate.as_ref().next;
+        }
+
+        if let Some(next_block) = next {
+            if block.byte_add(size) == next_block {
+                size += next_block.as_ref().size;
+                next = next_block.as_ref().next;
+            }
+        }
+
+        if let Some(mut previous_block) = previous {
+            if previous_block.byte_add(previous_block.as_ref().size) == block {
+                previous_block.as_mut().size += size;
+                previous_block.as_mut().next = next;
+                return;
+            }
+        }
+
+        block.write(FreeBlock { size, next });
+        self.link(previous, Some(block));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `FreeListAllocator` with a generic vector.
+    fn test_generic_vector_with_free_list_allocator() {
+        let allocator = FreeListAllocator::new(4096);
+        let mut vector: Vec<usize, &FreeListAllocator> = Vec::new_in(&allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+        assert_eq!(allocator.free_bytes(), allocator.capacity());
+        assert_eq!(allocator.free_block_count(), 1);
+    }
+
+    #[test]
+    /// Tests that allocations respect the requested alignment and do not overlap.
+    fn test_alignment() {
+        let allocator = FreeListAllocator::new(1024);
+        let small = allocator
+            .allocate(Layout::from_size_align(3, 1).unwrap())
+            .unwrap();
+        let aligned = allocator
+            .allocate(Layout::from_size_align(8, 128).unwrap())
+            .unwrap();
+
+        assert_eq!(aligned.as_mut_ptr() as usize % 128, 0);
+        assert!(aligned.as_mut_ptr() as usize >= small.as_mut_ptr() as usize + 3);
+    }
+
+    #[test]
+    /// Tests that freeing three adjacent blocks in every possible order coalesces them back into
+    /// a single free block covering the whole buffer.
+    fn test_coalescing_in_every_order() {
+        const ORDERS: [[usize; 3]; 6] = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+        let layout = Layout::from_size_align(48, 8).unwrap();
+
+        for order in ORDERS {
+            let allocator = FreeListAllocator::new(256);
+            let blocks: Vec<NonNull<[u8]>> = (0..3)
+                .map(|_| allocator.allocate(layout).unwrap())
+                .collect();
+
+            for (freed, &index) in order.iter().enumerate() {
+                unsafe { allocator.deallocate(blocks[index].as_non_null_ptr(), layout) };
+
+                let all_freed = &order[..=freed];
+                let gaps = (0..3)
+                    .filter(|index| all_freed.contains(index))
+                    .fold((0, None), |(runs, last), index| {
+                        let starts_run = last.is_none_or(|last: usize| last + 1 != index);
+                        (runs + usize::from(starts_run), Some(index))
+                    })
+                    .0;
+                // The tail of the buffer after the third block is free too and merges with it.
+                let merges_with_tail = all_freed.contains(&2);
+                let expected = gaps + usize::from(!merges_with_tail);
+                assert_eq!(allocator.free_block_count(), expected, "order {order:?}");
+            }
+
+            assert_eq!(allocator.free_block_count(), 1, "order {order:?}");
+            assert_eq!(
+                allocator.free_bytes(),
+                allocator.capacity(),
+                "order {order:?}"
+            );
+        }
+    }
+
+    #[test]
+    /// Tests that an exhausted allocator fails and recovers after a deallocation.
+    fn test_out_of_memory() {
+        let allocator = FreeListAllocator::new(128);
+        let layout = Layout::from_size_align(100, 8).unwrap();
+
+        let block = allocator.allocate(layout).unwrap();
+        assert!(allocator.allocate(layout).is_err());
+
+        unsafe { allocator.deallocate(block.as_non_null_ptr(), layout) };
+        assert!(allocator.allocate(layout).is_ok());
+    }
+
+    /// Builds an allocator whose free list holds a small hole, a large hole, and the tail, in
+    /// that address order.
+    fn fragmented(strategy: SearchStrategy) -> FreeListAllocator {
+        let allocator = FreeListAllocator::with_strategy(1024, strategy);
+        let small = Layout::from_size_align(48, 8).unwrap();
+        let large = Layout::from_size_align(240, 8).unwrap();
+
+        let small_block = allocator.allocate(small).unwrap();
+        allocator.allocate(small).unwrap();
+        let large_block = allocator.allocate(large).unwrap();
+        allocator.allocate(small).unwrap();
+
+        unsafe {
+            allocator.deallocate(small_block.as_non_null_ptr(), small);
+            allocator.deallocate(large_block.as_non_null_ptr(), large);
+        }
+        assert_eq!(allocator.free_block_count(), 3);
+        allocator
+    }
+
+    #[test]
+    /// Tests which free block each strategy picks for a small request.
+    fn test_strategies() {
+        let layout = Layout::from_size_align(16, 8).unwrap();
+
+        let first_fit = fragmented(SearchStrategy::FirstFit);
+        let first_hole = first_fit.free_blocks().next().unwrap();
+        let chosen = first_fit.allocate(layout).unwrap();
+        assert_eq!(chosen.as_mut_ptr(), unsafe {
+            first_hole.cast::<u8>().add(UNIT).as_ptr()
+        });
+
+        let best_fit = fragmented(SearchStrategy::BestFit);
+        let smallest_hole = best_fit.free_blocks().next().unwrap();
+        let chosen = best_fit.allocate(layout).unwrap();
+        assert_eq!(chosen.as_mut_ptr(), unsafe {
+            smallest_hole.cast::<u8>().add(UNIT).as_ptr()
+        });
+
+        let worst_fit = fragmented(SearchStrategy::WorstFit);
+        let largest_hole = worst_fit.free_blocks().last().unwrap();
+        let chosen = worst_fit.allocate(layout).unwrap();
+        assert_eq!(chosen.as_mut_ptr(), unsafe {
+            largest_hole.cast::<u8>().add(UNIT).as_ptr()
+        });
+    }
+
+    #[test]
+    /// Tests that next-fit resumes after the previous allocation and wraps around to the head.
+    fn test_next_fit_wraps_around() {
+        let allocator = fragmented(SearchStrategy::NextFit);
+        let small_hole = allocator.free_blocks().next().unwrap();
+        let tail = allocator.free_blocks().last().unwrap();
+
+        // The holes lie before the end of the previous allocation, so the tail is used first.
+        let layout = Layout::from_size_align(16, 8).unwrap();
+        let chosen = allocator.allocate(layout).unwrap();
+        assert_eq!(chosen.as_mut_ptr(), unsafe {
+            tail.cast::<u8>().add(UNIT).as_ptr()
+        });
+
+        // Use up the rest of the tail, leaving nothing after the cursor.
+        let rest = unsafe { allocator.free_blocks().last().unwrap().as_ref().size };
+        let rest_layout = Layout::from_size_align(rest - UNIT, 8).unwrap();
+        allocator.allocate(rest_layout).unwrap();
+
+        let chosen = allocator.allocate(layout).unwrap();
+        assert_eq!(chosen.as_mut_ptr(), unsafe {
+            small_hole.cast::<u8>().add(UNIT).as_ptr()
+        });
+    }
+}
@@ -0,0 +1,241 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    ptr::NonNull,
+};
+
+#[cfg(debug_assertions)]
+use core::{cell::RefCell, ptr, slice};
+
+#[cfg(debug_assertions)]
+use alloc::vec::Vec;
+
+use alloc::alloc::Global;
+
+use crate::arena_allocator::ArenaAllocator;
+
+/// Number of canary bytes written before and after every allocation in debug builds.
+pub const CANARY_SIZE: usize = 4;
+
+/// The bytes written into every canary, `0xDEADBEEF` in memory order.
+pub const CANARY: [u8; CANARY_SIZE] = 0xDEAD_BEEF_u32.to_be_bytes();
+
+/// An [`ArenaAllocator`] that surrounds every allocation with canary bytes to catch
+/// out-of-bounds writes.
+///
+/// In debug builds each block is preceded and followed by [`CANARY`]. Deallocating a block checks
+/// both of its canaries and [`GuardedArenaAllocator::check_canaries`] checks those of every live
+/// block; either panics, naming the block, if a canary was overwritten. With `debug_assertions`
+/// off no canaries are written and the allocator is a plain arena. This is a debugging aid, not a
+/// production allocator.
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct GuardedArenaAllocator<A: Allocator = Global> {
+    arena: ArenaAllocator<A>,
+    #[cfg(debug_assertions)]
+    live: RefCell<Vec<(usize, Layout)>>,
+}
+
+impl GuardedArenaAllocator {
+    /// Creates a new guarded arena with a backing buffer of `bytes` bytes, which also has to hold
+    /// the canaries.
+    #[must_use]
+    pub fn new(bytes: usize) -> Self {
+        Self::new_with_allocator(bytes, Global)
+    }
+}
+
+impl<A: Allocator> GuardedArenaAllocator<A> {
+    /// Creates a new guarded arena whose backing buffer of `bytes` bytes is allocated from `alloc`.
+    #[must_use]
+    pub fn new_with_allocator(bytes: usize, alloc: A) -> Self {
+        Self {
+            arena: ArenaAllocator::new_with_allocator(bytes, alloc),
+            #[cfg(debug_assertions)]
+            live: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Returns the size of the backing buffer in bytes.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.arena.capacity()
+    }
+
+    /// Returns the number of bytes consumed so far, including canaries and alignment padding.
+    #[must_use]
+    pub fn used(&self) -> usize {
+        self.arena.used()
+    }
+
+    /// Checks the canaries of every live allocation.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if any canary was overwritten.
+    #[cfg_attr(
+        not(debug_assertions),
+        allow(clippy::missing_const_for_fn, clippy::unused_self)
+    )]
+    pub fn check_canaries(&self) {
+        #[cfg(debug_assertions)]
+        for &(address, layout) in self.live.borrow().iter() {
+            unsafe { check_block(address as *const u8, layout) };
+        }
+    }
+
+    /// Resets the arena and forgets every live allocation.
+    ///
+    /// Taking `&mut self` ensures nothing borrowing the arena outlives the reset. Raw pointers
+    /// handed out before the reset must no longer be used.
+    #[cfg_attr(not(debug_assertions), allow(clippy::missing_const_for_fn))]
+    pub fn reset(&mut self) {
+        #[cfg(debug_assertions)]
+        self.live.get_mut().clear();
+        self.arena.reset();
+    }
+}
+
+/// Returns the offset of the user block inside the guarded block: the canary rounded up to the
+/// block's alignment.
+#[cfg(debug_assertions)]
+const fn front_padding(layout: Layout) -> usize {
+    CANARY_SIZE.next_multiple_of(layout.align())
+}
+
+/// Returns the layout of the guarded block that holds a user block of `layout`.
+#[cfg(debug_assertions)]
+fn guarded_layout(layout: Layout) -> Result<Layout, AllocError> {
+    let size = front_padding(layout)
+        .checked_add(layout.size())
+        .and_then(|size| size.checked_add(CANARY_SIZE))
+        .ok_or(AllocError)?;
+    Layout::from_size_align(size, layout.align()).map_err(|_| AllocError)
+}
+
+/// Panics if a canary around the user block at `block` was overwritten.
+///
+/// # Safety
+///
+/// `block` must be a live user block of `layout` from a `GuardedArenaAllocator`.
+#[cfg(debug_assertions)]
+#[track_caller]
+unsafe fn check_block(block: *const u8, layout: Layout) {
+    let front = slice::from_raw_parts(block.sub(CANARY_SIZE), CANARY_SIZE);
+    assert!(
+        front == CANARY,
+        "guarded arena: canary before block {block:p} was overwritten"
+    );
+    let back = slice::from_raw_parts(block.add(layout.size()), CANARY_SIZE);
+    assert!(
+        back == CANARY,
+        "guarded arena: canary after block {block:p} of {} bytes was overwritten",
+        layout.size()
+    );
+}
+
+unsafe impl<A: Allocator> Allocator for GuardedArenaAllocator<A> {
+    #[cfg(debug_assertions)]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let guarded = self.arena.allocate(guarded_layout(layout)?)?;
+        unsafe {
+            let block = guarded.as_mut_ptr().add(front_padding(layout));
+            ptr::copy_nonoverlapping(CANARY.as_ptr(), block.sub(CANARY_SIZE), CANARY_SIZE);
+            ptr::copy_nonoverlapping(CANARY.as_ptr(), block.add(layout.size()), CANARY_SIZE);
+            self.live.borrow_mut().push((block as usize, layout));
+            Ok(NonNull::slice_from_raw_parts(
+                NonNull::new_unchecked(block),
+                layout.size(),
+            ))
+        }
+    }
+
+    #[cfg(not(debug_assertions))]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.arena.allocate(layout)
+    }
+
+    /// # Panics
+    ///
+    /// Panics in debug builds if a canary around the block was overwritten.
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        #[cfg(debug_assertions)]
+        {
+            check_block(allocated_ptr.as_ptr(), layout);
+            let address = allocated_ptr.as_ptr() as usize;
+            let mut live = self.live.borrow_mut();
+            if let Some(index) = live.iter().position(|&(logged, _)| logged == address) {
+                live.swap_remove(index);
+            }
+        }
+        self.arena.deallocate(allocated_ptr, layout);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `GuardedArenaAllocator` with a generic vector.
+    fn test_generic_vector_with_guarded_arena() {
+        let allocator = GuardedArenaAllocator::new(8192);
+        let mut vector: Vec<usize, &GuardedArenaAllocator> = Vec::with_capacity_in(4, &allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        allocator.check_canaries();
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    /// Tests that every block is surrounded by canaries and stays aligned.
+    fn test_canaries_written() {
+        let allocator = GuardedArenaAllocator::new(256);
+        let layout = Layout::from_size_align(10, 16).unwrap();
+        let block = allocator.allocate(layout).unwrap().as_mut_ptr();
+
+        assert!((block as usize).is_multiple_of(16));
+        assert!(allocator.used() >= 2 * CANARY_SIZE + 10);
+        assert!(allocator.used() <= allocator.capacity());
+        unsafe {
+            assert_eq!(
+                slice::from_raw_parts(block.sub(CANARY_SIZE), CANARY_SIZE),
+                CANARY
+            );
+            assert_eq!(slice::from_raw_parts(block.add(10), CANARY_SIZE), CANARY);
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "bytes was overwritten")]
+    /// Tests that writing past the end of a block is caught on deallocation.
+    fn test_overflow_detected_on_deallocate() {
+        let allocator = GuardedArenaAllocator::new(256);
+        let layout = Layout::from_size_align(8, 8).unwrap();
+        let block = allocator.allocate(layout).unwrap();
+        unsafe {
+            block.as_mut_ptr().add(8).write(0);
+            allocator.deallocate(block.as_non_null_ptr(), layout);
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "canary before block")]
+    /// Tests that writing before the start of a block is caught by `check_canaries`.
+    fn test_underflow_detected_by_check() {
+        let allocator = GuardedArenaAllocator::new(256);
+        let block = allocator.allocate(Layout::new::<u64>()).unwrap();
+        allocator.check_canaries();
+        unsafe { block.as_mut_ptr().sub(1).write(0) };
+        allocator.check_canaries();
+    }
+}
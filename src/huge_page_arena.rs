@@ -0,0 +1,169 @@
+use core::{
+    alloc::{AllocError, Allocator, GlobalAlloc, Layout},
+    mem::MaybeUninit,
+    ptr::{self, NonNull},
+};
+
+use libc::{
+    mmap, munmap, MAP_ANONYMOUS, MAP_FAILED, MAP_HUGETLB, MAP_PRIVATE, PROT_READ, PROT_WRITE,
+};
+
+use crate::arena_allocator::ArenaAllocatorView;
+
+/// Size of the default huge page on x86-64 and most 64-bit Arm Linux systems.
+pub const HUGE_PAGE_SIZE: usize = 2 * 1024 * 1024;
+
+/// A bump-pointer arena backed by an anonymous huge-page mapping.
+///
+/// Huge pages cut down on TLB misses for large working sets. The capacity is rounded up to a
+/// multiple of [`HUGE_PAGE_SIZE`] and mapped with `MAP_HUGETLB`; when the system has no huge pages
+/// to spare, the arena falls back to a regular anonymous mapping of the same size, which
+/// [`HugePageArena::is_huge_page`] reports. Apart from its backing memory the arena behaves like
+/// an [`ArenaAllocator`](crate::arena_allocator::ArenaAllocator).
+#[derive(Debug)]
+pub struct HugePageArena {
+    mapping: NonNull<u8>,
+    huge_page: bool,
+    arena: ArenaAllocatorView<'static>,
+}
+
+impl HugePageArena {
+    /// Creates a new arena with at least `capacity` bytes, rounded up to whole huge pages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError` if the capacity overflows or neither mapping succeeds.
+    pub fn new(capacity: usize) -> Result<Self, AllocError> {
+        let length = capacity
+            .max(1)
+            .checked_next_multiple_of(HUGE_PAGE_SIZE)
+            .ok_or(AllocError)?;
+
+        let (mapping, huge_page) = match unsafe { map(length, MAP_HUGETLB) } {
+            Some(mapping) => (mapping, true),
+            None => (unsafe { map(length, 0) }.ok_or(AllocError)?, false),
+        };
+
+        // The view never outlives the mapping: both live in `self`, and `Drop` unmaps the memory
+        // only once the arena is gone.
+        let buffer = unsafe {
+            core::slice::from_raw_parts_mut(mapping.as_ptr().cast::<MaybeUninit<u8>>(), length)
+        };
+        Ok(Self {
+            mapping,
+            huge_page,
+            arena: ArenaAllocatorView::new(buffer),
+        })
+    }
+
+    /// Returns `true` if the arena is backed by huge pages rather than regular pages.
+    #[must_use]
+    pub const fn is_huge_page(&self) -> bool {
+        self.huge_page
+    }
+
+    /// Returns the total size of the mapping in bytes.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.arena.capacity()
+    }
+
+    /// Returns the number of bytes consumed so far, including alignment padding.
+    #[must_use]
+    pub fn used(&self) -> usize {
+        self.arena.used()
+    }
+
+    /// Returns the number of bytes still available for allocation.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.arena.remaining()
+    }
+
+    /// Resets the arena, making its whole mapping available again.
+    ///
+    /// The unique borrow rules out live collections in the mapping; raw pointers from before the
+    /// reset must not be used afterwards.
+    pub const fn reset(&mut self) {
+        self.arena.reset();
+    }
+}
+
+/// Maps `length` bytes of anonymous memory with the extra mmap `flags`.
+unsafe fn map(length: usize, flags: i32) -> Option<NonNull<u8>> {
+    let mapped_ptr = mmap(
+        ptr::null_mut(),
+        length,
+        PROT_READ | PROT_WRITE,
+        MAP_PRIVATE | MAP_ANONYMOUS | flags,
+        -1,
+        0,
+    );
+    if mapped_ptr == MAP_FAILED {
+        None
+    } else {
+        NonNull::new(mapped_ptr.cast())
+    }
+}
+
+unsafe impl Allocator for HugePageArena {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.arena.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {}
+}
+
+unsafe impl GlobalAlloc for HugePageArena {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.arena.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, _: *mut u8, _: Layout) {}
+}
+
+impl Drop for HugePageArena {
+    fn drop(&mut self) {
+        unsafe { munmap(self.mapping.as_ptr().cast(), self.capacity()) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `HugePageArena` with a generic vector.
+    fn test_generic_vector_with_huge_page_arena() {
+        let arena = HugePageArena::new(1024).unwrap();
+        let mut vector: Vec<usize, &HugePageArena> = Vec::with_capacity_in(100, &arena);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    /// Tests that the capacity is rounded up to whole huge pages, with or without huge pages.
+    fn test_capacity_rounding() {
+        let mut arena = HugePageArena::new(HUGE_PAGE_SIZE + 1).unwrap();
+        assert_eq!(arena.capacity(), 2 * HUGE_PAGE_SIZE);
+        if arena.is_huge_page() {
+            assert_eq!(arena.mapping.as_ptr() as usize % HUGE_PAGE_SIZE, 0);
+        }
+
+        let layout = Layout::from_size_align(arena.capacity(), 1).unwrap();
+        let block = arena.allocate(layout).unwrap();
+        unsafe { ptr::write_bytes(block.as_mut_ptr(), 0xEE, layout.size()) };
+        assert!(arena.allocate(Layout::new::<u8>()).is_err());
+
+        arena.reset();
+        assert_eq!(arena.remaining(), arena.capacity());
+    }
+}
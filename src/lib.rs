@@ -1,5 +1,140 @@
 #![no_std]
-#![feature(allocator_api, cfg_match, slice_ptr_get)]
+#![feature(allocator_api, slice_ptr_get)]
 
+#[cfg(any(
+    feature = "arena_allocator",
+    feature = "double_ended_arena",
+    feature = "fixed_capacity_arena",
+    feature = "free_list_allocator",
+    all(
+        feature = "mmap_allocator",
+        any(target_os = "linux", target_os = "macos")
+    ),
+    feature = "recycling_arena",
+    feature = "stack_allocator",
+    feature = "static_arena",
+    feature = "sync_arena",
+    feature = "tlsf_allocator"
+))]
+mod align;
+#[cfg(feature = "aligned_vec")]
+pub mod aligned_vec;
+// Every feature that needs `alloc` enables the `alloc` feature; the unit tests use it as well.
+// The `alloc` feature on its own links the crate without using it.
+#[cfg(any(feature = "alloc", test))]
+#[allow(unused_extern_crates)]
+extern crate alloc;
+#[cfg(any(feature = "mutex_arena", feature = "thread_local_arena"))]
+extern crate std;
+
+#[cfg(feature = "alloc_box")]
+pub mod alloc_box;
+#[cfg(feature = "allocator_pool")]
+pub mod allocator_pool;
+#[cfg(feature = "arena_allocator")]
+pub mod arena_allocator;
+#[cfg(feature = "bitmap_allocator")]
+pub mod bitmap_allocator;
+#[cfg(any(feature = "bitmap_allocator", feature = "pool_allocator"))]
+mod block_buffer;
+#[cfg(feature = "buddy_allocator")]
+pub mod buddy_allocator;
 #[cfg(feature = "c_allocator")]
 pub mod c_allocator;
+#[cfg(feature = "checked_arena")]
+pub mod checked_arena;
+#[cfg(feature = "chunk_arena")]
+pub mod chunk_arena;
+#[cfg(feature = "debug_allocator")]
+pub mod debug_allocator;
+#[cfg(feature = "double_ended_arena")]
+pub mod double_ended_arena;
+pub mod error;
+#[cfg(feature = "fallback_allocator")]
+pub mod fallback_allocator;
+#[cfg(feature = "fixed_capacity_arena")]
+pub mod fixed_capacity_arena;
+#[cfg(feature = "free_list_allocator")]
+pub mod free_list_allocator;
+#[cfg(feature = "guarded_arena")]
+pub mod guarded_arena;
+#[cfg(all(feature = "huge_page", target_os = "linux"))]
+pub mod huge_page_arena;
+#[cfg(feature = "limited_allocator")]
+pub mod limited_allocator;
+#[cfg(feature = "linked_arena")]
+pub mod linked_arena;
+#[cfg(feature = "memory_budget")]
+pub mod memory_budget;
+#[cfg(feature = "mimalloc")]
+pub mod mimalloc_allocator;
+#[cfg(feature = "mirrored_allocator")]
+pub mod mirrored_allocator;
+#[cfg(all(
+    feature = "mmap_allocator",
+    any(target_os = "linux", target_os = "macos")
+))]
+pub mod mmap_allocator;
+#[cfg(feature = "mutex_arena")]
+pub mod mutex_arena;
+#[cfg(feature = "noop_allocator")]
+pub mod noop_allocator;
+#[cfg(feature = "padded_allocator")]
+pub mod padded_allocator;
+#[cfg(all(
+    feature = "page_allocator",
+    any(target_os = "linux", target_os = "macos", target_os = "windows")
+))]
+pub mod page_allocator;
+#[cfg(feature = "panic_on_oom")]
+pub mod panic_on_oom;
+#[cfg(feature = "pool_allocator")]
+pub mod pool_allocator;
+#[cfg(feature = "pool_arena")]
+pub mod pool_arena;
+#[cfg(feature = "quota_allocator")]
+pub mod quota_allocator;
+#[cfg(feature = "recording_allocator")]
+pub mod recording_allocator;
+#[cfg(feature = "recycling_arena")]
+pub mod recycling_arena;
+#[cfg(feature = "region")]
+pub mod region;
+#[cfg(feature = "report_allocator")]
+pub mod report_allocator;
+#[cfg(feature = "segmented_arena")]
+pub mod segmented_arena;
+#[cfg(feature = "segregated_allocator")]
+pub mod segregated_allocator;
+#[cfg(feature = "size_classes")]
+pub mod size_classes;
+#[cfg(feature = "slab_allocator")]
+pub mod slab_allocator;
+#[cfg(feature = "spin_arena")]
+pub mod spin_arena;
+#[cfg(feature = "stack_allocator")]
+pub mod stack_allocator;
+#[cfg(feature = "stack_frame")]
+pub mod stack_frame;
+#[cfg(feature = "static_arena")]
+pub mod static_arena;
+#[cfg(feature = "stats_allocator")]
+pub mod stats_allocator;
+#[cfg(feature = "sync_arena")]
+pub mod sync_arena;
+#[cfg(feature = "tagged_arena")]
+pub mod tagged_arena;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+#[cfg(feature = "thread_local_arena")]
+pub mod thread_local_arena;
+#[cfg(feature = "tlsf_allocator")]
+pub mod tlsf_allocator;
+#[cfg(feature = "tracing_allocator")]
+pub mod tracing_allocator;
+#[cfg(all(feature = "wasm_allocator", target_arch = "wasm32"))]
+pub mod wasm_allocator;
+#[cfg(all(feature = "win_heap_allocator", target_os = "windows"))]
+pub mod win_heap_allocator;
+#[cfg(feature = "zeroizing_allocator")]
+pub mod zeroizing_allocator;
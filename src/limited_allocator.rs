@@ -0,0 +1,167 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// A wrapper allocator that refuses to hand out more than a fixed number of bytes at once.
+///
+/// Freed bytes are returned to the budget, so the limit applies to bytes in use rather than to
+/// the total ever allocated.
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct LimitedAllocator<A: Allocator> {
+    inner: A,
+    limit: usize,
+    in_use: AtomicUsize,
+}
+
+impl<A: Allocator> LimitedAllocator<A> {
+    /// Wraps `inner`, allowing at most `limit_bytes` bytes to be allocated at any one time.
+    pub const fn new(inner: A, limit_bytes: usize) -> Self {
+        Self {
+            inner,
+            limit: limit_bytes,
+            in_use: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the maximum number of bytes that may be in use at once.
+    pub const fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Returns the number of bytes currently in use.
+    pub fn in_use(&self) -> usize {
+        self.in_use.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of bytes that can still be allocated before hitting the limit.
+    pub fn remaining(&self) -> usize {
+        self.limit.saturating_sub(self.in_use())
+    }
+
+    /// Claims `bytes` from the budget.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError` if claiming `bytes` would exceed the limit.
+    fn reserve(&self, bytes: usize) -> Result<(), AllocError> {
+        let previous = self.in_use.fetch_add(bytes, Ordering::Relaxed);
+        if previous
+            .checked_add(bytes)
+            .is_none_or(|total| total > self.limit)
+        {
+            self.release(bytes);
+            return Err(AllocError);
+        }
+        Ok(())
+    }
+
+    /// Returns `bytes` to the budget.
+    fn release(&self, bytes: usize) {
+        self.in_use.fetch_sub(bytes, Ordering::Relaxed);
+    }
+}
+
+unsafe impl<A: Allocator> Allocator for LimitedAllocator<A> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.reserve(layout.size())?;
+        self.inner.allocate(layout).inspect_err(|_| {
+            self.release(layout.size());
+        })
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.reserve(layout.size())?;
+        self.inner.allocate_zeroed(layout).inspect_err(|_| {
+            self.release(layout.size());
+        })
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        self.inner.deallocate(allocated_ptr, layout);
+        self.release(layout.size());
+    }
+
+    unsafe fn grow(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let delta = new_layout.size() - old_layout.size();
+        self.reserve(delta)?;
+        self.inner
+            .grow(old_ptr, old_layout, new_layout)
+            .inspect_err(|_| self.release(delta))
+    }
+
+    unsafe fn grow_zeroed(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let delta = new_layout.size() - old_layout.size();
+        self.reserve(delta)?;
+        self.inner
+            .grow_zeroed(old_ptr, old_layout, new_layout)
+            .inspect_err(|_| self.release(delta))
+    }
+
+    unsafe fn shrink(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let new_ptr = self.inner.shrink(old_ptr, old_layout, new_layout)?;
+        self.release(old_layout.size() - new_layout.size());
+        Ok(new_ptr)
+    }
+}
+
+#[cfg(all(test, feature = "c_allocator"))]
+mod tests {
+    use super::*;
+    use crate::c_allocator::CAllocator;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests that allocations beyond the limit fail and that deallocation restores the budget.
+    fn test_limit_enforced() {
+        let allocator = LimitedAllocator::new(CAllocator, 64);
+        let layout = Layout::from_size_align(48, 8).unwrap();
+
+        let first = allocator.allocate(layout).unwrap();
+        assert_eq!(allocator.in_use(), 48);
+        assert!(allocator.allocate(layout).is_err());
+        assert_eq!(allocator.in_use(), 48);
+
+        unsafe { allocator.deallocate(first.as_non_null_ptr(), layout) };
+        assert_eq!(allocator.in_use(), 0);
+
+        let second = allocator.allocate(layout).unwrap();
+        unsafe { allocator.deallocate(second.as_non_null_ptr(), layout) };
+    }
+
+    #[test]
+    /// Tests that growing and shrinking a vector adjusts the bytes in use.
+    fn test_generic_vector_with_limited_allocator() {
+        let allocator = LimitedAllocator::new(CAllocator, 64);
+        let mut vector: Vec<u8, &LimitedAllocator<CAllocator>> =
+            Vec::with_capacity_in(32, &allocator);
+        vector.extend(0..32);
+
+        assert!(vector.try_reserve_exact(64).is_err());
+        assert_eq!(allocator.in_use(), 32);
+
+        vector.reserve_exact(32);
+        assert_eq!(allocator.in_use(), 64);
+
+        vector.shrink_to_fit();
+        assert_eq!(allocator.in_use(), 32);
+        assert_eq!(vector.len(), 32);
+    }
+}
@@ -0,0 +1,135 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    cell::UnsafeCell,
+    ptr::NonNull,
+};
+
+use alloc::vec::Vec;
+
+use crate::arena_allocator::ArenaAllocator;
+
+/// A bump-pointer memory allocator that chains [`ArenaAllocator`] chunks, adding a new chunk
+/// whenever the current one runs out of space.
+///
+/// Chunks own heap-allocated buffers, so adding a chunk never moves memory that was already handed
+/// out. Like in a single arena, individual deallocations are ignored.
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct LinkedArenaAllocator {
+    chunks: UnsafeCell<Vec<ArenaAllocator>>,
+    chunk_capacity: usize,
+}
+
+impl LinkedArenaAllocator {
+    /// Creates a new allocator whose chunks hold `chunk_capacity` bytes each.
+    #[must_use]
+    pub fn new(chunk_capacity: usize) -> Self {
+        Self {
+            chunks: UnsafeCell::new(alloc::vec![ArenaAllocator::new(chunk_capacity)]),
+            chunk_capacity,
+        }
+    }
+
+    /// Returns the number of chunks currently allocated.
+    #[must_use]
+    pub fn chunk_count(&self) -> usize {
+        // SAFETY: the `UnsafeCell` makes the allocator `!Sync`, and no method keeps a reference
+        // into `chunks` past its own return, so nothing mutates the vector during this read.
+        unsafe { (*self.chunks.get()).len() }
+    }
+
+    /// Returns the combined capacity of all chunks in bytes.
+    #[must_use]
+    pub fn total_capacity(&self) -> usize {
+        // SAFETY: as in `chunk_count`, no other borrow of `chunks` is alive during this read.
+        unsafe {
+            (*self.chunks.get())
+                .iter()
+                .map(ArenaAllocator::capacity)
+                .sum()
+        }
+    }
+
+    /// Resets every chunk and releases all of them except the first.
+    ///
+    /// Taking `&mut self` ensures no collection still allocates from the chunks being freed.
+    /// Raw pointers handed out before the reset must no longer be used.
+    pub fn reset(&mut self) {
+        let chunks = self.chunks.get_mut();
+        chunks.truncate(1);
+        chunks[0].reset();
+    }
+}
+
+unsafe impl Allocator for LinkedArenaAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        // SAFETY: the allocator is `!Sync` and `ArenaAllocator::allocate` never calls back into
+        // it, so this is the only borrow of `chunks` until the function returns. Pushing may move
+        // the `ArenaAllocator`s but not their heap buffers, so earlier blocks stay valid.
+        let chunks = unsafe { &mut *self.chunks.get() };
+        if let Some(allocated_ptr) = chunks.last().and_then(|chunk| chunk.allocate(layout).ok()) {
+            return Ok(allocated_ptr);
+        }
+
+        // Requests larger than a chunk get a dedicated chunk big enough for them, padding included.
+        let padded_size = layout
+            .size()
+            .checked_add(layout.align() - 1)
+            .ok_or(AllocError)?;
+        chunks.push(ArenaAllocator::new(self.chunk_capacity.max(padded_size)));
+        chunks.last().ok_or(AllocError)?.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `LinkedArenaAllocator` with a generic vector that outgrows a single chunk.
+    fn test_generic_vector_with_linked_arena() {
+        let allocator = LinkedArenaAllocator::new(64);
+        let mut vector: Vec<usize, &LinkedArenaAllocator> = Vec::new_in(&allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    /// Tests that overflowing a chunk adds a new one and that a reset drops back to one chunk.
+    fn test_overflow_and_reset() {
+        let mut allocator = LinkedArenaAllocator::new(64);
+        let layout = Layout::from_size_align(48, 1).unwrap();
+
+        let first = allocator.allocate(layout).unwrap();
+        let second = allocator.allocate(layout).unwrap();
+        assert_ne!(first.as_mut_ptr(), second.as_mut_ptr());
+        assert_eq!(allocator.chunk_count(), 2);
+        assert_eq!(allocator.total_capacity(), 128);
+
+        allocator.reset();
+        assert_eq!(allocator.chunk_count(), 1);
+        assert_eq!(allocator.total_capacity(), 64);
+    }
+
+    #[test]
+    /// Tests that a request larger than a chunk is served from a dedicated chunk.
+    fn test_oversized_allocation() {
+        let allocator = LinkedArenaAllocator::new(64);
+        let layout = Layout::from_size_align(256, 16).unwrap();
+
+        let allocated_ptr = allocator.allocate(layout).unwrap();
+        assert_eq!(allocated_ptr.len(), 256);
+        assert_eq!(allocated_ptr.as_mut_ptr() as usize % 16, 0);
+        assert_eq!(allocator.chunk_count(), 2);
+    }
+}
@@ -0,0 +1,145 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    mem::{self, MaybeUninit},
+    slice,
+};
+
+use alloc::vec::Vec;
+
+use crate::arena_allocator::{ArenaAllocator, ArenaAllocatorView};
+
+/// Fixed byte quotas for named subsystems, carved out of one borrowed [`ArenaAllocator`].
+///
+/// Construction claims one region of the arena per subsystem and wraps it in an
+/// [`ArenaAllocatorView`], so a subsystem can never use more than its quota and starve the
+/// others. Like [`Region`](crate::region::Region), the regions are only reclaimed by resetting
+/// the parent arena once the budget is gone.
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use mem_allocs::{arena_allocator::ArenaAllocator, memory_budget::MemoryBudget};
+///
+/// let arena = ArenaAllocator::new(4096);
+/// let budget = MemoryBudget::new(&arena, &[("audio", 1024), ("physics", 2048)]).unwrap();
+///
+/// let audio = budget.subsystem("audio").unwrap();
+/// let samples: Vec<f32, _> = Vec::with_capacity_in(256, audio);
+/// assert_eq!(audio.remaining(), 0);
+/// drop(samples);
+/// ```
+#[derive(Debug)]
+pub struct MemoryBudget<'a> {
+    subsystems: Vec<(&'static str, ArenaAllocatorView<'a>)>,
+}
+
+impl<'a> MemoryBudget<'a> {
+    /// Claims a region of `bytes` bytes from `arena` for every `(name, bytes)` pair in `quotas`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError` if the arena cannot hold every quota. The arena is rolled back to
+    /// where it was, so no space is lost to the quotas claimed before the failure.
+    pub fn new(
+        arena: &'a ArenaAllocator,
+        quotas: &[(&'static str, usize)],
+    ) -> Result<Self, AllocError> {
+        let marker = arena.save();
+        let mut subsystems = Vec::with_capacity(quotas.len());
+
+        for &(name, bytes) in quotas {
+            let Ok(view) = Self::claim(arena, bytes) else {
+                drop(subsystems);
+                // SAFETY: everything allocated since the marker belongs to the views just dropped.
+                unsafe { arena.restore(marker) };
+                return Err(AllocError);
+            };
+            subsystems.push((name, view));
+        }
+
+        Ok(Self { subsystems })
+    }
+
+    /// Allocates `bytes` bytes from `arena` and builds a view over them.
+    fn claim(
+        arena: &'a ArenaAllocator,
+        bytes: usize,
+    ) -> Result<ArenaAllocatorView<'a>, AllocError> {
+        let layout =
+            Layout::from_size_align(bytes, mem::align_of::<usize>()).map_err(|_| AllocError)?;
+        let block = arena.allocate(layout)?;
+        // SAFETY: the arena handed the block out exclusively and the budget never deallocates it.
+        // The `'a` borrow keeps the arena alive and, since resetting it takes `&mut self`, also
+        // keeps it from being reset, so the block stays valid and unaliased for `'a`.
+        let buffer = unsafe {
+            slice::from_raw_parts_mut(block.as_mut_ptr().cast::<MaybeUninit<u8>>(), bytes)
+        };
+        Ok(ArenaAllocatorView::new(buffer))
+    }
+
+    /// Returns the sub-arena of the subsystem called `name`, or `None` if there is no such
+    /// subsystem.
+    ///
+    /// The view is borrowed rather than returned by value, so its offset persists across calls.
+    #[must_use]
+    pub fn subsystem(&self, name: &str) -> Option<&ArenaAllocatorView<'a>> {
+        self.subsystems
+            .iter()
+            .find(|(subsystem, _)| *subsystem == name)
+            .map(|(_, view)| view)
+    }
+
+    /// Iterates over the names and sub-arenas of all subsystems, in the order they were defined.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &ArenaAllocatorView<'a>)> + '_ {
+        self.subsystems.iter().map(|(name, view)| (*name, view))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Tests the `MemoryBudget` with a generic vector in one subsystem.
+    fn test_generic_vector_with_memory_budget() {
+        let arena = ArenaAllocator::new(2048);
+        let budget = MemoryBudget::new(&arena, &[("render", 1024)]).unwrap();
+        let render = budget.subsystem("render").unwrap();
+        let mut vector: Vec<usize, &ArenaAllocatorView> = Vec::with_capacity_in(100, render);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    /// Tests that every subsystem is limited to its own quota.
+    fn test_quotas_are_independent() {
+        let arena = ArenaAllocator::with_alignment(256, 8);
+        let budget = MemoryBudget::new(&arena, &[("audio", 64), ("physics", 128)]).unwrap();
+        assert_eq!(arena.used(), 192);
+
+        let audio = budget.subsystem("audio").unwrap();
+        let physics = budget.subsystem("physics").unwrap();
+        assert!(audio.allocate(Layout::new::<[u8; 64]>()).is_ok());
+        assert!(audio.allocate(Layout::new::<u8>()).is_err());
+        assert_eq!(physics.remaining(), 128);
+        assert!(budget.subsystem("input").is_none());
+    }
+
+    #[test]
+    /// Tests that quotas exceeding the arena fail and leave the arena untouched.
+    fn test_over_budget() {
+        let arena = ArenaAllocator::with_alignment(256, 8);
+        arena.allocate_one::<u64>().unwrap();
+
+        let result = MemoryBudget::new(&arena, &[("audio", 128), ("physics", 128)]);
+        assert!(result.is_err());
+        assert_eq!(arena.used(), 8);
+    }
+}
@@ -0,0 +1,259 @@
+use core::{
+    alloc::{AllocError, Allocator, GlobalAlloc, Layout},
+    ffi::{c_char, c_void, CStr},
+    ptr::{self, NonNull},
+};
+
+use libmimalloc_sys::{
+    mi_free, mi_malloc_aligned, mi_realloc_aligned, mi_stats_print_out, mi_zalloc_aligned,
+};
+
+/// A memory allocator backed by [mimalloc](https://github.com/microsoft/mimalloc).
+#[derive(Debug, Default, Clone, Copy)]
+#[allow(clippy::module_name_repetitions)]
+pub struct MiMallocAllocator;
+
+/// Memory statistics reported by mimalloc, in bytes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MiHeapStats {
+    /// The most memory committed at any point so far.
+    pub peak_used: usize,
+    /// The memory committed right now.
+    pub current_used: usize,
+}
+
+impl MiMallocAllocator {
+    /// Returns mimalloc's process-wide commit statistics.
+    ///
+    /// The numbers are parsed from the report printed by `mi_stats_print_out`, which rounds them
+    /// to three significant digits, so they are approximate.
+    #[must_use]
+    pub fn heap_stats() -> MiHeapStats {
+        let mut parser = StatsParser::default();
+        unsafe {
+            mi_stats_print_out(
+                Some(StatsParser::output),
+                ptr::from_mut(&mut parser).cast::<c_void>(),
+            );
+        }
+        parser.finish();
+        parser.stats
+    }
+}
+
+/// Collects the output of `mi_stats_print_out` line by line and picks out the commit counters.
+struct StatsParser {
+    line: [u8; 256],
+    len: usize,
+    stats: MiHeapStats,
+}
+
+impl Default for StatsParser {
+    fn default() -> Self {
+        Self {
+            line: [0; 256],
+            len: 0,
+            stats: MiHeapStats::default(),
+        }
+    }
+}
+
+impl StatsParser {
+    /// The output callback handed to mimalloc; `arg` points to the `StatsParser`.
+    unsafe extern "C" fn output(message: *const c_char, arg: *mut c_void) {
+        let parser = &mut *arg.cast::<Self>();
+        for &byte in CStr::from_ptr(message).to_bytes() {
+            parser.push(byte);
+        }
+    }
+
+    fn push(&mut self, byte: u8) {
+        if byte == b'\n' {
+            self.finish();
+        } else if self.len < self.line.len() {
+            self.line[self.len] = byte;
+            self.len += 1;
+        }
+    }
+
+    /// Parses the buffered line and starts a new one.
+    fn finish(&mut self) {
+        let line = core::str::from_utf8(&self.line[..self.len]).unwrap_or_default();
+        if let Some(stats) = parse_committed(line) {
+            self.stats = stats;
+        }
+        self.len = 0;
+    }
+}
+
+/// Parses the `committed` row of a mimalloc statistics report, whose columns are the peak, total
+/// and current amounts, each optionally followed by a unit such as `KiB` or `MiB`.
+fn parse_committed(line: &str) -> Option<MiHeapStats> {
+    let (label, values) = line.split_once(':')?;
+    if label.trim() != "committed" {
+        return None;
+    }
+
+    let mut tokens = values.split_whitespace().peekable();
+    let mut amounts = [0; 3];
+    for amount in &mut amounts {
+        let number: f64 = tokens.next()?.parse().ok()?;
+        let scale = match tokens.peek().copied() {
+            Some("B") => 1.0,
+            Some("KiB") => 1024.0,
+            Some("MiB") => 1024.0 * 1024.0,
+            Some("GiB") => 1024.0 * 1024.0 * 1024.0,
+            Some("TiB") => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+            _ => {
+                *amount = bytes(number);
+                continue;
+            }
+        };
+        tokens.next();
+        *amount = bytes(number * scale);
+    }
+
+    let [peak_used, _, current_used] = amounts;
+    Some(MiHeapStats {
+        peak_used,
+        current_used,
+    })
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // Reported sizes are positive.
+const fn bytes(amount: f64) -> usize {
+    amount as usize
+}
+
+unsafe impl Allocator for MiMallocAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let allocated_ptr = unsafe { mi_malloc_aligned(layout.size(), layout.align()) };
+        NonNull::new(ptr::slice_from_raw_parts_mut(
+            allocated_ptr.cast::<u8>(),
+            layout.size(),
+        ))
+        .ok_or(AllocError)
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let allocated_ptr = unsafe { mi_zalloc_aligned(layout.size(), layout.align()) };
+        NonNull::new(ptr::slice_from_raw_parts_mut(
+            allocated_ptr.cast::<u8>(),
+            layout.size(),
+        ))
+        .ok_or(AllocError)
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, _: Layout) {
+        mi_free(allocated_ptr.as_ptr().cast::<c_void>());
+    }
+
+    unsafe fn grow(
+        &self,
+        old_ptr: NonNull<u8>,
+        _: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let new_ptr = mi_realloc_aligned(
+            old_ptr.as_ptr().cast::<c_void>(),
+            new_layout.size(),
+            new_layout.align(),
+        );
+        NonNull::new(ptr::slice_from_raw_parts_mut(
+            new_ptr.cast::<u8>(),
+            new_layout.size(),
+        ))
+        .ok_or(AllocError)
+    }
+}
+
+unsafe impl GlobalAlloc for MiMallocAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        mi_malloc_aligned(layout.size(), layout.align()).cast::<u8>()
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        mi_zalloc_aligned(layout.size(), layout.align()).cast::<u8>()
+    }
+
+    unsafe fn dealloc(&self, allocated_ptr: *mut u8, _: Layout) {
+        mi_free(allocated_ptr.cast::<c_void>());
+    }
+
+    unsafe fn realloc(&self, old_ptr: *mut u8, old_layout: Layout, new_size: usize) -> *mut u8 {
+        mi_realloc_aligned(old_ptr.cast::<c_void>(), new_size, old_layout.align()).cast::<u8>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `MiMallocAllocator` with a generic vector.
+    fn test_generic_vector_with_mimalloc_allocator() {
+        let mut vector: Vec<usize, MiMallocAllocator> =
+            Vec::with_capacity_in(100, MiMallocAllocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    /// Tests that growing keeps the contents and honours a larger alignment.
+    fn test_grow_aligned() {
+        let old_layout = Layout::from_size_align(16, 8).unwrap();
+        let new_layout = Layout::from_size_align(4096, 256).unwrap();
+
+        let block = MiMallocAllocator.allocate(old_layout).unwrap();
+        unsafe { ptr::write_bytes(block.as_mut_ptr(), 0x3C, 16) };
+        let grown = unsafe {
+            MiMallocAllocator
+                .grow(block.as_non_null_ptr(), old_layout, new_layout)
+                .unwrap()
+        };
+
+        assert_eq!(grown.as_mut_ptr() as usize % 256, 0);
+        let bytes = unsafe { core::slice::from_raw_parts(grown.as_mut_ptr(), 16) };
+        assert!(bytes.iter().all(|&byte| byte == 0x3C));
+        unsafe { MiMallocAllocator.deallocate(grown.as_non_null_ptr(), new_layout) };
+    }
+
+    #[test]
+    /// Tests parsing the `committed` row of a statistics report.
+    fn test_parse_committed() {
+        let line = "  committed :     3.5 MiB     4.0 MiB   512 KiB                          ";
+        let stats = parse_committed(line).unwrap();
+        assert_eq!(stats.peak_used, 3 * 1024 * 1024 + 512 * 1024);
+        assert_eq!(stats.current_used, 512 * 1024);
+
+        assert_eq!(
+            parse_committed("  reserved  :     1.0 GiB     1.0 GiB     1.0 GiB"),
+            None
+        );
+        assert_eq!(
+            parse_committed("  committed :     0           0           0"),
+            Some(MiHeapStats::default())
+        );
+    }
+
+    #[test]
+    /// Tests that `heap_stats` reflects a large live allocation.
+    fn test_heap_stats() {
+        let layout = Layout::from_size_align(8 << 20, 8).unwrap();
+        let block = MiMallocAllocator.allocate_zeroed(layout).unwrap();
+
+        let stats = MiMallocAllocator::heap_stats();
+        assert!(stats.current_used >= 8 << 20);
+        assert!(stats.peak_used >= stats.current_used);
+
+        unsafe { MiMallocAllocator.deallocate(block.as_non_null_ptr(), layout) };
+    }
+}
@@ -0,0 +1,250 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    cell::RefCell,
+    ptr::NonNull,
+};
+
+use alloc::vec::Vec;
+
+/// A wrapper allocator that performs every operation on two allocators and panics as soon as
+/// their results disagree.
+///
+/// Every request goes to both the primary allocator `A` and the mirror `B`. Both must succeed or
+/// both must fail, and successful blocks must have the same length; only the primary's blocks are
+/// handed out. This makes it easy to regression-test a new allocator against a known-good one.
+/// The mirror's block for every live allocation is remembered in a list on the global heap, so
+/// the wrapper is meant for tests rather than production code.
+///
+/// # Panics
+///
+/// Every allocator method panics if the two allocators disagree, and deallocation panics if the
+/// pointer was never handed out by this wrapper.
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct MirroredAllocator<A: Allocator, B: Allocator> {
+    primary: A,
+    mirror: B,
+    /// The primary's and the mirror's block of every live allocation.
+    blocks: RefCell<Vec<(NonNull<u8>, NonNull<u8>)>>,
+}
+
+impl<A: Allocator, B: Allocator> MirroredAllocator<A, B> {
+    /// Mirrors every operation on `primary` onto `mirror`.
+    pub const fn new(primary: A, mirror: B) -> Self {
+        Self {
+            primary,
+            mirror,
+            blocks: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Returns a reference to the allocator whose blocks are handed out.
+    pub const fn primary(&self) -> &A {
+        &self.primary
+    }
+
+    /// Returns a reference to the allocator the primary is checked against.
+    pub const fn mirror(&self) -> &B {
+        &self.mirror
+    }
+
+    /// Returns the number of blocks currently allocated through the wrapper.
+    #[must_use]
+    pub fn live_allocations(&self) -> usize {
+        self.blocks.borrow().len()
+    }
+
+    /// Checks that both results agree, remembers the pair of blocks on success and returns the
+    /// primary's block.
+    fn record(
+        &self,
+        operation: &str,
+        primary: Result<NonNull<[u8]>, AllocError>,
+        mirror: Result<NonNull<[u8]>, AllocError>,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        match (primary, mirror) {
+            (Ok(primary), Ok(mirror)) => {
+                assert_eq!(
+                    primary.len(),
+                    mirror.len(),
+                    "{operation}: primary returned {} bytes but mirror returned {}",
+                    primary.len(),
+                    mirror.len()
+                );
+                self.blocks
+                    .borrow_mut()
+                    .push((primary.as_non_null_ptr(), mirror.as_non_null_ptr()));
+                Ok(primary)
+            }
+            (Err(AllocError), Err(AllocError)) => Err(AllocError),
+            (Ok(_), Err(AllocError)) => {
+                panic!("{operation}: primary succeeded but mirror failed")
+            }
+            (Err(AllocError), Ok(_)) => {
+                panic!("{operation}: mirror succeeded but primary failed")
+            }
+        }
+    }
+
+    /// Looks up and forgets the mirror's block for the primary's block at `ptr`.
+    fn take_mirror(&self, operation: &str, ptr: NonNull<u8>) -> NonNull<u8> {
+        let mut blocks = self.blocks.borrow_mut();
+        let index = blocks
+            .iter()
+            .position(|&(primary, _)| primary == ptr)
+            .unwrap_or_else(|| {
+                panic!("{operation}: pointer {ptr:p} was not allocated by this allocator")
+            });
+        blocks.swap_remove(index).1
+    }
+
+    /// Remembers `mirror` as the mirror's block for `primary` again after a failed resize.
+    fn restore_mirror(&self, primary: NonNull<u8>, mirror: NonNull<u8>) {
+        self.blocks.borrow_mut().push((primary, mirror));
+    }
+}
+
+unsafe impl<A: Allocator, B: Allocator> Allocator for MirroredAllocator<A, B> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.record(
+            "allocate",
+            self.primary.allocate(layout),
+            self.mirror.allocate(layout),
+        )
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.record(
+            "allocate_zeroed",
+            self.primary.allocate_zeroed(layout),
+            self.mirror.allocate_zeroed(layout),
+        )
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        let mirror_ptr = self.take_mirror("deallocate", allocated_ptr);
+        self.primary.deallocate(allocated_ptr, layout);
+        self.mirror.deallocate(mirror_ptr, layout);
+    }
+
+    unsafe fn grow(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let operation = "grow";
+        let mirror_ptr = self.take_mirror(operation, old_ptr);
+        let primary = self.primary.grow(old_ptr, old_layout, new_layout);
+        let mirror = self.mirror.grow(mirror_ptr, old_layout, new_layout);
+        let result = self.record(operation, primary, mirror);
+        if result.is_err() {
+            self.restore_mirror(old_ptr, mirror_ptr);
+        }
+        result
+    }
+
+    unsafe fn grow_zeroed(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let operation = "grow_zeroed";
+        let mirror_ptr = self.take_mirror(operation, old_ptr);
+        let primary = self.primary.grow_zeroed(old_ptr, old_layout, new_layout);
+        let mirror = self.mirror.grow_zeroed(mirror_ptr, old_layout, new_layout);
+        let result = self.record(operation, primary, mirror);
+        if result.is_err() {
+            self.restore_mirror(old_ptr, mirror_ptr);
+        }
+        result
+    }
+
+    unsafe fn shrink(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let operation = "shrink";
+        let mirror_ptr = self.take_mirror(operation, old_ptr);
+        let primary = self.primary.shrink(old_ptr, old_layout, new_layout);
+        let mirror = self.mirror.shrink(mirror_ptr, old_layout, new_layout);
+        let result = self.record(operation, primary, mirror);
+        if result.is_err() {
+            self.restore_mirror(old_ptr, mirror_ptr);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::alloc::Global;
+
+    #[test]
+    /// Tests the `MirroredAllocator` with a generic vector that grows several times.
+    fn test_generic_vector_with_mirrored_allocator() {
+        let allocator = MirroredAllocator::new(Global, Global);
+        let mut vector: Vec<usize, &MirroredAllocator<Global, Global>> = Vec::new_in(&allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        assert_eq!(allocator.live_allocations(), 1);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+        assert_eq!(allocator.live_allocations(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "c_allocator")]
+    /// Tests that the two C allocators agree on a mix of operations.
+    fn test_c_allocators_agree() {
+        use crate::c_allocator::{CAllocator, RawCAllocator};
+
+        let allocator = MirroredAllocator::new(CAllocator, RawCAllocator);
+        let small = Layout::from_size_align(24, 8).unwrap();
+        let large = Layout::from_size_align(512, 64).unwrap();
+
+        unsafe {
+            let block = allocator.allocate(small).unwrap().as_non_null_ptr();
+            let block = allocator
+                .grow(block, small, large)
+                .unwrap()
+                .as_non_null_ptr();
+            let block = allocator
+                .shrink(block, large, small)
+                .unwrap()
+                .as_non_null_ptr();
+            allocator.deallocate(block, small);
+        }
+        assert_eq!(allocator.live_allocations(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "noop_allocator")]
+    #[should_panic(expected = "allocate: primary succeeded but mirror failed")]
+    /// Tests that an allocation failing on only one side panics.
+    fn test_disagreement_panics() {
+        use crate::noop_allocator::NoopAllocator;
+
+        let allocator = MirroredAllocator::new(Global, NoopAllocator);
+        let _ = allocator.allocate(Layout::new::<u64>());
+    }
+
+    #[test]
+    #[should_panic(expected = "was not allocated by this allocator")]
+    /// Tests that deallocating a foreign pointer panics.
+    fn test_foreign_pointer_panics() {
+        let allocator = MirroredAllocator::new(Global, Global);
+        let layout = Layout::new::<u64>();
+        let foreign = Global.allocate(layout).unwrap().as_non_null_ptr();
+        unsafe { allocator.deallocate(foreign, layout) };
+    }
+}
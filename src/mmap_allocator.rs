@@ -0,0 +1,180 @@
+use core::{
+    alloc::{AllocError, Allocator, GlobalAlloc, Layout},
+    ptr::{self, NonNull},
+};
+
+use libc::{
+    mmap, munmap, sysconf, _SC_PAGESIZE, MAP_ANONYMOUS, MAP_FAILED, MAP_PRIVATE, PROT_READ,
+    PROT_WRITE,
+};
+
+use crate::align::align_up;
+
+/// A memory allocator that backs every allocation with its own anonymous memory mapping.
+///
+/// Sizes are rounded up to whole pages, which makes this allocator a good fit for large buffers
+/// of several megabytes and a poor fit for small objects. Mappings are always page-aligned, so any
+/// alignment up to the page size comes for free; larger alignments are served by over-mapping and
+/// unmapping the unaligned ends.
+#[derive(Debug, Default, Clone, Copy)]
+#[allow(clippy::module_name_repetitions)]
+pub struct MmapAllocator;
+
+impl MmapAllocator {
+    /// Returns the size of a memory page in bytes.
+    #[must_use]
+    pub fn page_size() -> usize {
+        usize::try_from(unsafe { sysconf(_SC_PAGESIZE) }).unwrap_or(4096)
+    }
+}
+
+/// Maps fresh anonymous memory for `layout`.
+///
+/// Zero-sized layouts get a dangling, well-aligned pointer without mapping anything.
+///
+/// # Errors
+///
+/// Returns an `AllocError` if the size overflows or the mapping fails.
+pub(crate) fn map_memory(layout: Layout) -> Result<*mut u8, AllocError> {
+    if layout.size() == 0 {
+        return Ok(ptr::without_provenance_mut(layout.align()));
+    }
+
+    let page_size = MmapAllocator::page_size();
+    let length = align_up(layout.size(), page_size).ok_or(AllocError)?;
+    let extra = layout.align().saturating_sub(page_size);
+    let mapped_length = length.checked_add(extra).ok_or(AllocError)?;
+
+    let mapped_ptr = unsafe {
+        mmap(
+            ptr::null_mut(),
+            mapped_length,
+            PROT_READ | PROT_WRITE,
+            MAP_PRIVATE | MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    if mapped_ptr == MAP_FAILED {
+        return Err(AllocError);
+    }
+
+    let mapped_ptr = mapped_ptr.cast::<u8>();
+    if extra == 0 {
+        return Ok(mapped_ptr);
+    }
+
+    // Trim the over-mapped region down to an aligned window of `length` bytes.
+    let head =
+        align_up(mapped_ptr as usize, layout.align()).ok_or(AllocError)? - mapped_ptr as usize;
+    let tail = extra - head;
+    unsafe {
+        if head > 0 {
+            munmap(mapped_ptr.cast(), head);
+        }
+        if tail > 0 {
+            munmap(mapped_ptr.add(head + length).cast(), tail);
+        }
+        Ok(mapped_ptr.add(head))
+    }
+}
+
+/// Unmaps memory obtained from [`map_memory`] with the same `layout`.
+pub(crate) unsafe fn unmap_memory(allocated_ptr: *mut u8, layout: Layout) {
+    if layout.size() == 0 {
+        return;
+    }
+    let length =
+        align_up(layout.size(), MmapAllocator::page_size()).unwrap_or_else(|| layout.size());
+    munmap(allocated_ptr.cast(), length);
+}
+
+unsafe impl Allocator for MmapAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let allocated_ptr = map_memory(layout)?;
+        NonNull::new(ptr::slice_from_raw_parts_mut(allocated_ptr, layout.size())).ok_or(AllocError)
+    }
+
+    /// Anonymous mappings are always zero-filled by the OS, so no extra zeroing is needed.
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        unmap_memory(allocated_ptr.as_ptr(), layout);
+    }
+}
+
+unsafe impl GlobalAlloc for MmapAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        map_memory(layout).unwrap_or(ptr::null_mut())
+    }
+
+    /// Anonymous mappings are always zero-filled by the OS, so no extra zeroing is needed.
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        self.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, allocated_ptr: *mut u8, layout: Layout) {
+        unmap_memory(allocated_ptr, layout);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    /// Allocates `size` bytes, checks they are zeroed and writable, then frees them.
+    fn check_allocation(size: usize, align: usize) {
+        let layout = Layout::from_size_align(size, align).unwrap();
+        let allocated_ptr = MmapAllocator.allocate(layout).unwrap();
+        assert_eq!(allocated_ptr.len(), size);
+        assert_eq!(allocated_ptr.as_mut_ptr() as usize % align, 0);
+
+        let bytes = unsafe { core::slice::from_raw_parts_mut(allocated_ptr.as_mut_ptr(), size) };
+        assert!(bytes.iter().all(|&byte| byte == 0));
+        bytes.fill(0xCD);
+
+        unsafe { MmapAllocator.deallocate(allocated_ptr.as_non_null_ptr(), layout) };
+    }
+
+    #[test]
+    /// Tests the `MmapAllocator` with a generic vector.
+    fn test_generic_vector_with_mmap_allocator() {
+        let mut vector: Vec<usize, MmapAllocator> = Vec::with_capacity_in(100, MmapAllocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    /// Tests an allocation smaller than a page.
+    fn test_less_than_a_page() {
+        check_allocation(100, 8);
+    }
+
+    #[test]
+    /// Tests an allocation of exactly one page.
+    fn test_exactly_a_page() {
+        check_allocation(MmapAllocator::page_size(), 8);
+    }
+
+    #[test]
+    /// Tests an allocation spanning multiple pages.
+    fn test_multiple_pages() {
+        check_allocation(5 * MmapAllocator::page_size() + 17, 8);
+    }
+
+    #[test]
+    /// Tests an alignment larger than the page size.
+    fn test_alignment_above_page_size() {
+        check_allocation(100, 16 * MmapAllocator::page_size());
+    }
+}
@@ -0,0 +1,259 @@
+use core::{
+    alloc::{AllocError, Allocator, GlobalAlloc, Layout},
+    mem::MaybeUninit,
+    ptr::{self, NonNull},
+    slice,
+};
+
+use std::{
+    alloc::System,
+    sync::{Mutex, MutexGuard},
+};
+
+use crate::arena_allocator::{ArenaAllocator, ArenaAllocatorView};
+
+/// The buffer of a [`MutexArenaAllocator`], created on first use.
+#[derive(Debug)]
+struct Region {
+    buffer: NonNull<u8>,
+    arena: ArenaAllocatorView<'static>,
+}
+
+// SAFETY: the buffer is a private heap allocation that nothing else points into, so the region
+// may move to another thread. Sharing goes through the mutex, which makes the arena `Sync`.
+#[allow(clippy::non_send_fields_in_send_ty)] // The view only points into `buffer`.
+unsafe impl Send for Region {}
+
+/// A bump-pointer arena behind a [`std::sync::Mutex`], usable as the global allocator of a
+/// multi-threaded program.
+///
+/// Every operation takes the lock, so threads are served one at a time and never receive
+/// overlapping memory. [`ArenaAllocator::new`] would allocate its buffer from the global
+/// allocator, which cannot work when the arena *is* the global allocator, so the buffer is taken
+/// from [`System`] the first time memory is requested. That also lets [`MutexArenaAllocator::new`]
+/// be a `const fn`:
+///
+/// ```no_run
+/// use mem_allocs::mutex_arena::MutexArenaAllocator;
+///
+/// #[global_allocator]
+/// static ARENA: MutexArenaAllocator = MutexArenaAllocator::new(64 * 1024 * 1024);
+///
+/// let handles: Vec<_> = (0..4)
+///     .map(|index| std::thread::spawn(move || vec![index; 1000]))
+///     .collect();
+/// for handle in handles {
+///     handle.join().unwrap();
+/// }
+/// ```
+///
+/// Deallocation takes the lock and does nothing; memory is reclaimed with
+/// [`MutexArenaAllocator::reset`]. A panic while the lock is held cannot leave the arena in an
+/// inconsistent state, so a poisoned lock is simply taken over.
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct MutexArenaAllocator {
+    capacity: usize,
+    region: Mutex<Option<Region>>,
+}
+
+impl MutexArenaAllocator {
+    /// Creates a new arena with room for `bytes` bytes.
+    ///
+    /// No memory is reserved until the first allocation.
+    #[must_use]
+    pub const fn new(bytes: usize) -> Self {
+        Self {
+            capacity: bytes,
+            region: Mutex::new(None),
+        }
+    }
+
+    /// Returns the total number of bytes available for allocation.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of bytes consumed so far, including alignment padding.
+    #[must_use]
+    pub fn used(&self) -> usize {
+        self.lock().as_ref().map_or(0, |region| region.arena.used())
+    }
+
+    /// Returns the number of bytes still available for allocation.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.capacity - self.used()
+    }
+
+    /// Takes the lock and resets the arena, making its whole buffer available again.
+    ///
+    /// # Safety
+    ///
+    /// The arena is shared between threads and often serves as the global allocator, so the
+    /// borrow checker cannot tell whether its blocks are still in use. The caller must ensure no
+    /// block handed out before the reset, on any thread, is used afterwards; that includes the
+    /// buffers of every live collection allocating from the arena.
+    pub unsafe fn reset(&self) {
+        if let Some(region) = self.lock().as_mut() {
+            region.arena.reset();
+        }
+    }
+
+    /// Takes the lock, recovering it if another thread panicked while holding it.
+    fn lock(&self) -> MutexGuard<'_, Option<Region>> {
+        self.region
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Runs `f` on the arena with the lock held, creating the buffer first if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError` if the buffer cannot be allocated.
+    fn with_arena<R>(
+        &self,
+        f: impl FnOnce(&ArenaAllocatorView<'static>) -> Result<R, AllocError>,
+    ) -> Result<R, AllocError> {
+        let mut region = self.lock();
+        if region.is_none() {
+            *region = Some(self.create_region()?);
+        }
+        region
+            .as_ref()
+            .map_or(Err(AllocError), |region| f(&region.arena))
+    }
+
+    /// Allocates the buffer from the system allocator.
+    fn create_region(&self) -> Result<Region, AllocError> {
+        let buffer = if self.capacity == 0 {
+            NonNull::dangling()
+        } else {
+            let layout = Layout::array::<u8>(self.capacity).map_err(|_| AllocError)?;
+            NonNull::new(unsafe { System.alloc(layout) }).ok_or(AllocError)?
+        };
+        // SAFETY: the buffer is freshly allocated, lives until `drop`, and is only reached
+        // through the arena.
+        let slice = unsafe {
+            slice::from_raw_parts_mut(buffer.as_ptr().cast::<MaybeUninit<u8>>(), self.capacity)
+        };
+        Ok(Region {
+            buffer,
+            arena: ArenaAllocator::from_slice(slice),
+        })
+    }
+}
+
+unsafe impl Allocator for MutexArenaAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.with_arena(|arena| arena.allocate(layout))
+    }
+
+    unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {
+        drop(self.lock());
+    }
+}
+
+unsafe impl GlobalAlloc for MutexArenaAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.allocate(layout)
+            .map_or(ptr::null_mut(), NonNull::as_mut_ptr)
+    }
+
+    /// The buffer may still hold bytes from before a reset, so the memory is always cleared.
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let allocated_ptr = self.alloc(layout);
+        if !allocated_ptr.is_null() {
+            ptr::write_bytes(allocated_ptr, 0, layout.size());
+        }
+        allocated_ptr
+    }
+
+    unsafe fn dealloc(&self, allocated_ptr: *mut u8, layout: Layout) {
+        if let Some(allocated_ptr) = NonNull::new(allocated_ptr) {
+            self.deallocate(allocated_ptr, layout);
+        }
+    }
+}
+
+impl Drop for MutexArenaAllocator {
+    fn drop(&mut self) {
+        let region = self
+            .region
+            .get_mut()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(region) = region.take() {
+            if self.capacity != 0 {
+                let layout = Layout::array::<u8>(self.capacity).unwrap();
+                unsafe { System.dealloc(region.buffer.as_ptr(), layout) };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `MutexArenaAllocator` with a generic vector.
+    fn test_generic_vector_with_mutex_arena() {
+        let allocator = MutexArenaAllocator::new(4096);
+        let mut vector: Vec<usize, &MutexArenaAllocator> = Vec::with_capacity_in(100, &allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    /// Tests that the buffer is only created on first use and that a reset frees it up again.
+    fn test_lazy_buffer_and_reset() {
+        let allocator = MutexArenaAllocator::new(64);
+        assert!(allocator.lock().is_none());
+        assert_eq!(allocator.used(), 0);
+
+        allocator.allocate(Layout::new::<[u8; 48]>()).unwrap();
+        assert_eq!(allocator.used(), 48);
+        assert!(allocator.allocate(Layout::new::<[u8; 48]>()).is_err());
+
+        // SAFETY: the only block was never used.
+        unsafe { allocator.reset() };
+        assert_eq!(allocator.remaining(), 64);
+    }
+
+    #[test]
+    /// Tests that threads sharing the arena never receive overlapping memory.
+    fn test_threads_do_not_overlap() {
+        let allocator = MutexArenaAllocator::new(64 * 1024);
+        let layout = Layout::new::<[u8; 64]>();
+
+        let mut blocks: Vec<usize> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        (0..64)
+                            .map(|_| allocator.allocate(layout).unwrap().as_mut_ptr() as usize)
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        });
+
+        blocks.sort_unstable();
+        assert!(blocks.windows(2).all(|pair| pair[1] - pair[0] >= 64));
+        assert_eq!(allocator.used(), 4 * 64 * 64);
+    }
+}
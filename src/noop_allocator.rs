@@ -0,0 +1,103 @@
+use core::{
+    alloc::{AllocError, Allocator, GlobalAlloc, Layout},
+    ptr::{self, NonNull},
+};
+
+/// An allocator that refuses every request.
+///
+/// Use it to force the fallback path of an allocator chain such as
+/// [`FallbackAllocator`](crate::fallback_allocator::FallbackAllocator), or to check that a data
+/// structure handles running out of memory.
+#[derive(Copy, Clone, Default, Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct NoopAllocator;
+
+impl NoopAllocator {
+    /// Always returns `false`: the allocator never hands out memory.
+    #[must_use]
+    pub const fn contains(&self, _: NonNull<u8>) -> bool {
+        false
+    }
+}
+
+unsafe impl Allocator for NoopAllocator {
+    fn allocate(&self, _: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        Err(AllocError)
+    }
+
+    unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {}
+
+    unsafe fn grow(
+        &self,
+        _: NonNull<u8>,
+        _: Layout,
+        _: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        Err(AllocError)
+    }
+
+    unsafe fn shrink(
+        &self,
+        _: NonNull<u8>,
+        _: Layout,
+        _: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        Err(AllocError)
+    }
+}
+
+unsafe impl GlobalAlloc for NoopAllocator {
+    unsafe fn alloc(&self, _: Layout) -> *mut u8 {
+        ptr::null_mut()
+    }
+
+    unsafe fn dealloc(&self, _: *mut u8, _: Layout) {}
+
+    unsafe fn realloc(&self, _: *mut u8, _: Layout, _: usize) -> *mut u8 {
+        ptr::null_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests that every request fails, through both allocator interfaces.
+    fn test_always_fails() {
+        let layout = Layout::from_size_align(8, 8).unwrap();
+        assert!(NoopAllocator.allocate(layout).is_err());
+        assert!(NoopAllocator.allocate_zeroed(layout).is_err());
+        unsafe {
+            assert!(GlobalAlloc::alloc(&NoopAllocator, layout).is_null());
+            assert!(GlobalAlloc::alloc_zeroed(&NoopAllocator, layout).is_null());
+        }
+    }
+
+    #[test]
+    /// Tests that a vector reports running out of memory instead of growing.
+    fn test_generic_vector_with_noop_allocator() {
+        let mut vector: Vec<usize, NoopAllocator> = Vec::new_in(NoopAllocator);
+        assert!(vector.try_reserve(1).is_err());
+        assert!(vector.is_empty());
+    }
+
+    #[test]
+    #[cfg(all(feature = "fallback_allocator", feature = "c_allocator"))]
+    /// Tests that a `FallbackAllocator` with a failing primary serves everything from the
+    /// secondary.
+    fn test_forces_fallback() {
+        use crate::{c_allocator::CAllocator, fallback_allocator::FallbackAllocator};
+
+        let allocator = FallbackAllocator::new(NoopAllocator, CAllocator);
+        let mut vector: Vec<usize, &FallbackAllocator<NoopAllocator, CAllocator>> =
+            Vec::new_in(&allocator);
+        vector.extend(0..100);
+
+        assert!(vector.iter().copied().eq(0..100));
+        assert!(!allocator
+            .primary()
+            .contains(NonNull::from(&vector[0]).cast()));
+    }
+}
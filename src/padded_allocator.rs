@@ -0,0 +1,198 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    ptr::{self, NonNull},
+};
+
+/// A wrapper allocator that follows every block with `PAD` zero bytes.
+///
+/// Each request is passed to the inner allocator with `PAD` extra bytes, the extra bytes are
+/// zeroed, and only the requested size is reported back. The caller therefore always finds a
+/// zeroed sentinel, such as a C-style nul terminator for `PAD = 1`, directly after the block.
+///
+/// The inner allocator sees larger allocations than the caller asked for, so its own statistics
+/// and limits count the padding too. The sentinel is rewritten whenever a block is allocated or
+/// resized, but nothing stops code from overwriting it through a pointer past the block's end.
+#[derive(Debug, Default, Clone, Copy)]
+#[allow(clippy::module_name_repetitions)]
+pub struct PaddedAllocator<A: Allocator, const PAD: usize> {
+    inner: A,
+}
+
+impl<A: Allocator, const PAD: usize> PaddedAllocator<A, PAD> {
+    /// Wraps `inner` so that every block is followed by `PAD` zero bytes.
+    pub const fn new(inner: A) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the wrapped allocator.
+    pub const fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Returns the layout actually requested from the inner allocator for `layout`.
+    fn padded(layout: Layout) -> Result<Layout, AllocError> {
+        let size = layout.size().checked_add(PAD).ok_or(AllocError)?;
+        Layout::from_size_align(size, layout.align()).map_err(|_| AllocError)
+    }
+
+    /// Zeroes the padding after the first `size` bytes of `block` and trims the block to `size`.
+    const unsafe fn seal(block: NonNull<[u8]>, size: usize) -> NonNull<[u8]> {
+        ptr::write_bytes(block.as_mut_ptr().add(size), 0, PAD);
+        NonNull::slice_from_raw_parts(block.as_non_null_ptr(), size)
+    }
+}
+
+unsafe impl<A: Allocator, const PAD: usize> Allocator for PaddedAllocator<A, PAD> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let block = self.inner.allocate(Self::padded(layout)?)?;
+        Ok(unsafe { Self::seal(block, layout.size()) })
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let block = self.inner.allocate_zeroed(Self::padded(layout)?)?;
+        Ok(NonNull::slice_from_raw_parts(
+            block.as_non_null_ptr(),
+            layout.size(),
+        ))
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        // The padded layout was valid when the block was allocated, so it still is.
+        let padded = Self::padded(layout).unwrap_unchecked();
+        self.inner.deallocate(allocated_ptr, padded);
+    }
+
+    /// The old padding becomes part of the grown block and new padding is written at the end.
+    unsafe fn grow(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let old_padded = Self::padded(old_layout).unwrap_unchecked();
+        let block = self
+            .inner
+            .grow(old_ptr, old_padded, Self::padded(new_layout)?)?;
+        Ok(Self::seal(block, new_layout.size()))
+    }
+
+    unsafe fn grow_zeroed(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let old_padded = Self::padded(old_layout).unwrap_unchecked();
+        let block = self
+            .inner
+            .grow_zeroed(old_ptr, old_padded, Self::padded(new_layout)?)?;
+        // The old padding was zero and everything after it is zeroed by the inner allocator.
+        Ok(NonNull::slice_from_raw_parts(
+            block.as_non_null_ptr(),
+            new_layout.size(),
+        ))
+    }
+
+    unsafe fn shrink(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let old_padded = Self::padded(old_layout).unwrap_unchecked();
+        let block = self
+            .inner
+            .shrink(old_ptr, old_padded, Self::padded(new_layout)?)?;
+        Ok(Self::seal(block, new_layout.size()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::alloc::Global;
+    use core::ffi::CStr;
+
+    #[test]
+    /// Tests that a string written into a block with `PAD = 1` reads back as a C string.
+    fn test_nul_terminated_string() {
+        let allocator = PaddedAllocator::<_, 1>::new(Global);
+        let text = b"protobuf";
+        let layout = Layout::array::<u8>(text.len()).unwrap();
+
+        let block = allocator.allocate(layout).unwrap();
+        assert_eq!(block.len(), text.len());
+        unsafe {
+            ptr::copy_nonoverlapping(text.as_ptr(), block.as_mut_ptr(), text.len());
+            let string = CStr::from_ptr(block.as_mut_ptr().cast());
+            assert_eq!(string.to_bytes(), text);
+            allocator.deallocate(block.as_non_null_ptr(), layout);
+        }
+    }
+
+    #[test]
+    /// Tests that `grow` and `shrink` move the terminator to the new end of the block.
+    fn test_resize_moves_padding() {
+        let allocator = PaddedAllocator::<_, 1>::new(Global);
+        let old_layout = Layout::array::<u8>(4).unwrap();
+        let new_layout = Layout::array::<u8>(8).unwrap();
+
+        unsafe {
+            let block = allocator.allocate(old_layout).unwrap();
+            ptr::copy_nonoverlapping(b"abcd".as_ptr(), block.as_mut_ptr(), 4);
+
+            let grown = allocator
+                .grow(block.as_non_null_ptr(), old_layout, new_layout)
+                .unwrap();
+            assert_eq!(grown.len(), 8);
+            ptr::copy_nonoverlapping(b"efgh".as_ptr(), grown.as_mut_ptr().add(4), 4);
+            let string = CStr::from_ptr(grown.as_mut_ptr().cast());
+            assert_eq!(string.to_bytes(), b"abcdefgh");
+
+            let shrunk = allocator
+                .shrink(grown.as_non_null_ptr(), new_layout, old_layout)
+                .unwrap();
+            let string = CStr::from_ptr(shrunk.as_mut_ptr().cast());
+            assert_eq!(string.to_bytes(), b"abcd");
+            allocator.deallocate(shrunk.as_non_null_ptr(), old_layout);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "arena_allocator")]
+    /// Tests that the padding is zeroed even when the inner allocator hands out dirty memory.
+    fn test_padding_overwrites_old_contents() {
+        use crate::arena_allocator::ArenaAllocator;
+
+        let mut arena = ArenaAllocator::new(64);
+        let dirty = arena.allocate_array::<u8>(64).unwrap();
+        unsafe { ptr::write_bytes(dirty.as_mut_ptr(), 0xFF, 64) };
+        arena.reset();
+
+        let allocator = PaddedAllocator::<_, 4>::new(&arena);
+        let layout = Layout::array::<u8>(16).unwrap();
+        let block = allocator.allocate(layout).unwrap();
+        assert_eq!(arena.used(), 20);
+        let padding = unsafe { core::slice::from_raw_parts(block.as_mut_ptr().add(16), 4) };
+        assert_eq!(padding, [0; 4]);
+    }
+
+    #[test]
+    /// Tests the `PaddedAllocator` with a generic vector.
+    fn test_generic_vector_with_padded_allocator() {
+        use alloc::vec::Vec;
+
+        let allocator = PaddedAllocator::<_, 8>::new(Global);
+        let mut vector: Vec<usize, &PaddedAllocator<Global, 8>> =
+            Vec::with_capacity_in(10, &allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    ptr::{self, NonNull},
+};
+
+/// A memory allocator that hands out whole pages straight from the operating system.
+///
+/// Every allocation is rounded up to a whole number of pages and gets its own mapping from `mmap`
+/// (POSIX) or `VirtualAlloc` (Windows), which is released again on deallocation. Mappings are
+/// page-aligned; requests for a larger alignment fail.
+///
+/// [`allocate_executable`](Self::allocate_executable) additionally makes the pages executable
+/// for JIT code generation.
+#[derive(Debug, Default, Clone, Copy)]
+#[allow(clippy::module_name_repetitions)]
+pub struct PageAllocator;
+
+impl PageAllocator {
+    /// Returns the size of a memory page in bytes.
+    #[must_use]
+    pub fn page_size() -> usize {
+        sys::page_size()
+    }
+
+    /// Allocates readable, writable and executable pages for `layout`.
+    ///
+    /// The memory must be freed with [`deallocate`](Allocator::deallocate) like any other
+    /// allocation from this allocator.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError` if `layout` is zero-sized, its alignment exceeds the page size, or
+    /// the operating system refuses to map or protect the pages.
+    pub fn allocate_executable(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if layout.size() == 0 {
+            return Err(AllocError);
+        }
+        let allocated_ptr = self.allocate(layout)?;
+        let length = page_length(layout).ok_or(AllocError)?;
+        if unsafe { sys::make_executable(allocated_ptr.as_mut_ptr(), length) } {
+            Ok(allocated_ptr)
+        } else {
+            unsafe { self.deallocate(allocated_ptr.as_non_null_ptr(), layout) };
+            Err(AllocError)
+        }
+    }
+}
+
+/// Returns the size of `layout` rounded up to whole pages.
+fn page_length(layout: Layout) -> Option<usize> {
+    let page_size = PageAllocator::page_size();
+    Some(layout.size().checked_add(page_size - 1)? & !(page_size - 1))
+}
+
+unsafe impl Allocator for PageAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if layout.size() == 0 {
+            let dangling = ptr::without_provenance_mut(layout.align());
+            return NonNull::new(ptr::slice_from_raw_parts_mut(dangling, 0)).ok_or(AllocError);
+        }
+        if layout.align() > Self::page_size() {
+            return Err(AllocError);
+        }
+
+        let length = page_length(layout).ok_or(AllocError)?;
+        let allocated_ptr = unsafe { sys::map_pages(length) };
+        NonNull::new(ptr::slice_from_raw_parts_mut(allocated_ptr, layout.size())).ok_or(AllocError)
+    }
+
+    /// Fresh pages are always zero-filled by the OS, so no extra zeroing is needed.
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        if layout.size() == 0 {
+            return;
+        }
+        let length = page_length(layout).unwrap_or_else(|| layout.size());
+        sys::unmap_pages(allocated_ptr.as_ptr(), length);
+    }
+}
+
+#[cfg(unix)]
+mod sys {
+    use core::{alloc::Layout, ptr};
+
+    use libc::{mprotect, PROT_EXEC, PROT_READ, PROT_WRITE};
+
+    use crate::mmap_allocator::{self, MmapAllocator};
+
+    pub fn page_size() -> usize {
+        MmapAllocator::page_size()
+    }
+
+    /// Maps `length` bytes of readable and writable memory, returning null on failure.
+    pub unsafe fn map_pages(length: usize) -> *mut u8 {
+        Layout::from_size_align(length, 1)
+            .ok()
+            .and_then(|layout| mmap_allocator::map_memory(layout).ok())
+            .unwrap_or(ptr::null_mut())
+    }
+
+    pub unsafe fn unmap_pages(mapped_ptr: *mut u8, length: usize) {
+        // SAFETY: the pages were mapped by `map_pages` with this length, so it formed a valid
+        // layout then.
+        let layout = Layout::from_size_align_unchecked(length, 1);
+        mmap_allocator::unmap_memory(mapped_ptr, layout);
+    }
+
+    pub unsafe fn make_executable(mapped_ptr: *mut u8, length: usize) -> bool {
+        mprotect(
+            mapped_ptr.cast(),
+            length,
+            PROT_READ | PROT_WRITE | PROT_EXEC,
+        ) == 0
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use core::{mem::MaybeUninit, ptr};
+
+    use winapi::um::{
+        memoryapi::{VirtualAlloc, VirtualFree, VirtualProtect},
+        sysinfoapi::GetSystemInfo,
+        winnt::{MEM_COMMIT, MEM_RELEASE, MEM_RESERVE, PAGE_EXECUTE_READWRITE, PAGE_READWRITE},
+    };
+
+    pub fn page_size() -> usize {
+        let mut info = MaybeUninit::uninit();
+        unsafe {
+            GetSystemInfo(info.as_mut_ptr());
+            info.assume_init().dwPageSize as usize
+        }
+    }
+
+    /// Maps `length` bytes of readable and writable memory, returning null on failure.
+    pub unsafe fn map_pages(length: usize) -> *mut u8 {
+        VirtualAlloc(
+            ptr::null_mut(),
+            length,
+            MEM_COMMIT | MEM_RESERVE,
+            PAGE_READWRITE,
+        )
+        .cast()
+    }
+
+    pub unsafe fn unmap_pages(mapped_ptr: *mut u8, _length: usize) {
+        VirtualFree(mapped_ptr.cast(), 0, MEM_RELEASE);
+    }
+
+    pub unsafe fn make_executable(mapped_ptr: *mut u8, length: usize) -> bool {
+        let mut old_protect = 0;
+        VirtualProtect(
+            mapped_ptr.cast(),
+            length,
+            PAGE_EXECUTE_READWRITE,
+            &raw mut old_protect,
+        ) != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `PageAllocator` with a generic vector.
+    fn test_generic_vector_with_page_allocator() {
+        let mut vector: Vec<usize, PageAllocator> = Vec::with_capacity_in(100, PageAllocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    /// Tests that allocations are page-aligned, zeroed and span whole pages.
+    fn test_whole_pages() {
+        let page_size = PageAllocator::page_size();
+        let layout = Layout::from_size_align(page_size + 1, 8).unwrap();
+        let allocated_ptr = PageAllocator.allocate(layout).unwrap();
+        assert_eq!(allocated_ptr.as_mut_ptr() as usize % page_size, 0);
+
+        // The second page is fully mapped even though only one byte of it was requested.
+        let bytes =
+            unsafe { core::slice::from_raw_parts_mut(allocated_ptr.as_mut_ptr(), 2 * page_size) };
+        assert!(bytes.iter().all(|&byte| byte == 0));
+        bytes.fill(0xCD);
+
+        unsafe { PageAllocator.deallocate(allocated_ptr.as_non_null_ptr(), layout) };
+    }
+
+    #[test]
+    /// Tests that alignments above the page size are rejected.
+    fn test_alignment_above_page_size() {
+        let layout = Layout::from_size_align(64, 2 * PageAllocator::page_size()).unwrap();
+        assert!(PageAllocator.allocate(layout).is_err());
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    /// Tests writing and calling a native function in executable pages.
+    fn test_execute_native_function() {
+        // mov eax, 42; ret
+        const CODE: [u8; 6] = [0xB8, 0x2A, 0x00, 0x00, 0x00, 0xC3];
+
+        let layout = Layout::from_size_align(CODE.len(), 1).unwrap();
+        let code_ptr = PageAllocator.allocate_executable(layout).unwrap();
+        unsafe {
+            ptr::copy_nonoverlapping(CODE.as_ptr(), code_ptr.as_mut_ptr(), CODE.len());
+            let function: extern "C" fn() -> i32 = core::mem::transmute(code_ptr.as_mut_ptr());
+            assert_eq!(function(), 42);
+            PageAllocator.deallocate(code_ptr.as_non_null_ptr(), layout);
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    ptr::NonNull,
+};
+
+/// A wrapper allocator that panics instead of returning an error when the inner allocator runs
+/// out of memory.
+///
+/// Collections such as `Vec` already panic on allocation failure, but calling
+/// [`Allocator::allocate`] directly forces every caller to handle a `Result`. Through this
+/// wrapper allocation and growth always succeed or panic with a message naming the failed
+/// layout, so [`PanicOnOomAllocator::allocate_infallible`] can hand out the block directly.
+#[derive(Debug, Default, Clone, Copy)]
+#[allow(clippy::module_name_repetitions)]
+pub struct PanicOnOomAllocator<A: Allocator> {
+    inner: A,
+}
+
+impl<A: Allocator> PanicOnOomAllocator<A> {
+    /// Wraps `inner` so that its allocation failures panic.
+    pub const fn new(inner: A) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the wrapped allocator.
+    pub const fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Allocates a block for `layout`.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the size and alignment of `layout` if the inner allocator fails.
+    #[track_caller]
+    pub fn allocate_infallible(&self, layout: Layout) -> NonNull<[u8]> {
+        expect_block(self.inner.allocate(layout), layout)
+    }
+}
+
+/// Unwraps `block`, panicking with `layout` if the allocation failed.
+#[track_caller]
+fn expect_block(block: Result<NonNull<[u8]>, AllocError>, layout: Layout) -> NonNull<[u8]> {
+    block.unwrap_or_else(|_| panic!("OOM: {layout:?}"))
+}
+
+unsafe impl<A: Allocator> Allocator for PanicOnOomAllocator<A> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        Ok(self.allocate_infallible(layout))
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        Ok(expect_block(self.inner.allocate_zeroed(layout), layout))
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        self.inner.deallocate(allocated_ptr, layout);
+    }
+
+    unsafe fn grow(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        Ok(expect_block(
+            self.inner.grow(old_ptr, old_layout, new_layout),
+            new_layout,
+        ))
+    }
+
+    unsafe fn grow_zeroed(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        Ok(expect_block(
+            self.inner.grow_zeroed(old_ptr, old_layout, new_layout),
+            new_layout,
+        ))
+    }
+
+    unsafe fn shrink(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        Ok(expect_block(
+            self.inner.shrink(old_ptr, old_layout, new_layout),
+            new_layout,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{alloc::Global, vec::Vec};
+
+    #[test]
+    /// Tests the `PanicOnOomAllocator` with a generic vector.
+    fn test_generic_vector_with_panic_on_oom() {
+        let allocator = PanicOnOomAllocator::new(Global);
+        let mut vector: Vec<usize, &PanicOnOomAllocator<Global>> = Vec::new_in(&allocator);
+        vector.extend(0..100);
+        assert!(vector.iter().copied().eq(0..100));
+
+        let layout = Layout::new::<u64>();
+        let block = allocator.allocate_infallible(layout);
+        unsafe { allocator.deallocate(block.as_non_null_ptr(), layout) };
+    }
+
+    #[test]
+    #[cfg(feature = "noop_allocator")]
+    #[should_panic(expected = "OOM: Layout { size: 24, align: 8")]
+    /// Tests that a failed allocation panics with the size and alignment of the layout.
+    fn test_panics_on_oom() {
+        use crate::noop_allocator::NoopAllocator;
+
+        let allocator = PanicOnOomAllocator::new(NoopAllocator);
+        let _ = allocator.allocate_infallible(Layout::from_size_align(24, 8).unwrap());
+    }
+}
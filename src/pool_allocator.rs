@@ -0,0 +1,369 @@
+use core::{
+    alloc::{AllocError, Allocator, GlobalAlloc, Layout},
+    cell::Cell,
+    mem,
+    ptr::{self, NonNull},
+};
+
+use alloc::alloc::handle_alloc_error;
+
+use crate::block_buffer::{block_align, buffer_layout};
+
+/// Node stored in every free block, linking it to the next free block.
+#[derive(Debug)]
+struct FreeBlock {
+    next: Option<NonNull<Self>>,
+}
+
+/// A fixed-size block allocator that keeps its free blocks in an intrusive linked list.
+///
+/// Allocating pops the head of the list and deallocating pushes the block back, so both are
+/// O(1). Every request must fit in one block; blocks are aligned to the largest power of two
+/// dividing the block size, up to a page.
+///
+/// The pool also implements [`GlobalAlloc`], which suits targets with small, predictable memory
+/// needs. The pool is not `Sync`, so on a single-threaded target it has to be wrapped, and
+/// [`PoolAllocator::from_raw_parts`] lets it run over a static buffer instead of the heap:
+///
+/// ```no_run
+/// use core::{
+///     alloc::{GlobalAlloc, Layout},
+///     cell::UnsafeCell,
+///     ptr::NonNull,
+/// };
+///
+/// use mem_allocs::pool_allocator::PoolAllocator;
+///
+/// struct Heap {
+///     buffer: UnsafeCell<[u64; 4096]>,
+///     pool: UnsafeCell<Option<PoolAllocator>>,
+/// }
+///
+/// // SAFETY: the target runs a single thread and never allocates from interrupts.
+/// unsafe impl Sync for Heap {}
+///
+/// impl Heap {
+///     unsafe fn pool(&self) -> &PoolAllocator {
+///         (*self.pool.get()).get_or_insert_with(|| {
+///             let base = NonNull::new_unchecked(self.buffer.get().cast::<u8>());
+///             PoolAllocator::from_raw_parts(base, 256, 128)
+///         })
+///     }
+/// }
+///
+/// unsafe impl GlobalAlloc for Heap {
+///     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+///         self.pool().alloc(layout)
+///     }
+///
+///     unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+///         self.pool().dealloc(ptr, layout);
+///     }
+///
+///     unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+///         self.pool().realloc(ptr, layout, new_size)
+///     }
+/// }
+///
+/// #[global_allocator]
+/// static HEAP: Heap = Heap {
+///     buffer: UnsafeCell::new([0; 4096]),
+///     pool: UnsafeCell::new(None),
+/// };
+/// ```
+///
+/// Every allocation takes a whole block, so `realloc` can never grow past the block size and
+/// returns null for larger sizes; the program must keep all of its allocations that small.
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct PoolAllocator {
+    base: NonNull<u8>,
+    block_size: usize,
+    block_count: usize,
+    /// Alignment every block is guaranteed to have.
+    block_align: usize,
+    /// Whether the buffer was allocated by [`PoolAllocator::new`] and must be freed on drop.
+    owned: bool,
+    free_head: Cell<Option<NonNull<FreeBlock>>>,
+    used_blocks: Cell<usize>,
+}
+
+impl PoolAllocator {
+    /// Creates a new pool of `block_count` blocks of `block_size` bytes each.
+    ///
+    /// The block size is rounded up to a multiple of the pointer size so every free block can
+    /// hold a link.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_size` or `block_count` is zero, or if the buffer size overflows.
+    #[must_use]
+    pub fn new(block_size: usize, block_count: usize) -> Self {
+        assert!(block_size > 0, "block size must not be zero");
+        assert!(block_count > 0, "block count must not be zero");
+
+        let block_size = Self::round_block_size(block_size).expect("buffer size overflows");
+        let layout = buffer_layout(block_size, block_count);
+        let base = NonNull::new(unsafe { alloc::alloc::alloc(layout) })
+            .unwrap_or_else(|| handle_alloc_error(layout));
+
+        unsafe { Self::with_buffer(base, block_size, block_count, true) }
+    }
+
+    /// Creates a pool of `block_count` blocks of `block_size` bytes in memory the pool does not
+    /// own, such as a region claimed from an arena.
+    ///
+    /// The block size is rounded up like in [`PoolAllocator::new`]. Blocks are aligned to the
+    /// largest power of two dividing both the block size and the address of `base`. The memory
+    /// is not freed when the pool is dropped.
+    ///
+    /// # Safety
+    ///
+    /// `base` must be aligned for a pointer and valid for reads and writes of
+    /// `block_count` times the rounded block size for as long as the pool or any block from it
+    /// is in use, and nothing else may access that memory in the meantime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_size` or `block_count` is zero, or if the buffer size overflows.
+    #[must_use]
+    pub unsafe fn from_raw_parts(base: NonNull<u8>, block_size: usize, block_count: usize) -> Self {
+        assert!(block_size > 0, "block size must not be zero");
+        assert!(block_count > 0, "block count must not be zero");
+
+        let block_size = Self::round_block_size(block_size).expect("buffer size overflows");
+        Self::with_buffer(base, block_size, block_count, false)
+    }
+
+    /// Builds the pool over `base` and links all of its blocks into the free list.
+    unsafe fn with_buffer(
+        base: NonNull<u8>,
+        block_size: usize,
+        block_count: usize,
+        owned: bool,
+    ) -> Self {
+        let base_align = 1 << (base.as_ptr() as usize).trailing_zeros();
+        let pool = Self {
+            base,
+            block_size,
+            block_count,
+            block_align: block_align(block_size).min(base_align),
+            owned,
+            free_head: Cell::new(None),
+            used_blocks: Cell::new(0),
+        };
+        // Link the blocks back to front so they are handed out in address order.
+        for index in (0..block_count).rev() {
+            pool.push(base.add(index * block_size));
+        }
+        pool
+    }
+
+    /// Returns the size of every block in bytes.
+    #[must_use]
+    pub const fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Returns the total number of blocks.
+    #[must_use]
+    pub const fn capacity_blocks(&self) -> usize {
+        self.block_count
+    }
+
+    /// Returns the number of blocks currently in use.
+    #[must_use]
+    pub const fn used_blocks(&self) -> usize {
+        self.used_blocks.get()
+    }
+
+    /// Returns `true` if `ptr` points into the buffer of this pool.
+    #[must_use]
+    pub fn contains(&self, ptr: NonNull<u8>) -> bool {
+        let start = self.base.as_ptr() as usize;
+        let address = ptr.as_ptr() as usize;
+        start <= address && address < start + self.block_size * self.block_count
+    }
+
+    /// Rounds `block_size` up so a block can hold a free-list link.
+    pub(crate) const fn round_block_size(block_size: usize) -> Option<usize> {
+        block_size.checked_next_multiple_of(mem::size_of::<FreeBlock>())
+    }
+
+    /// Puts the block at `block` onto the front of the free list.
+    unsafe fn push(&self, block: NonNull<u8>) {
+        let block = block.cast::<FreeBlock>();
+        block.write(FreeBlock {
+            next: self.free_head.get(),
+        });
+        self.free_head.set(Some(block));
+    }
+}
+
+unsafe impl Allocator for PoolAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if layout.size() > self.block_size || layout.align() > self.block_align {
+            return Err(AllocError);
+        }
+
+        let block = self.free_head.get().ok_or(AllocError)?;
+        self.free_head.set(unsafe { block.as_ref().next });
+        self.used_blocks.set(self.used_blocks.get() + 1);
+        Ok(NonNull::slice_from_raw_parts(block.cast(), layout.size()))
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, _: Layout) {
+        debug_assert!(
+            self.contains(allocated_ptr),
+            "pointer not owned by this pool"
+        );
+        self.push(allocated_ptr);
+        self.used_blocks.set(self.used_blocks.get() - 1);
+    }
+}
+
+unsafe impl GlobalAlloc for PoolAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.allocate(layout)
+            .map_or(ptr::null_mut(), NonNull::as_mut_ptr)
+    }
+
+    /// Freed blocks keep their old contents, so the memory is always cleared.
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let allocated_ptr = self.alloc(layout);
+        if !allocated_ptr.is_null() {
+            ptr::write_bytes(allocated_ptr, 0, layout.size());
+        }
+        allocated_ptr
+    }
+
+    unsafe fn dealloc(&self, allocated_ptr: *mut u8, layout: Layout) {
+        self.deallocate(NonNull::new_unchecked(allocated_ptr), layout);
+    }
+
+    /// Every block already spans the full block size, so a block is resized in place. Sizes
+    /// larger than a block cannot be honoured and return null, leaving the old block untouched.
+    unsafe fn realloc(&self, old_ptr: *mut u8, _: Layout, new_size: usize) -> *mut u8 {
+        if new_size > self.block_size {
+            return ptr::null_mut();
+        }
+        old_ptr
+    }
+}
+
+impl Drop for PoolAllocator {
+    fn drop(&mut self) {
+        if !self.owned {
+            return;
+        }
+        let layout = buffer_layout(self.block_size, self.block_count);
+        unsafe { alloc::alloc::dealloc(self.base.as_ptr(), layout) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `PoolAllocator` with a generic vector that fits in a single block.
+    fn test_generic_vector_with_pool_allocator() {
+        let allocator = PoolAllocator::new(1024, 4);
+        let mut vector: Vec<usize, &PoolAllocator> = Vec::with_capacity_in(100, &allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        assert_eq!(allocator.used_blocks(), 1);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+        assert_eq!(allocator.used_blocks(), 0);
+    }
+
+    #[test]
+    /// Tests that every block is handed out exactly once and freed blocks are reused first.
+    fn test_exhaust_and_reuse() {
+        let allocator = PoolAllocator::new(32, 16);
+        let layout = Layout::from_size_align(32, 32).unwrap();
+
+        let mut blocks: Vec<usize> = (0..16)
+            .map(|_| allocator.allocate(layout).unwrap().as_mut_ptr() as usize)
+            .collect();
+        assert!(allocator.allocate(layout).is_err());
+        assert_eq!(allocator.used_blocks(), allocator.capacity_blocks());
+
+        blocks.sort_unstable();
+        blocks.dedup();
+        assert_eq!(blocks.len(), 16);
+        assert!(blocks.iter().all(|block| block % 32 == 0));
+
+        let freed = NonNull::new(blocks[7] as *mut u8).unwrap();
+        unsafe { allocator.deallocate(freed, layout) };
+        assert_eq!(allocator.allocate(layout).unwrap().as_non_null_ptr(), freed);
+    }
+
+    #[test]
+    /// Tests that the block size is rounded up to hold a free-list link.
+    fn test_block_size_rounding() {
+        let allocator = PoolAllocator::new(1, 4);
+        assert_eq!(allocator.block_size(), mem::size_of::<usize>());
+    }
+
+    #[test]
+    /// Tests a pool over a borrowed buffer, whose alignment limits the block alignment.
+    fn test_from_raw_parts() {
+        let mut buffer = [0_u64; 17];
+        let base = NonNull::new(buffer.as_mut_ptr().cast::<u8>()).unwrap();
+        let pool = unsafe { PoolAllocator::from_raw_parts(base.byte_add(8), 64, 2) };
+
+        let block = pool
+            .allocate(Layout::from_size_align(64, 8).unwrap())
+            .unwrap();
+        assert!(pool.contains(block.as_non_null_ptr()));
+        let aligned = Layout::from_size_align(64, 64).unwrap();
+        let fits = (base.as_ptr() as usize + 8).is_multiple_of(64);
+        assert_eq!(pool.allocate(aligned).is_ok(), fits);
+    }
+
+    #[test]
+    /// Tests the `GlobalAlloc` implementation, including `realloc` beyond the block size.
+    fn test_global_alloc() {
+        let allocator = PoolAllocator::new(64, 2);
+        let layout = Layout::from_size_align(16, 8).unwrap();
+
+        unsafe {
+            let block = allocator.alloc_zeroed(layout);
+            assert!(!block.is_null());
+            assert!(core::slice::from_raw_parts(block, 16)
+                .iter()
+                .all(|&byte| byte == 0));
+
+            assert_eq!(allocator.realloc(block, layout, 64), block);
+            assert!(allocator.realloc(block, layout, 65).is_null());
+            assert_eq!(allocator.used_blocks(), 1);
+
+            allocator.dealloc(block, layout);
+        }
+        assert_eq!(allocator.used_blocks(), 0);
+    }
+
+    #[test]
+    /// Tests that requests larger or more aligned than a block are rejected.
+    fn test_rejects_unfit_layouts() {
+        let allocator = PoolAllocator::new(48, 8);
+
+        assert!(allocator
+            .allocate(Layout::from_size_align(49, 1).unwrap())
+            .is_err());
+        assert!(allocator
+            .allocate(Layout::from_size_align(8, 32).unwrap())
+            .is_err());
+        assert!(allocator
+            .allocate(Layout::from_size_align(48, 16).unwrap())
+            .is_ok());
+    }
+}
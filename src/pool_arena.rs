@@ -0,0 +1,133 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    marker::PhantomData,
+    ptr::NonNull,
+};
+
+use alloc::vec::Vec;
+
+use crate::{arena_allocator::ArenaAllocator, block_buffer, pool_allocator::PoolAllocator};
+
+/// A set of fixed-size pools carved out of a borrowed [`ArenaAllocator`].
+///
+/// Construction claims one region of the arena per size class and runs a [`PoolAllocator`] over
+/// it. Requests go to the smallest class that fits their size and alignment and still has a
+/// free block; requests no class can hold fail. The arena is only borrowed, so the regions are
+/// reclaimed by resetting it once the pool arena is gone.
+#[derive(Debug)]
+pub struct PoolArena<'a> {
+    /// Pools sorted by ascending block size.
+    pools: Vec<PoolAllocator>,
+    _arena: PhantomData<&'a ArenaAllocator>,
+}
+
+impl<'a> PoolArena<'a> {
+    /// Claims `block_size * block_count` bytes from `arena` for every `(block_size, block_count)`
+    /// pair in `classes` and sets up a pool over each region.
+    ///
+    /// Block sizes are rounded up like in [`PoolAllocator::new`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError` if the arena cannot hold every region, or if a block size or
+    /// count is zero or overflows.
+    pub fn new(arena: &'a ArenaAllocator, classes: &[(usize, usize)]) -> Result<Self, AllocError> {
+        let mut pools = Vec::with_capacity(classes.len());
+        for &(block_size, block_count) in classes {
+            if block_size == 0 || block_count == 0 {
+                return Err(AllocError);
+            }
+            let block_size = PoolAllocator::round_block_size(block_size).ok_or(AllocError)?;
+            let region = Layout::from_size_align(
+                block_size.checked_mul(block_count).ok_or(AllocError)?,
+                block_buffer::block_align(block_size),
+            )
+            .map_err(|_| AllocError)?;
+
+            let base = arena.allocate(region)?.as_non_null_ptr();
+            // SAFETY: the arena handed the region out exclusively and it is never deallocated.
+            // `_arena` holds the `'a` borrow, which keeps the arena alive and, because
+            // `ArenaAllocator::reset` takes `&mut self`, unreset while the pool arena exists.
+            pools.push(unsafe { PoolAllocator::from_raw_parts(base, block_size, block_count) });
+        }
+        pools.sort_by_key(PoolAllocator::block_size);
+
+        Ok(Self {
+            pools,
+            _arena: PhantomData,
+        })
+    }
+
+    /// Returns the pools, sorted by ascending block size.
+    #[must_use]
+    pub fn pools(&self) -> &[PoolAllocator] {
+        &self.pools
+    }
+}
+
+unsafe impl Allocator for PoolArena<'_> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.pools
+            .iter()
+            .find_map(|pool| pool.allocate(layout).ok())
+            .ok_or(AllocError)
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        if let Some(pool) = self.pools.iter().find(|pool| pool.contains(allocated_ptr)) {
+            pool.deallocate(allocated_ptr, layout);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `PoolArena` with a generic vector that fits in its largest class.
+    fn test_generic_vector_with_pool_arena() {
+        let arena = ArenaAllocator::new(4096);
+        let pools = PoolArena::new(&arena, &[(1024, 2), (64, 8)]).unwrap();
+        let mut vector: Vec<usize, &PoolArena> = Vec::with_capacity_in(100, &pools);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+        assert!(pools.pools().iter().all(|pool| pool.used_blocks() == 0));
+    }
+
+    #[test]
+    /// Tests that each class claims its region from the arena and that requests are routed to
+    /// the smallest class with room.
+    fn test_routing() {
+        let arena = ArenaAllocator::new(4096);
+        let pools = PoolArena::new(&arena, &[(128, 2), (32, 1)]).unwrap();
+        assert!(arena.used() >= 2 * 128 + 32);
+
+        let small = Layout::from_size_align(24, 8).unwrap();
+        let first = pools.allocate(small).unwrap();
+        let second = pools.allocate(small).unwrap();
+        assert!(pools.pools()[0].contains(first.as_non_null_ptr()));
+        assert!(pools.pools()[1].contains(second.as_non_null_ptr()));
+
+        unsafe { pools.deallocate(first.as_non_null_ptr(), small) };
+        assert_eq!(pools.pools()[0].used_blocks(), 0);
+        assert!(pools
+            .allocate(Layout::from_size_align(256, 8).unwrap())
+            .is_err());
+    }
+
+    #[test]
+    /// Tests that construction fails when the arena is too small for every class.
+    fn test_arena_too_small() {
+        let arena = ArenaAllocator::new(256);
+        assert!(PoolArena::new(&arena, &[(64, 2), (128, 2)]).is_err());
+    }
+}
@@ -0,0 +1,300 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    cell::UnsafeCell,
+    hint,
+    ptr::NonNull,
+    sync::atomic::{AtomicU8, AtomicUsize, Ordering},
+};
+
+/// The maximum number of distinct labels that can be tracked at once.
+pub const MAX_LABELS: usize = 32;
+
+/// The slot is free.
+const EMPTY: u8 = 0;
+/// A thread is writing the slot's label.
+const CLAIMING: u8 = 1;
+/// The slot's label is written and will not change.
+const READY: u8 = 2;
+
+/// The usage counter of one label.
+#[derive(Debug)]
+struct LabelSlot {
+    state: AtomicU8,
+    label: UnsafeCell<&'static str>,
+    usage: AtomicUsize,
+}
+
+// SAFETY: `label` is only written by the thread that moved `state` from `EMPTY` to `CLAIMING`,
+// and only read once `state` is `READY`.
+unsafe impl Sync for LabelSlot {}
+
+impl LabelSlot {
+    const fn new() -> Self {
+        Self {
+            state: AtomicU8::new(EMPTY),
+            label: UnsafeCell::new(""),
+            usage: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the slot's label if it has been published.
+    fn label(&self) -> Option<&'static str> {
+        // SAFETY: the label is never written again once the slot is `READY`.
+        (self.state.load(Ordering::Acquire) == READY).then(|| unsafe { *self.label.get() })
+    }
+}
+
+/// The usage counters shared by every `QuotaAllocator`, claimed in order.
+static SLOTS: [LabelSlot; MAX_LABELS] = [const { LabelSlot::new() }; MAX_LABELS];
+
+/// Returns the slot tracking `label`, claiming a free one if `register` is set.
+fn find_slot(label: &'static str, register: bool) -> Option<&'static LabelSlot> {
+    for slot in &SLOTS {
+        loop {
+            match slot.state.load(Ordering::Acquire) {
+                READY => {
+                    if slot.label() == Some(label) {
+                        return Some(slot);
+                    }
+                    break;
+                }
+                CLAIMING => hint::spin_loop(),
+                _ if !register => return None,
+                _ => {
+                    if slot
+                        .state
+                        .compare_exchange(EMPTY, CLAIMING, Ordering::Acquire, Ordering::Acquire)
+                        .is_ok()
+                    {
+                        // SAFETY: claiming the slot gives this thread exclusive access to the
+                        // label until it is published.
+                        unsafe { *slot.label.get() = label };
+                        slot.state.store(READY, Ordering::Release);
+                        return Some(slot);
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Returns the number of bytes currently allocated under `label` by all `QuotaAllocator`s.
+#[must_use]
+pub fn global_usage(label: &'static str) -> usize {
+    find_slot(label, false).map_or(0, |slot| slot.usage.load(Ordering::Relaxed))
+}
+
+/// Sets the usage of `label` back to zero.
+///
+/// Blocks that are still live when the label is reset no longer count against its quota; they
+/// are returned to the budget without letting the usage drop below zero.
+pub fn reset_label(label: &'static str) {
+    if let Some(slot) = find_slot(label, false) {
+        slot.usage.store(0, Ordering::Relaxed);
+    }
+}
+
+/// A wrapper allocator that charges every allocation to a named, process-wide budget.
+///
+/// Every `QuotaAllocator` with the same label shares one usage counter, so a subsystem can be
+/// capped however many allocators it creates. Each instance enforces its own `limit` against
+/// that shared usage. Counters live in a fixed table of [`MAX_LABELS`] entries, so no heap or
+/// locking is needed. Labels are compared by content.
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct QuotaAllocator<A: Allocator> {
+    inner: A,
+    label: &'static str,
+    limit: usize,
+    slot: &'static LabelSlot,
+}
+
+impl<A: Allocator> QuotaAllocator<A> {
+    /// Wraps `inner`, charging its allocations to `label` and failing them once the label's
+    /// usage would exceed `limit` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `label` is new and [`MAX_LABELS`] labels are already tracked.
+    pub fn new(inner: A, label: &'static str, limit: usize) -> Self {
+        let slot = find_slot(label, true).expect("too many quota labels");
+        Self {
+            inner,
+            label,
+            limit,
+            slot,
+        }
+    }
+
+    /// Returns the label allocations are charged to.
+    pub const fn label(&self) -> &'static str {
+        self.label
+    }
+
+    /// Returns the maximum number of bytes this allocator lets the label use.
+    pub const fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Returns the number of bytes currently charged to the label, by any allocator.
+    pub fn usage(&self) -> usize {
+        self.slot.usage.load(Ordering::Relaxed)
+    }
+
+    /// Charges `bytes` to the label.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError` if charging `bytes` would exceed the limit.
+    fn reserve(&self, bytes: usize) -> Result<(), AllocError> {
+        let previous = self.slot.usage.fetch_add(bytes, Ordering::Relaxed);
+        if previous
+            .checked_add(bytes)
+            .is_none_or(|total| total > self.limit)
+        {
+            self.release(bytes);
+            return Err(AllocError);
+        }
+        Ok(())
+    }
+
+    /// Returns `bytes` to the label's budget.
+    fn release(&self, bytes: usize) {
+        let _ = self
+            .slot
+            .usage
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |usage| {
+                Some(usage.saturating_sub(bytes))
+            });
+    }
+}
+
+unsafe impl<A: Allocator> Allocator for QuotaAllocator<A> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.reserve(layout.size())?;
+        self.inner.allocate(layout).inspect_err(|_| {
+            self.release(layout.size());
+        })
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.reserve(layout.size())?;
+        self.inner.allocate_zeroed(layout).inspect_err(|_| {
+            self.release(layout.size());
+        })
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        self.inner.deallocate(allocated_ptr, layout);
+        self.release(layout.size());
+    }
+
+    unsafe fn grow(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let delta = new_layout.size() - old_layout.size();
+        self.reserve(delta)?;
+        self.inner
+            .grow(old_ptr, old_layout, new_layout)
+            .inspect_err(|_| self.release(delta))
+    }
+
+    unsafe fn grow_zeroed(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let delta = new_layout.size() - old_layout.size();
+        self.reserve(delta)?;
+        self.inner
+            .grow_zeroed(old_ptr, old_layout, new_layout)
+            .inspect_err(|_| self.release(delta))
+    }
+
+    unsafe fn shrink(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let new_ptr = self.inner.shrink(old_ptr, old_layout, new_layout)?;
+        self.release(old_layout.size() - new_layout.size());
+        Ok(new_ptr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{alloc::Global, vec::Vec};
+
+    #[test]
+    /// Tests that allocators sharing a label share its usage and that the limit is enforced.
+    fn test_shared_quota() {
+        let first = QuotaAllocator::new(Global, "test_shared_quota", 64);
+        let second = QuotaAllocator::new(Global, "test_shared_quota", 64);
+        let layout = Layout::from_size_align(48, 8).unwrap();
+
+        let block = first.allocate(layout).unwrap();
+        assert_eq!(global_usage("test_shared_quota"), 48);
+        assert_eq!(second.usage(), 48);
+        assert!(second.allocate(layout).is_err());
+
+        unsafe { first.deallocate(block.as_non_null_ptr(), layout) };
+        assert_eq!(global_usage("test_shared_quota"), 0);
+        let block = second.allocate(layout).unwrap();
+        unsafe { second.deallocate(block.as_non_null_ptr(), layout) };
+    }
+
+    #[test]
+    /// Tests that different labels have independent budgets.
+    fn test_independent_labels() {
+        let network = QuotaAllocator::new(Global, "test_network", 32);
+        let render = QuotaAllocator::new(Global, "test_render", 32);
+        let layout = Layout::from_size_align(32, 8).unwrap();
+
+        let first = network.allocate(layout).unwrap();
+        let second = render.allocate(layout).unwrap();
+        assert_eq!(global_usage("test_network"), 32);
+        assert_eq!(global_usage("test_render"), 32);
+
+        unsafe {
+            network.deallocate(first.as_non_null_ptr(), layout);
+            render.deallocate(second.as_non_null_ptr(), layout);
+        }
+        assert_eq!(global_usage("test_unknown"), 0);
+    }
+
+    #[test]
+    /// Tests that resetting a label clears its usage without underflowing on later frees.
+    fn test_reset_label() {
+        let allocator = QuotaAllocator::new(Global, "test_reset_label", 64);
+        let layout = Layout::from_size_align(64, 8).unwrap();
+
+        let block = allocator.allocate(layout).unwrap();
+        reset_label("test_reset_label");
+        assert_eq!(allocator.usage(), 0);
+
+        unsafe { allocator.deallocate(block.as_non_null_ptr(), layout) };
+        assert_eq!(allocator.usage(), 0);
+    }
+
+    #[test]
+    /// Tests the `QuotaAllocator` with a generic vector.
+    fn test_generic_vector_with_quota_allocator() {
+        let allocator = QuotaAllocator::new(Global, "test_generic_vector", 64);
+        let mut vector: Vec<u8, &QuotaAllocator<Global>> = Vec::with_capacity_in(32, &allocator);
+        vector.extend(0..32);
+
+        assert!(vector.try_reserve_exact(64).is_err());
+        assert_eq!(global_usage("test_generic_vector"), 32);
+
+        drop(vector);
+        assert_eq!(global_usage("test_generic_vector"), 0);
+    }
+}
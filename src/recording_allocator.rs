@@ -0,0 +1,191 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    cell::UnsafeCell,
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// An operation recorded by a [`RecordingAllocator`].
+///
+/// Pointers are stored as plain addresses, so events can be kept and printed long after the
+/// blocks are gone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AllocEvent {
+    /// A block was handed out by the inner allocator.
+    Allocated {
+        /// The address of the block.
+        ptr: usize,
+        /// The requested size in bytes.
+        size: usize,
+        /// The requested alignment in bytes.
+        align: usize,
+    },
+    /// A block was returned to the inner allocator.
+    Deallocated {
+        /// The address of the block.
+        ptr: usize,
+    },
+}
+
+/// A wrapper allocator that remembers the last `N` allocations and deallocations in an inline
+/// ring buffer, for post-mortem debugging.
+///
+/// The buffer is part of the struct, so recording never allocates. Once `N` events have been
+/// recorded, each new event overwrites the oldest one. Resizing goes through the default
+/// `Allocator` methods, so it shows up as an allocation followed by a deallocation. Unlike
+/// [`TracingAllocator`](crate::tracing_allocator::TracingAllocator), which hands every event to
+/// a callback as it happens, the history is kept for later inspection with
+/// [`RecordingAllocator::events`].
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct RecordingAllocator<A: Allocator, const N: usize> {
+    inner: A,
+    events: UnsafeCell<[AllocEvent; N]>,
+    /// Total number of events recorded; the next one goes to slot `written % N`.
+    written: AtomicUsize,
+}
+
+impl<A: Allocator, const N: usize> RecordingAllocator<A, N> {
+    /// Wraps `inner`, recording its last `N` operations.
+    pub const fn new(inner: A) -> Self {
+        Self {
+            inner,
+            events: UnsafeCell::new([AllocEvent::Deallocated { ptr: 0 }; N]),
+            written: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns a reference to the wrapped allocator.
+    pub const fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Returns the total number of events recorded, including those already overwritten.
+    #[must_use]
+    pub fn total_events(&self) -> usize {
+        self.written.load(Ordering::Relaxed)
+    }
+
+    /// Iterates over the recorded events that have not been overwritten yet, from oldest to
+    /// newest.
+    pub fn events(&self) -> impl Iterator<Item = AllocEvent> + '_ {
+        let written = self.total_events();
+        (written.saturating_sub(N)..written)
+            .map(move |index| unsafe { (*self.events.get())[index % N] })
+    }
+
+    /// Forgets every recorded event.
+    pub fn clear(&self) {
+        self.written.store(0, Ordering::Relaxed);
+    }
+
+    /// Appends `event` to the ring buffer, overwriting the oldest event once it is full.
+    fn record(&self, event: AllocEvent) {
+        if N == 0 {
+            return;
+        }
+        let index = self.written.fetch_add(1, Ordering::Relaxed);
+        // SAFETY: the buffer is not `Sync`, so no other thread is reading or writing it.
+        unsafe { (*self.events.get())[index % N] = event };
+    }
+}
+
+unsafe impl<A: Allocator, const N: usize> Allocator for RecordingAllocator<A, N> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let allocated_ptr = self.inner.allocate(layout)?;
+        self.record(AllocEvent::Allocated {
+            ptr: allocated_ptr.as_mut_ptr() as usize,
+            size: layout.size(),
+            align: layout.align(),
+        });
+        Ok(allocated_ptr)
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        self.record(AllocEvent::Deallocated {
+            ptr: allocated_ptr.as_ptr() as usize,
+        });
+        self.inner.deallocate(allocated_ptr, layout);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{alloc::Global, vec::Vec};
+
+    #[test]
+    /// Tests the `RecordingAllocator` with a generic vector, checking the recorded history.
+    fn test_generic_vector_with_recording_allocator() {
+        let allocator = RecordingAllocator::<Global, 16>::new(Global);
+        let mut vector: Vec<usize, &RecordingAllocator<Global, 16>> =
+            Vec::with_capacity_in(100, &allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        let address = vector.as_ptr() as usize;
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+
+        let events: Vec<AllocEvent> = allocator.events().collect();
+        assert_eq!(
+            events,
+            [
+                AllocEvent::Allocated {
+                    ptr: address,
+                    size: 100 * core::mem::size_of::<usize>(),
+                    align: core::mem::align_of::<usize>(),
+                },
+                AllocEvent::Deallocated { ptr: address },
+            ]
+        );
+    }
+
+    #[test]
+    /// Tests that the ring buffer keeps only the newest `N` events, oldest first.
+    fn test_wraparound() {
+        let allocator = RecordingAllocator::<Global, 4>::new(Global);
+        let sizes = [1, 2, 3, 4, 5, 6];
+        for size in sizes {
+            let layout = Layout::from_size_align(size, 1).unwrap();
+            let block = allocator.allocate(layout).unwrap();
+            unsafe { allocator.deallocate(block.as_non_null_ptr(), layout) };
+        }
+
+        assert_eq!(allocator.total_events(), 12);
+        let events: Vec<AllocEvent> = allocator.events().collect();
+        assert_eq!(events.len(), 4);
+        assert!(matches!(events[0], AllocEvent::Allocated { size: 5, .. }));
+        assert!(matches!(events[1], AllocEvent::Deallocated { .. }));
+        assert!(matches!(events[2], AllocEvent::Allocated { size: 6, .. }));
+        assert!(matches!(events[3], AllocEvent::Deallocated { .. }));
+    }
+
+    #[test]
+    /// Tests the ring buffer before it fills up, after a clear and without any slots.
+    fn test_partial_and_empty_buffers() {
+        let allocator = RecordingAllocator::<Global, 4>::new(Global);
+        let layout = Layout::new::<u64>();
+        let block = allocator.allocate(layout).unwrap();
+        assert_eq!(allocator.events().count(), 1);
+
+        allocator.clear();
+        assert_eq!(allocator.events().count(), 0);
+        unsafe { allocator.deallocate(block.as_non_null_ptr(), layout) };
+        assert_eq!(
+            allocator.events().collect::<Vec<_>>(),
+            [AllocEvent::Deallocated {
+                ptr: block.as_mut_ptr() as usize
+            }]
+        );
+
+        let unrecorded = RecordingAllocator::<Global, 0>::new(Global);
+        let block = unrecorded.allocate(layout).unwrap();
+        unsafe { unrecorded.deallocate(block.as_non_null_ptr(), layout) };
+        assert_eq!(unrecorded.events().count(), 0);
+    }
+}
@@ -0,0 +1,290 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    cell::Cell,
+    mem::{self, MaybeUninit},
+    ptr::NonNull,
+};
+
+use alloc::vec::Vec;
+
+use crate::align::align_up;
+
+/// Node stored at the start of every freed block, linking the recycled blocks together.
+#[derive(Debug)]
+struct FreeBlock {
+    size: usize,
+    next: Option<NonNull<Self>>,
+}
+
+/// Granularity of all blocks; large enough for a [`FreeBlock`], so every freed block can hold one.
+const UNIT: usize = mem::size_of::<FreeBlock>();
+
+/// Minimum alignment of every block, so that a [`FreeBlock`] can be written at its start.
+const MIN_ALIGN: usize = mem::align_of::<FreeBlock>();
+
+/// A `UNIT`-sized cell of the backing storage, aligned for a [`FreeBlock`].
+type Unit = MaybeUninit<[usize; 2]>;
+
+/// A bump-pointer arena that recycles individually freed blocks.
+///
+/// Deallocated blocks are pushed onto an intrusive free list stored in the freed memory itself.
+/// Allocation first looks for a recycled block that is large enough and suitably aligned, and
+/// only bumps the offset when none fits. Blocks are never coalesced; [`RecyclingArena::reset`]
+/// drops the free list and starts over, just like a plain arena.
+#[derive(Debug)]
+pub struct RecyclingArena {
+    // Only kept to own the memory; all accesses go through `base`.
+    _buffer: Vec<Unit>,
+    base: NonNull<u8>,
+    capacity: usize,
+    offset: Cell<usize>,
+    free: Cell<Option<NonNull<FreeBlock>>>,
+}
+
+impl RecyclingArena {
+    /// Creates a new arena with a backing buffer of `bytes` bytes, rounded down to the block
+    /// granularity.
+    #[must_use]
+    pub fn new(bytes: usize) -> Self {
+        let mut buffer: Vec<Unit> = alloc::vec![MaybeUninit::uninit(); bytes / UNIT];
+        let base = NonNull::new(buffer.as_mut_ptr().cast::<u8>()).unwrap_or(NonNull::dangling());
+        let capacity = buffer.len() * UNIT;
+        Self {
+            _buffer: buffer,
+            base,
+            capacity,
+            offset: Cell::new(0),
+            free: Cell::new(None),
+        }
+    }
+
+    /// Returns the total size of the backing buffer in bytes.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of bytes claimed by the bump pointer so far, including recycled blocks.
+    #[must_use]
+    pub const fn used(&self) -> usize {
+        self.offset.get()
+    }
+
+    /// Returns the number of bytes the bump pointer has not reached yet.
+    #[must_use]
+    pub const fn remaining(&self) -> usize {
+        self.capacity - self.used()
+    }
+
+    /// Returns the number of freed blocks waiting to be reused.
+    #[must_use]
+    pub fn free_block_count(&self) -> usize {
+        core::iter::successors(self.free.get(), |block| unsafe { block.as_ref().next }).count()
+    }
+
+    /// Resets the arena, dropping the free list and making its whole buffer available again.
+    ///
+    /// Taking `&mut self` guarantees that no collection still borrows the arena, so no pointer
+    /// handed out before the reset can be used afterwards.
+    pub const fn reset(&mut self) {
+        *self.free.get_mut() = None;
+        *self.offset.get_mut() = 0;
+    }
+
+    /// Takes the first recycled block of at least `size` bytes starting at a multiple of
+    /// `align`, splitting off and keeping any excess.
+    fn take_free(&self, size: usize, align: usize) -> Option<NonNull<u8>> {
+        let mut previous: Option<NonNull<FreeBlock>> = None;
+        let mut current = self.free.get();
+
+        while let Some(block) = current {
+            let FreeBlock {
+                size: block_size,
+                next,
+            } = unsafe { block.read() };
+
+            if block_size >= size && (block.as_ptr() as usize).is_multiple_of(align) {
+                // Sizes are multiples of `UNIT`, so any remainder can hold a free block.
+                let replacement = if block_size > size {
+                    let tail = unsafe { block.byte_add(size) };
+                    unsafe {
+                        tail.write(FreeBlock {
+                            size: block_size - size,
+                            next,
+                        });
+                    }
+                    Some(tail)
+                } else {
+                    next
+                };
+                match previous {
+                    Some(mut previous) => unsafe { previous.as_mut().next = replacement },
+                    None => self.free.set(replacement),
+                }
+                return Some(block.cast());
+            }
+
+            previous = current;
+            current = next;
+        }
+
+        None
+    }
+
+    /// Claims `size` bytes aligned to `align` from the untouched part of the buffer.
+    fn bump(&self, size: usize, align: usize) -> Option<NonNull<u8>> {
+        let base = self.base.as_ptr() as usize;
+        let start = align_up(base.checked_add(self.offset.get())?, align)? - base;
+        let end = start.checked_add(size)?;
+        if end > self.capacity {
+            return None;
+        }
+
+        self.offset.set(end);
+        Some(unsafe { self.base.add(start) })
+    }
+}
+
+/// Returns the number of bytes a block for `layout` occupies, or `None` on overflow.
+const fn block_size(layout: Layout) -> Option<usize> {
+    align_up(layout.size(), UNIT)
+}
+
+unsafe impl Allocator for RecyclingArena {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let size = block_size(layout).ok_or(AllocError)?;
+        let align = layout.align().max(MIN_ALIGN);
+
+        let allocated_ptr = (size > 0)
+            .then(|| self.take_free(size, align))
+            .flatten()
+            .or_else(|| self.bump(size, align))
+            .ok_or(AllocError)?;
+        Ok(NonNull::slice_from_raw_parts(allocated_ptr, layout.size()))
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        let Some(size) = block_size(layout).filter(|&size| size > 0) else {
+            return;
+        };
+
+        let block = allocated_ptr.cast::<FreeBlock>();
+        block.write(FreeBlock {
+            size,
+            next: self.free.get(),
+        });
+        self.free.set(Some(block));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `RecyclingArena` with a generic vector.
+    fn test_generic_vector_with_recycling_arena() {
+        let arena = RecyclingArena::new(4096);
+        let mut vector: Vec<usize, &RecyclingArena> = Vec::new_in(&arena);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    /// Tests that a freed block is reused before the bump pointer advances.
+    fn test_reuses_freed_block() {
+        let arena = RecyclingArena::new(256);
+        let layout = Layout::from_size_align(32, 8).unwrap();
+
+        let first = arena.allocate(layout).unwrap();
+        arena.allocate(layout).unwrap();
+        let used = arena.used();
+
+        unsafe { arena.deallocate(first.as_non_null_ptr(), layout) };
+        assert_eq!(arena.free_block_count(), 1);
+
+        let reused = arena.allocate(layout).unwrap();
+        assert_eq!(reused.as_mut_ptr(), first.as_mut_ptr());
+        assert_eq!(arena.used(), used);
+        assert_eq!(arena.free_block_count(), 0);
+    }
+
+    #[test]
+    /// Tests that a large freed block is split to serve smaller requests.
+    fn test_splits_freed_block() {
+        let arena = RecyclingArena::new(256);
+        let large = Layout::from_size_align(64, 8).unwrap();
+        let small = Layout::from_size_align(16, 8).unwrap();
+
+        let block = arena.allocate(large).unwrap();
+        let used = arena.used();
+        unsafe { arena.deallocate(block.as_non_null_ptr(), large) };
+
+        let first = arena.allocate(small).unwrap();
+        let second = arena.allocate(small).unwrap();
+        assert_eq!(first.as_mut_ptr(), block.as_mut_ptr());
+        assert_eq!(
+            second.as_mut_ptr() as usize,
+            block.as_mut_ptr() as usize + 16
+        );
+        assert_eq!(arena.used(), used);
+        assert_eq!(arena.free_block_count(), 1);
+    }
+
+    #[test]
+    /// Tests that a freed block that is too small is skipped in favour of the bump pointer.
+    fn test_skips_small_blocks() {
+        let arena = RecyclingArena::new(1024);
+        let small = Layout::from_size_align(16, 8).unwrap();
+
+        arena.allocate(small).unwrap();
+        let block = arena.allocate(small).unwrap();
+        unsafe { arena.deallocate(block.as_non_null_ptr(), small) };
+
+        let large = arena
+            .allocate(Layout::from_size_align(32, 8).unwrap())
+            .unwrap();
+        assert_ne!(large.as_mut_ptr(), block.as_mut_ptr());
+        assert_eq!(arena.free_block_count(), 1);
+    }
+
+    #[test]
+    /// Tests that small blocks pack the buffer without padding, however its base is aligned.
+    fn test_fills_whole_buffer() {
+        let arena = RecyclingArena::new(64);
+        let layout = Layout::from_size_align(UNIT, 1).unwrap();
+
+        for _ in 0..64 / UNIT {
+            arena.allocate(layout).unwrap();
+        }
+        assert_eq!(arena.remaining(), 0);
+        assert!(arena.allocate(layout).is_err());
+    }
+
+    #[test]
+    /// Tests that resetting drops the free list and rewinds the bump pointer.
+    fn test_reset() {
+        let mut arena = RecyclingArena::new(128);
+        let layout = Layout::from_size_align(64, 8).unwrap();
+
+        let block = arena.allocate(layout).unwrap();
+        unsafe { arena.deallocate(block.as_non_null_ptr(), layout) };
+        arena.reset();
+
+        assert_eq!(arena.used(), 0);
+        assert_eq!(arena.free_block_count(), 0);
+        let first = arena.allocate(layout).unwrap();
+        let second = arena.allocate(layout).unwrap();
+        assert_eq!(first.as_mut_ptr(), block.as_mut_ptr());
+        assert_ne!(second.as_mut_ptr(), first.as_mut_ptr());
+        assert!(arena.allocate(layout).is_err());
+    }
+}
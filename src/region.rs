@@ -0,0 +1,150 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    mem::{self, MaybeUninit},
+    ptr::NonNull,
+    slice,
+};
+
+use crate::arena_allocator::{ArenaAllocator, ArenaAllocatorView};
+
+/// A bounded sub-arena that claims a contiguous slice of a parent arena and bumps through it with
+/// its own offset.
+///
+/// Allocations from a region can never use more than the region's size, which keeps one part of a
+/// larger data structure from starving the rest of the parent. Regions nest: a region can be
+/// carved out of another with [`Region::sub_region`]. Like any arena allocation, the slice is not
+/// given back to the parent when the region is dropped; it is reclaimed when the parent is reset.
+#[derive(Debug)]
+pub struct Region<'a> {
+    arena: ArenaAllocatorView<'a>,
+}
+
+impl<'a> Region<'a> {
+    /// Claims `size` bytes from `parent` and wraps them in a new region.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError` if `parent` cannot provide `size` bytes.
+    pub fn new(parent: &'a ArenaAllocator, size: usize) -> Result<Self, AllocError> {
+        Self::claim(parent, size)
+    }
+
+    /// Claims `size` bytes from this region and wraps them in a nested region.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError` if this region cannot provide `size` bytes.
+    pub fn sub_region(&self, size: usize) -> Result<Region<'_>, AllocError> {
+        Region::claim(&self.arena, size)
+    }
+
+    /// Allocates `size` bytes from `parent` and builds a region over them.
+    fn claim<A: Allocator>(parent: &'a A, size: usize) -> Result<Self, AllocError> {
+        let layout =
+            Layout::from_size_align(size, mem::align_of::<usize>()).map_err(|_| AllocError)?;
+        let block = parent.allocate(layout)?;
+        // SAFETY: `parent` handed the block out exclusively and a region never deallocates it.
+        // The `'a` borrow keeps `parent` alive and, since resetting an arena or a view takes
+        // `&mut self`, also keeps it from being reset, so the block stays valid and unaliased for
+        // `'a`.
+        let buffer = unsafe {
+            slice::from_raw_parts_mut(block.as_mut_ptr().cast::<MaybeUninit<u8>>(), size)
+        };
+        Ok(Self {
+            arena: ArenaAllocatorView::new(buffer),
+        })
+    }
+
+    /// Returns the size of the region in bytes.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.arena.capacity()
+    }
+
+    /// Returns the number of bytes consumed so far, including alignment padding.
+    #[must_use]
+    pub fn used(&self) -> usize {
+        self.arena.used()
+    }
+
+    /// Returns the number of bytes still available for allocation.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.arena.remaining()
+    }
+}
+
+unsafe impl Allocator for Region<'_> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.arena.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        self.arena.deallocate(allocated_ptr, layout);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests a `Region` with a generic vector.
+    fn test_generic_vector_with_region() {
+        let parent = ArenaAllocator::new(4096);
+        let region = Region::new(&parent, 1024).unwrap();
+        let mut vector: Vec<usize, &Region> = Vec::with_capacity_in(100, &region);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    /// Tests that regions carve disjoint slices out of their parent and are bounded by their
+    /// size.
+    fn test_regions_are_bounded() {
+        let parent = ArenaAllocator::with_alignment(256, 8);
+        let first = Region::new(&parent, 64).unwrap();
+        let second = Region::new(&parent, 64).unwrap();
+        assert_eq!(parent.used(), 128);
+
+        let layout = Layout::new::<[u8; 64]>();
+        let a = first.allocate(layout).unwrap();
+        let b = second.allocate(layout).unwrap();
+        assert_eq!(b.as_mut_ptr() as usize - a.as_mut_ptr() as usize, 64);
+        assert!(first.allocate(Layout::new::<u8>()).is_err());
+
+        assert!(Region::new(&parent, 256).is_err());
+        {
+            let _scratch = Region::new(&parent, 32).unwrap();
+        }
+        assert_eq!(parent.used(), 160);
+    }
+
+    #[test]
+    /// Tests nested regions carving up a parent arena.
+    fn test_nested_regions() {
+        let parent = ArenaAllocator::new(1024);
+        let outer = Region::new(&parent, 512).unwrap();
+        let left = outer.sub_region(128).unwrap();
+        let right = outer.sub_region(256).unwrap();
+        let innermost = right.sub_region(64).unwrap();
+
+        assert_eq!(outer.used(), 384);
+        assert_eq!(right.remaining(), 192);
+        assert!(outer.sub_region(256).is_err());
+
+        let layout = Layout::new::<u64>();
+        let value = innermost.allocate(layout).unwrap();
+        assert!(parent.contains(value.as_non_null_ptr()));
+        assert_eq!(left.capacity(), 128);
+        assert_eq!(innermost.used(), 8);
+    }
+}
@@ -0,0 +1,263 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Receives the operations performed through a [`ReportAllocator`].
+///
+/// Allocations and resizes are reported once the inner allocator has succeeded, deallocations
+/// before the block is returned to it; failed operations are not reported.
+pub trait AllocReporter {
+    /// Called after a block was allocated with `layout` at `ptr`.
+    fn on_alloc(&self, layout: Layout, ptr: NonNull<u8>);
+
+    /// Called before the block at `ptr`, allocated with `layout`, is deallocated.
+    fn on_dealloc(&self, ptr: NonNull<u8>, layout: Layout);
+
+    /// Called after a block was grown from `old` to `new`.
+    fn on_grow(&self, old: Layout, new: Layout);
+
+    /// Called after a block was shrunk from `old` to `new`.
+    fn on_shrink(&self, old: Layout, new: Layout);
+}
+
+/// A reporter that ignores every operation.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct NullReporter;
+
+impl AllocReporter for NullReporter {
+    fn on_alloc(&self, _: Layout, _: NonNull<u8>) {}
+
+    fn on_dealloc(&self, _: NonNull<u8>, _: Layout) {}
+
+    fn on_grow(&self, _: Layout, _: Layout) {}
+
+    fn on_shrink(&self, _: Layout, _: Layout) {}
+}
+
+/// A reporter that counts every kind of operation with atomic counters.
+#[derive(Debug, Default)]
+pub struct CountingReporter {
+    allocations: AtomicUsize,
+    deallocations: AtomicUsize,
+    grows: AtomicUsize,
+    shrinks: AtomicUsize,
+}
+
+impl CountingReporter {
+    /// Creates a reporter with every counter at zero.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            allocations: AtomicUsize::new(0),
+            deallocations: AtomicUsize::new(0),
+            grows: AtomicUsize::new(0),
+            shrinks: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the number of successful allocations.
+    #[must_use]
+    pub fn allocations(&self) -> usize {
+        self.allocations.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of deallocations.
+    #[must_use]
+    pub fn deallocations(&self) -> usize {
+        self.deallocations.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of successful grows.
+    #[must_use]
+    pub fn grows(&self) -> usize {
+        self.grows.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of successful shrinks.
+    #[must_use]
+    pub fn shrinks(&self) -> usize {
+        self.shrinks.load(Ordering::Relaxed)
+    }
+}
+
+impl AllocReporter for CountingReporter {
+    fn on_alloc(&self, _: Layout, _: NonNull<u8>) {
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn on_dealloc(&self, _: NonNull<u8>, _: Layout) {
+        self.deallocations.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn on_grow(&self, _: Layout, _: Layout) {
+        self.grows.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn on_shrink(&self, _: Layout, _: Layout) {
+        self.shrinks.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// A wrapper allocator that forwards every operation to `A` and reports it to `R`.
+///
+/// Unlike [`TracingAllocator`](crate::tracing_allocator::TracingAllocator), resizes are forwarded
+/// to the inner allocator's own `grow` and `shrink`, so they keep any in-place optimisation and
+/// are reported as such rather than as an allocation followed by a deallocation.
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use std::alloc::Global;
+///
+/// use mem_allocs::report_allocator::{CountingReporter, ReportAllocator};
+///
+/// let allocator = ReportAllocator::new(Global, CountingReporter::new());
+/// let mut vector = Vec::with_capacity_in(1, &allocator);
+/// vector.extend([1, 2, 3]);
+/// drop(vector);
+///
+/// assert_eq!(allocator.reporter().allocations(), 1);
+/// assert_eq!(allocator.reporter().grows(), 1);
+/// assert_eq!(allocator.reporter().deallocations(), 1);
+/// ```
+#[derive(Debug, Default)]
+#[allow(clippy::module_name_repetitions)]
+pub struct ReportAllocator<A: Allocator, R: AllocReporter> {
+    inner: A,
+    reporter: R,
+}
+
+impl<A: Allocator, R: AllocReporter> ReportAllocator<A, R> {
+    /// Wraps `inner` so that every operation is reported to `reporter`.
+    pub const fn new(inner: A, reporter: R) -> Self {
+        Self { inner, reporter }
+    }
+
+    /// Returns a reference to the wrapped allocator.
+    pub const fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Returns a reference to the reporter.
+    pub const fn reporter(&self) -> &R {
+        &self.reporter
+    }
+}
+
+unsafe impl<A: Allocator, R: AllocReporter> Allocator for ReportAllocator<A, R> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let allocated_ptr = self.inner.allocate(layout)?;
+        self.reporter
+            .on_alloc(layout, allocated_ptr.as_non_null_ptr());
+        Ok(allocated_ptr)
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let allocated_ptr = self.inner.allocate_zeroed(layout)?;
+        self.reporter
+            .on_alloc(layout, allocated_ptr.as_non_null_ptr());
+        Ok(allocated_ptr)
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        self.reporter.on_dealloc(allocated_ptr, layout);
+        self.inner.deallocate(allocated_ptr, layout);
+    }
+
+    unsafe fn grow(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let new_ptr = self.inner.grow(old_ptr, old_layout, new_layout)?;
+        self.reporter.on_grow(old_layout, new_layout);
+        Ok(new_ptr)
+    }
+
+    unsafe fn grow_zeroed(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let new_ptr = self.inner.grow_zeroed(old_ptr, old_layout, new_layout)?;
+        self.reporter.on_grow(old_layout, new_layout);
+        Ok(new_ptr)
+    }
+
+    unsafe fn shrink(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let new_ptr = self.inner.shrink(old_ptr, old_layout, new_layout)?;
+        self.reporter.on_shrink(old_layout, new_layout);
+        Ok(new_ptr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{alloc::Global, vec::Vec};
+
+    #[test]
+    /// Tests the `ReportAllocator` with a generic vector and a `NullReporter`.
+    fn test_generic_vector_with_report_allocator() {
+        let allocator = ReportAllocator::new(Global, NullReporter);
+        let mut vector: Vec<usize, ReportAllocator<Global, NullReporter>> =
+            Vec::with_capacity_in(100, allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    /// Tests that a `CountingReporter` sees every kind of operation exactly once.
+    fn test_counting_reporter() {
+        let allocator = ReportAllocator::new(Global, CountingReporter::new());
+        let old_layout = Layout::from_size_align(16, 8).unwrap();
+        let grown_layout = Layout::from_size_align(64, 8).unwrap();
+        let shrunk_layout = Layout::from_size_align(32, 8).unwrap();
+
+        unsafe {
+            let block = allocator.allocate(old_layout).unwrap().as_non_null_ptr();
+            let block = allocator
+                .grow(block, old_layout, grown_layout)
+                .unwrap()
+                .as_non_null_ptr();
+            let block = allocator
+                .shrink(block, grown_layout, shrunk_layout)
+                .unwrap()
+                .as_non_null_ptr();
+            allocator.deallocate(block, shrunk_layout);
+        }
+
+        let reporter = allocator.reporter();
+        assert_eq!(reporter.allocations(), 1);
+        assert_eq!(reporter.grows(), 1);
+        assert_eq!(reporter.shrinks(), 1);
+        assert_eq!(reporter.deallocations(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "noop_allocator")]
+    /// Tests that failed allocations are not reported.
+    fn test_failures_are_not_reported() {
+        use crate::noop_allocator::NoopAllocator;
+
+        let allocator = ReportAllocator::new(NoopAllocator, CountingReporter::new());
+        assert!(allocator.allocate(Layout::new::<u64>()).is_err());
+        assert_eq!(allocator.reporter().allocations(), 0);
+    }
+}
@@ -0,0 +1,188 @@
+use core::alloc::Allocator;
+
+use alloc::alloc::Global;
+
+use crate::arena_allocator::ArenaAllocator;
+
+/// The most segments a [`SegmentedArena`] can hold.
+pub const MAX_SEGMENTS: usize = 8;
+
+/// A set of named [`ArenaAllocator`]s for data with different lifetimes, such as per-level,
+/// per-frame and per-tick memory in a game loop.
+///
+/// Each segment is an independent arena, so resetting one never touches the others. The segment
+/// table is a fixed array; only the segments' buffers live on the heap.
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use mem_allocs::segmented_arena::SegmentedArena;
+///
+/// let mut arena = SegmentedArena::new(&[("level", 4096), ("frame", 1024)]);
+/// let frame = arena.segment("frame").unwrap();
+/// let scratch: Vec<u32, _> = Vec::with_capacity_in(16, frame);
+/// drop(scratch);
+///
+/// arena.reset_segment("frame");
+/// assert_eq!(arena.segment("frame").unwrap().used(), 0);
+/// ```
+#[derive(Debug)]
+pub struct SegmentedArena<A: Allocator = Global> {
+    segments: [Option<(&'static str, ArenaAllocator<A>)>; MAX_SEGMENTS],
+}
+
+impl SegmentedArena {
+    /// Creates one segment for every `(name, capacity)` pair.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than [`MAX_SEGMENTS`] segments are requested or a name appears twice.
+    #[must_use]
+    pub fn new(segments: &[(&'static str, usize)]) -> Self {
+        Self::new_with_allocator(segments, Global)
+    }
+}
+
+impl<A: Allocator> SegmentedArena<A> {
+    /// Creates one segment for every `(name, capacity)` pair, allocating each segment's buffer
+    /// from a clone of `alloc`.
+    ///
+    /// # Panics
+    ///
+    /// Panics like [`SegmentedArena::new`].
+    #[must_use]
+    pub fn new_with_allocator(segments: &[(&'static str, usize)], alloc: A) -> Self
+    where
+        A: Clone,
+    {
+        assert!(
+            segments.len() <= MAX_SEGMENTS,
+            "a SegmentedArena holds at most {MAX_SEGMENTS} segments, got {}",
+            segments.len()
+        );
+
+        let mut table = [const { None }; MAX_SEGMENTS];
+        for (index, &(name, capacity)) in segments.iter().enumerate() {
+            assert!(
+                segments[..index].iter().all(|&(other, _)| other != name),
+                "segment {name:?} is defined twice"
+            );
+            table[index] = Some((
+                name,
+                ArenaAllocator::new_with_allocator(capacity, alloc.clone()),
+            ));
+        }
+        Self { segments: table }
+    }
+
+    /// Returns the segment called `name`, or `None` if there is no such segment.
+    #[must_use]
+    pub fn segment(&self, name: &str) -> Option<&ArenaAllocator<A>> {
+        self.iter()
+            .find(|&(segment, _)| segment == name)
+            .map(|(_, arena)| arena)
+    }
+
+    /// Iterates over the names and arenas of all segments, in the order they were defined.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &ArenaAllocator<A>)> + '_ {
+        self.segments
+            .iter()
+            .map_while(|segment| segment.as_ref().map(|(name, arena)| (*name, arena)))
+    }
+
+    /// Resets the segment called `name`, leaving every other segment untouched. Unknown names are
+    /// ignored.
+    ///
+    /// Taking `&mut self` ensures nothing borrowing a segment outlives the reset. Raw pointers
+    /// handed out by that segment before the reset must no longer be used.
+    pub fn reset_segment(&mut self, name: &str) {
+        if let Some((_, arena)) = self.segments_mut().find(|(segment, _)| *segment == name) {
+            arena.reset();
+        }
+    }
+
+    /// Resets every segment.
+    ///
+    /// Raw pointers handed out by any segment before the reset must no longer be used.
+    pub fn reset_all(&mut self) {
+        for (_, arena) in self.segments_mut() {
+            arena.reset();
+        }
+    }
+
+    /// Iterates mutably over the defined segments.
+    fn segments_mut(&mut self) -> impl Iterator<Item = &mut (&'static str, ArenaAllocator<A>)> {
+        self.segments.iter_mut().map_while(Option::as_mut)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `SegmentedArena` with a generic vector in one of its segments.
+    fn test_generic_vector_with_segmented_arena() {
+        let arena = SegmentedArena::new(&[("frame", 1024)]);
+        let segment = arena.segment("frame").unwrap();
+        let mut vector: Vec<usize, &ArenaAllocator> = Vec::with_capacity_in(100, segment);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    /// Tests that three named segments are reset independently of each other.
+    fn test_independent_reset() {
+        let mut arena = SegmentedArena::new(&[("level", 256), ("frame", 128), ("tick", 64)]);
+        for (name, segment) in arena.iter() {
+            segment
+                .allocate_array::<u8>(segment.capacity() / 2)
+                .unwrap();
+            assert_eq!(arena.segment(name).unwrap().used(), segment.capacity() / 2);
+        }
+
+        arena.reset_segment("frame");
+        let used: Vec<usize> = arena.iter().map(|(_, segment)| segment.used()).collect();
+        assert_eq!(used, [128, 0, 32]);
+
+        arena.reset_segment("unknown");
+        arena.reset_all();
+        assert!(arena.iter().all(|(_, segment)| segment.used() == 0));
+    }
+
+    #[test]
+    /// Tests that looking up a missing segment returns `None`.
+    fn test_missing_segment() {
+        let arena = SegmentedArena::new(&[("level", 64)]);
+        assert!(arena.segment("frame").is_none());
+        assert_eq!(arena.iter().count(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "segment \"tick\" is defined twice")]
+    /// Tests that a duplicated segment name is rejected.
+    fn test_duplicate_name() {
+        let _ = SegmentedArena::new(&[("tick", 64), ("tick", 32)]);
+    }
+
+    #[test]
+    #[cfg(feature = "c_allocator")]
+    /// Tests that every segment gets its own buffer from the given allocator.
+    fn test_new_with_allocator() {
+        use crate::c_allocator::CAllocator;
+
+        let arena = SegmentedArena::new_with_allocator(&[("a", 64), ("b", 32)], CAllocator);
+        for (_, segment) in arena.iter() {
+            assert_eq!(segment.allocator(), &CAllocator);
+        }
+        assert_eq!(arena.segment("b").unwrap().capacity(), 32);
+    }
+}
@@ -0,0 +1,139 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    ptr::NonNull,
+};
+
+use crate::{
+    c_allocator::CAllocator,
+    pool_allocator::PoolAllocator,
+    size_classes::{self, size_class_for},
+};
+
+/// Block sizes of the pools, in bytes: the shared size classes up to 512 bytes.
+const CLASSES: &[usize] = size_classes::CLASSES.split_at(7).0;
+
+/// A size-class segregated allocator in the style of tcmalloc and jemalloc.
+///
+/// Small requests are served by one [`PoolAllocator`] per size class, picking the smallest class
+/// that holds the request; every class is a power of two, so its blocks are aligned to their
+/// size. Requests above the largest class, and requests whose pool is exhausted, go to a
+/// [`CAllocator`].
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct SegregatedAllocator {
+    pools: [PoolAllocator; CLASSES.len()],
+}
+
+impl SegregatedAllocator {
+    /// Creates a new allocator with `blocks_per_class` blocks in every size class.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `blocks_per_class` is zero or the pool buffers overflow.
+    #[must_use]
+    pub fn new(blocks_per_class: usize) -> Self {
+        Self {
+            pools: core::array::from_fn(|index| {
+                PoolAllocator::new(CLASSES[index], blocks_per_class)
+            }),
+        }
+    }
+
+    /// Returns the block sizes of the size classes, in ascending order.
+    #[must_use]
+    pub const fn classes() -> &'static [usize] {
+        CLASSES
+    }
+
+    /// Returns the pool serving `layout`, or `None` if it is larger than every class.
+    fn pool(&self, layout: Layout) -> Option<&PoolAllocator> {
+        self.pools.get(size_class_for(layout)?)
+    }
+}
+
+unsafe impl Allocator for SegregatedAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.pool(layout)
+            .ok_or(AllocError)
+            .and_then(|pool| pool.allocate(layout))
+            .or_else(|_| CAllocator.allocate(layout))
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        match self.pool(layout) {
+            Some(pool) if pool.contains(allocated_ptr) => pool.deallocate(allocated_ptr, layout),
+            _ => CAllocator.deallocate(allocated_ptr, layout),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `SegregatedAllocator` with a generic vector that moves through the classes and
+    /// into the fallback.
+    fn test_generic_vector_with_segregated_allocator() {
+        let allocator = SegregatedAllocator::new(4);
+        let mut vector: Vec<usize, &SegregatedAllocator> = Vec::new_in(&allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+        assert!(allocator.pools.iter().all(|pool| pool.used_blocks() == 0));
+    }
+
+    #[test]
+    /// Tests that requests go to the smallest class that holds both their size and alignment.
+    fn test_routing() {
+        let allocator = SegregatedAllocator::new(4);
+        let cases = [
+            (1, 1, 0),
+            (8, 8, 0),
+            (9, 1, 1),
+            (24, 8, 2),
+            (8, 64, 3),
+            (512, 8, 6),
+        ];
+
+        for (size, align, class) in cases {
+            let layout = Layout::from_size_align(size, align).unwrap();
+            let block = allocator.allocate(layout).unwrap();
+            assert!(
+                allocator.pools[class].contains(block.as_non_null_ptr()),
+                "size {size}, align {align}"
+            );
+            assert_eq!(block.as_mut_ptr() as usize % align, 0);
+            unsafe { allocator.deallocate(block.as_non_null_ptr(), layout) };
+        }
+        assert_eq!(SegregatedAllocator::classes()[6], 512);
+    }
+
+    #[test]
+    /// Tests that large requests and requests to an exhausted class use the fallback.
+    fn test_fallback() {
+        let allocator = SegregatedAllocator::new(1);
+        let large = Layout::from_size_align(4096, 8).unwrap();
+        let small = Layout::from_size_align(16, 8).unwrap();
+
+        let big = allocator.allocate(large).unwrap();
+        let first = allocator.allocate(small).unwrap();
+        let overflow = allocator.allocate(small).unwrap();
+        assert!(allocator.pools[1].contains(first.as_non_null_ptr()));
+        assert!(!allocator.pools[1].contains(overflow.as_non_null_ptr()));
+
+        unsafe {
+            allocator.deallocate(big.as_non_null_ptr(), large);
+            allocator.deallocate(overflow.as_non_null_ptr(), small);
+            allocator.deallocate(first.as_non_null_ptr(), small);
+        }
+        assert_eq!(allocator.pools[1].used_blocks(), 0);
+    }
+}
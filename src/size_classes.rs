@@ -0,0 +1,71 @@
+//! Power-of-two size classes shared by the allocators that serve small requests from per-class
+//! pools, such as [`SegregatedAllocator`](crate::segregated_allocator::SegregatedAllocator).
+
+use core::alloc::Layout;
+
+/// Block sizes of the size classes, in ascending order.
+///
+/// Every class is a power of two, so a block placed at a multiple of its class size is aligned to
+/// that size as well.
+pub const CLASSES: &[usize] = &[8, 16, 32, 64, 128, 256, 512, 1024, 2048, 4096];
+
+/// Returns the index in [`CLASSES`] of the smallest class that can hold `layout`, or `None` if the
+/// layout is larger than every class.
+///
+/// The size is rounded up to the alignment, and the class is never smaller than the alignment, so
+/// a zero-sized layout with a large alignment still lands in a class that satisfies it.
+#[must_use]
+pub fn size_class_for(layout: Layout) -> Option<usize> {
+    let needed = layout.pad_to_align().size().max(layout.align());
+    CLASSES.iter().position(|&class| class >= needed)
+}
+
+/// Returns the index of `class` in [`CLASSES`], or `None` if it is not a size class.
+#[must_use]
+pub fn class_index(class: usize) -> Option<usize> {
+    CLASSES.binary_search(&class).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Tests `size_class_for` against a table of sizes and alignments.
+    fn test_size_class_for() {
+        const CASES: [(usize, usize, Option<usize>); 12] = [
+            (0, 1, Some(0)),
+            (1, 1, Some(0)),
+            (8, 8, Some(0)),
+            (9, 1, Some(1)),
+            (9, 8, Some(1)),
+            (17, 16, Some(2)),
+            (1, 64, Some(3)),
+            (0, 256, Some(5)),
+            (100, 128, Some(4)),
+            (4096, 8, Some(9)),
+            (4097, 1, None),
+            (8, 8192, None),
+        ];
+
+        for (size, align, expected) in CASES {
+            let layout = Layout::from_size_align(size, align).unwrap();
+            assert_eq!(
+                size_class_for(layout),
+                expected,
+                "size {size}, align {align}"
+            );
+        }
+    }
+
+    #[test]
+    /// Tests `class_index` for every class and for sizes between and beyond them.
+    fn test_class_index() {
+        for (index, &class) in CLASSES.iter().enumerate() {
+            assert_eq!(class_index(class), Some(index));
+        }
+        for size in [0, 1, 7, 9, 24, 1000, 8192] {
+            assert_eq!(class_index(size), None, "size {size}");
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use core::{
+    alloc::AllocError,
+    cell::Cell,
+    mem::{ManuallyDrop, MaybeUninit},
+    ptr::NonNull,
+};
+
+use alloc::vec::Vec;
+
+/// A slot of a [`SlabAllocator`], holding either a value or a link to the next free slot.
+union Slot<T> {
+    _value: ManuallyDrop<MaybeUninit<T>>,
+    next: Option<NonNull<Slot<T>>>,
+}
+
+/// A typed allocator that recycles a fixed number of slots for values of type `T`.
+///
+/// Free slots form an intrusive linked list, so handing a slot out and taking it back are both
+/// O(1). The allocator never runs `T`'s destructor; dropping values is up to the caller.
+#[allow(clippy::module_name_repetitions)]
+pub struct SlabAllocator<T> {
+    // Only kept to own the memory; all accesses go through the free list.
+    _slots: Vec<Slot<T>>,
+    free_head: Cell<Option<NonNull<Slot<T>>>>,
+    capacity: usize,
+    available: Cell<usize>,
+}
+
+impl<T> SlabAllocator<T> {
+    /// Creates a new slab allocator with room for `count` values of type `T`.
+    #[must_use]
+    pub fn new(count: usize) -> Self {
+        let mut slots: Vec<Slot<T>> = (0..count).map(|_| Slot { next: None }).collect();
+        let base = slots.as_mut_ptr();
+        for index in 1..count {
+            unsafe { (*base.add(index - 1)).next = Some(NonNull::new_unchecked(base.add(index))) };
+        }
+
+        Self {
+            free_head: Cell::new((count > 0).then(|| unsafe { NonNull::new_unchecked(base) })),
+            _slots: slots,
+            capacity: count,
+            available: Cell::new(count),
+        }
+    }
+
+    /// Returns the total number of slots.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of slots that are currently free.
+    #[must_use]
+    pub const fn available(&self) -> usize {
+        self.available.get()
+    }
+
+    /// Hands out an uninitialized slot large enough and aligned for a `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError` if every slot is in use.
+    pub fn allocate_one(&self) -> Result<NonNull<T>, AllocError> {
+        let slot = self.free_head.get().ok_or(AllocError)?;
+        self.free_head.set(unsafe { slot.as_ref().next });
+        self.available.set(self.available.get() - 1);
+        Ok(slot.cast::<T>())
+    }
+
+    /// Returns a slot to the allocator.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by [`SlabAllocator::allocate_one`] on this allocator and must
+    /// not have been returned already. The value in the slot is not dropped; if `T` needs to be
+    /// dropped, the caller must do so (for example with `ptr::drop_in_place`) before calling this.
+    pub unsafe fn deallocate_one(&self, ptr: NonNull<T>) {
+        let slot = ptr.cast::<Slot<T>>();
+        slot.write(Slot {
+            next: self.free_head.get(),
+        });
+        self.free_head.set(Some(slot));
+        self.available.set(self.available.get() + 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::{
+        ptr,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    #[test]
+    /// Tests that slots are handed out until exhausted and recycled once returned.
+    fn test_allocate_and_recycle() {
+        let slab = SlabAllocator::<u64>::new(2);
+
+        let first = slab.allocate_one().unwrap();
+        let second = slab.allocate_one().unwrap();
+        assert_ne!(first, second);
+        assert!(slab.allocate_one().is_err());
+        assert_eq!(slab.available(), 0);
+
+        unsafe { slab.deallocate_one(first) };
+        assert_eq!(slab.available(), 1);
+        assert_eq!(slab.allocate_one().unwrap(), first);
+    }
+
+    #[test]
+    /// Tests that slots are aligned for over-aligned types.
+    fn test_alignment() {
+        #[repr(align(64))]
+        struct Aligned(#[allow(dead_code)] u8);
+
+        let slab = SlabAllocator::<Aligned>::new(4);
+        for _ in 0..4 {
+            let slot = slab.allocate_one().unwrap();
+            assert_eq!(slot.as_ptr() as usize % 64, 0);
+        }
+    }
+
+    #[test]
+    /// Tests that the allocator never drops values itself, so dropping them manually happens once.
+    fn test_no_double_drop() {
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        struct Tracked(u32);
+
+        impl Drop for Tracked {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        {
+            let slab = SlabAllocator::<Tracked>::new(4);
+            let slot = slab.allocate_one().unwrap();
+            unsafe {
+                slot.as_ptr().write(Tracked(42));
+                assert_eq!((*slot.as_ptr()).0, 42);
+                ptr::drop_in_place(slot.as_ptr());
+                slab.deallocate_one(slot);
+            }
+
+            let leaked = slab.allocate_one().unwrap();
+            unsafe { leaked.as_ptr().write(Tracked(7)) };
+        }
+
+        assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+    }
+}
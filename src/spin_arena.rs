@@ -0,0 +1,148 @@
+use core::{
+    alloc::{AllocError, Allocator, GlobalAlloc, Layout},
+    ptr::{self, NonNull},
+};
+
+use spin::Mutex;
+
+use crate::arena_allocator::ArenaAllocator;
+
+/// A thread-safe bump-pointer memory allocator that guards an [`ArenaAllocator`] with a spinlock.
+///
+/// This suits `no_std` targets that have no operating system to block threads but can spin, such
+/// as multi-core embedded systems. Every operation holds the lock only for the few instructions
+/// the arena needs to bump its offset.
+#[derive(Debug, Default)]
+pub struct SpinArenaAllocator {
+    arena: Mutex<ArenaAllocator>,
+}
+
+// SAFETY: the arena is only ever accessed while the spinlock is held, so no two threads touch its
+// offset at the same time, and every allocation hands out a region no other thread received.
+unsafe impl Send for SpinArenaAllocator {}
+unsafe impl Sync for SpinArenaAllocator {}
+
+impl SpinArenaAllocator {
+    /// Creates a new arena with a backing buffer of `bytes` bytes.
+    #[must_use]
+    pub fn new(bytes: usize) -> Self {
+        Self {
+            arena: Mutex::new(ArenaAllocator::new(bytes)),
+        }
+    }
+
+    /// Returns the total size of the backing buffer in bytes.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.arena.lock().capacity()
+    }
+
+    /// Returns the number of bytes consumed so far, including alignment padding.
+    #[must_use]
+    pub fn used(&self) -> usize {
+        self.arena.lock().used()
+    }
+
+    /// Returns the number of bytes still available for allocation.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.arena.lock().remaining()
+    }
+
+    /// Resets the arena, making its whole buffer available again.
+    ///
+    /// # Safety
+    ///
+    /// The arena is shared between threads, so the borrow checker cannot tell whether its blocks
+    /// are still in use. The caller must ensure no block handed out before the reset, on any
+    /// thread, is used afterwards; that includes the buffers of every live collection allocating
+    /// from the arena.
+    pub unsafe fn reset(&self) {
+        self.arena.lock().reset();
+    }
+}
+
+unsafe impl Allocator for SpinArenaAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.arena.lock().allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        self.arena.lock().deallocate(allocated_ptr, layout);
+    }
+}
+
+unsafe impl GlobalAlloc for SpinArenaAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.allocate(layout)
+            .map_or(ptr::null_mut(), NonNull::as_mut_ptr)
+    }
+
+    unsafe fn dealloc(&self, allocated_ptr: *mut u8, layout: Layout) {
+        if let Some(allocated_ptr) = NonNull::new(allocated_ptr) {
+            self.deallocate(allocated_ptr, layout);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `SpinArenaAllocator` with a generic vector.
+    fn test_generic_vector_with_spin_arena() {
+        let allocator = SpinArenaAllocator::new(1024);
+        let mut vector: Vec<usize, &SpinArenaAllocator> = Vec::with_capacity_in(100, &allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    /// Tests that allocations made concurrently from two threads never overlap.
+    fn test_concurrent_allocation() {
+        const ALLOCATIONS: usize = 128;
+
+        let allocator = SpinArenaAllocator::new(2 * ALLOCATIONS * 16);
+        let layout = Layout::from_size_align(16, 1).unwrap();
+
+        let mut addresses: Vec<usize> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..2_u8)
+                .map(|thread_index| {
+                    let allocator = &allocator;
+                    scope.spawn(move || {
+                        (0..ALLOCATIONS)
+                            .map(|_| {
+                                let block = allocator.allocate(layout).unwrap();
+                                unsafe {
+                                    ptr::write_bytes(block.as_mut_ptr(), thread_index, 16);
+                                }
+                                block.as_mut_ptr() as usize
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        });
+
+        assert_eq!(allocator.remaining(), 0);
+        addresses.sort_unstable();
+        for pair in addresses.windows(2) {
+            assert!(pair[1] - pair[0] >= layout.size());
+        }
+    }
+}
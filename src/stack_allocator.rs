@@ -0,0 +1,213 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    cell::UnsafeCell,
+    mem::{self, MaybeUninit},
+    ptr::{self, NonNull},
+};
+
+use alloc::vec::Vec;
+
+use crate::align::align_up;
+
+/// Size of the header stored in front of every allocation, holding the offset before it was made.
+const HEADER_SIZE: usize = mem::size_of::<usize>();
+
+/// A bump-pointer memory allocator that reclaims space when blocks are freed in LIFO order.
+///
+/// Each allocation is preceded by a small header recording the previous offset, so freeing the
+/// topmost block also gives back the alignment padding in front of it. Freeing any other block is
+/// ignored, just like in an [`ArenaAllocator`](crate::arena_allocator::ArenaAllocator).
+#[derive(Debug, Default)]
+#[allow(clippy::module_name_repetitions)]
+pub struct StackAllocator {
+    buffer: UnsafeCell<Vec<MaybeUninit<u8>>>,
+    offset: UnsafeCell<usize>,
+}
+
+impl StackAllocator {
+    /// Creates a new stack allocator with a backing buffer of `bytes` bytes.
+    #[must_use]
+    pub fn new(bytes: usize) -> Self {
+        Self {
+            buffer: UnsafeCell::new(alloc::vec![MaybeUninit::uninit(); bytes]),
+            offset: UnsafeCell::new(0),
+        }
+    }
+
+    /// Returns the total size of the backing buffer in bytes.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        unsafe { (*self.buffer.get()).len() }
+    }
+
+    /// Returns the number of bytes consumed so far, including headers and alignment padding.
+    #[must_use]
+    pub fn used(&self) -> usize {
+        unsafe { *self.offset.get() }
+    }
+
+    /// Returns the number of bytes still available for allocation.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.capacity() - self.used()
+    }
+
+    /// Resets the allocator, making its whole buffer available again.
+    ///
+    /// Taking `&mut self` guarantees that no collection still borrows the allocator, so no pointer
+    /// handed out before the reset can be used afterwards.
+    pub const fn reset(&mut self) {
+        *self.offset.get_mut() = 0;
+    }
+
+    /// Returns a pointer to the start of the backing buffer.
+    fn base_ptr(&self) -> *mut u8 {
+        unsafe { (*self.buffer.get()).as_mut_ptr().cast::<u8>() }
+    }
+
+    /// Returns `true` if the block at `allocated_ptr` of `size` bytes is the topmost allocation.
+    fn is_top(&self, allocated_ptr: NonNull<u8>, size: usize) -> bool {
+        allocated_ptr.as_ptr() as usize + size == self.base_ptr() as usize + self.used()
+    }
+}
+
+unsafe impl Allocator for StackAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let base = self.base_ptr();
+        let offset = self.used();
+        let header_end = (base as usize + offset)
+            .checked_add(HEADER_SIZE)
+            .ok_or(AllocError)?;
+        let start = align_up(header_end, layout.align()).ok_or(AllocError)? - base as usize;
+        let end = start.checked_add(layout.size()).ok_or(AllocError)?;
+
+        if end > self.capacity() {
+            return Err(AllocError);
+        }
+
+        unsafe {
+            let allocated_ptr = base.add(start);
+            allocated_ptr
+                .sub(HEADER_SIZE)
+                .cast::<usize>()
+                .write_unaligned(offset);
+            *self.offset.get() = end;
+
+            NonNull::new(ptr::slice_from_raw_parts_mut(allocated_ptr, layout.size()))
+                .ok_or(AllocError)
+        }
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        if self.is_top(allocated_ptr, layout.size()) {
+            let previous_offset = allocated_ptr
+                .as_ptr()
+                .sub(HEADER_SIZE)
+                .cast::<usize>()
+                .read_unaligned();
+            *self.offset.get() = previous_offset;
+        }
+    }
+
+    unsafe fn shrink(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let fits_in_place = (old_ptr.as_ptr() as usize).is_multiple_of(new_layout.align());
+        if fits_in_place && self.is_top(old_ptr, old_layout.size()) {
+            *self.offset.get() -= old_layout.size() - new_layout.size();
+            return Ok(NonNull::slice_from_raw_parts(old_ptr, new_layout.size()));
+        }
+
+        let new_ptr = self.allocate(new_layout)?;
+        ptr::copy_nonoverlapping(old_ptr.as_ptr(), new_ptr.as_mut_ptr(), new_layout.size());
+        self.deallocate(old_ptr, old_layout);
+        Ok(new_ptr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `StackAllocator` with a generic vector.
+    fn test_generic_vector_with_stack_allocator() {
+        let allocator = StackAllocator::new(1024);
+        let mut vector: Vec<usize, &StackAllocator> = Vec::with_capacity_in(100, &allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+        assert_eq!(allocator.used(), 0);
+    }
+
+    #[test]
+    /// Tests that freeing blocks in strict LIFO order reclaims all space, including padding.
+    fn test_lifo_deallocation_reclaims_everything() {
+        let allocator = StackAllocator::new(256);
+        let layouts = [
+            Layout::from_size_align(1, 1).unwrap(),
+            Layout::from_size_align(8, 32).unwrap(),
+            Layout::from_size_align(3, 2).unwrap(),
+        ];
+
+        let blocks: Vec<NonNull<[u8]>> = layouts
+            .iter()
+            .map(|&layout| allocator.allocate(layout).unwrap())
+            .collect();
+        assert!(allocator.used() > 0);
+
+        for (block, &layout) in blocks.iter().zip(&layouts).rev() {
+            unsafe { allocator.deallocate(block.as_non_null_ptr(), layout) };
+        }
+        assert_eq!(allocator.used(), 0);
+    }
+
+    #[test]
+    /// Tests that out-of-order frees are ignored without corrupting live allocations.
+    fn test_out_of_order_deallocation_is_ignored() {
+        let allocator = StackAllocator::new(256);
+        let layout = Layout::from_size_align(16, 8).unwrap();
+
+        let first = allocator.allocate(layout).unwrap();
+        let second = allocator.allocate(layout).unwrap();
+        unsafe { ptr::write_bytes(second.as_mut_ptr(), 0x5A, layout.size()) };
+        let used = allocator.used();
+
+        unsafe { allocator.deallocate(first.as_non_null_ptr(), layout) };
+        assert_eq!(allocator.used(), used);
+
+        let third = allocator.allocate(layout).unwrap();
+        assert!(third.as_mut_ptr() as usize >= second.as_mut_ptr() as usize + layout.size());
+        let bytes = unsafe { core::slice::from_raw_parts(second.as_mut_ptr(), layout.size()) };
+        assert!(bytes.iter().all(|&byte| byte == 0x5A));
+    }
+
+    #[test]
+    /// Tests that shrinking the topmost block walks the offset back.
+    fn test_shrink_topmost_in_place() {
+        let allocator = StackAllocator::new(256);
+        let old_layout = Layout::from_size_align(64, 8).unwrap();
+        let new_layout = Layout::from_size_align(16, 8).unwrap();
+
+        let block = allocator.allocate(old_layout).unwrap();
+        let used = allocator.used();
+        let shrunk = unsafe {
+            allocator
+                .shrink(block.as_non_null_ptr(), old_layout, new_layout)
+                .unwrap()
+        };
+
+        assert_eq!(shrunk.as_mut_ptr(), block.as_mut_ptr());
+        assert_eq!(allocator.used(), used - 48);
+    }
+}
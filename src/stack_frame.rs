@@ -0,0 +1,177 @@
+//! A bump-pointer allocator over a buffer on the caller's stack.
+//!
+//! [`StackAllocatorFrame`] gives `alloca`-like behaviour without any platform support: the caller
+//! declares a local array and the allocator borrows it for `'frame`. Everything allocated from the
+//! frame is released when that array goes out of scope, and the borrow checker rejects any
+//! collection that tries to outlive it.
+//!
+//! ```compile_fail
+//! #![feature(allocator_api)]
+//!
+//! use core::mem::MaybeUninit;
+//!
+//! use mem_allocs::stack_frame::StackAllocatorFrame;
+//!
+//! fn escape() -> Vec<u32, &'static StackAllocatorFrame<'static>> {
+//!     let mut buffer = [MaybeUninit::uninit(); 256];
+//!     let frame = StackAllocatorFrame::new(&mut buffer);
+//!     Vec::new_in(&frame)
+//! }
+//! ```
+
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    cell::Cell,
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ptr::{self, NonNull},
+};
+
+/// A bump-pointer memory allocator that hands out memory from a buffer borrowed from the calling
+/// stack frame.
+///
+/// Individual deallocations are ignored; the memory is reclaimed when the buffer goes out of
+/// scope, or earlier with [`StackAllocatorFrame::reset`].
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use core::mem::MaybeUninit;
+///
+/// use mem_allocs::stack_frame::StackAllocatorFrame;
+///
+/// fn sum_of_squares(values: &[u64]) -> u64 {
+///     let mut buffer = [MaybeUninit::uninit(); 4096];
+///     let frame = StackAllocatorFrame::new(&mut buffer);
+///
+///     let mut squares = Vec::with_capacity_in(values.len(), &frame);
+///     squares.extend(values.iter().map(|value| value * value));
+///     squares.iter().sum()
+/// }
+///
+/// assert_eq!(sum_of_squares(&[1, 2, 3]), 14);
+/// ```
+#[derive(Debug)]
+pub struct StackAllocatorFrame<'frame> {
+    buffer: NonNull<MaybeUninit<u8>>,
+    capacity: usize,
+    offset: Cell<usize>,
+    _marker: PhantomData<&'frame mut [MaybeUninit<u8>]>,
+}
+
+impl<'frame> StackAllocatorFrame<'frame> {
+    /// Creates a frame allocating from `buf`, typically a local array of the calling function.
+    #[must_use]
+    pub const fn new(buf: &'frame mut [MaybeUninit<u8>]) -> Self {
+        Self {
+            capacity: buf.len(),
+            buffer: NonNull::from_mut(buf).cast(),
+            offset: Cell::new(0),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the size of the borrowed buffer in bytes.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of bytes consumed so far, including alignment padding.
+    #[must_use]
+    pub const fn used(&self) -> usize {
+        self.offset.get()
+    }
+
+    /// Returns the number of bytes still available for allocation.
+    #[must_use]
+    pub const fn remaining(&self) -> usize {
+        self.capacity - self.used()
+    }
+
+    /// Makes the whole buffer available again.
+    ///
+    /// Taking `&mut self` guarantees that no collection still borrows the frame, so no pointer
+    /// handed out before the reset can be used afterwards.
+    pub const fn reset(&mut self) {
+        *self.offset.get_mut() = 0;
+    }
+}
+
+unsafe impl Allocator for StackAllocatorFrame<'_> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let base = self.buffer.as_ptr() as usize;
+        let cursor = base.checked_add(self.used()).ok_or(AllocError)?;
+        let start = cursor
+            .checked_next_multiple_of(layout.align())
+            .ok_or(AllocError)?
+            - base;
+        let end = start.checked_add(layout.size()).ok_or(AllocError)?;
+        if end > self.capacity {
+            return Err(AllocError);
+        }
+
+        self.offset.set(end);
+        let allocated_ptr = unsafe { self.buffer.as_ptr().add(start).cast::<u8>() };
+
+        NonNull::new(ptr::slice_from_raw_parts_mut(allocated_ptr, layout.size())).ok_or(AllocError)
+    }
+
+    unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `StackAllocatorFrame` with a generic vector.
+    fn test_generic_vector_with_stack_frame() {
+        let mut buffer = [MaybeUninit::uninit(); 4096];
+        let frame = StackAllocatorFrame::new(&mut buffer);
+        let mut vector: Vec<usize, &StackAllocatorFrame<'_>> = Vec::with_capacity_in(100, &frame);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    /// Tests that allocations stay inside the borrowed buffer and fail once it is exhausted.
+    fn test_out_of_memory_and_reset() {
+        let mut buffer = [MaybeUninit::uninit(); 64];
+        let start = buffer.as_mut_ptr().cast::<u8>();
+        let mut frame = StackAllocatorFrame::new(&mut buffer);
+        let layout = Layout::from_size_align(32, 1).unwrap();
+
+        assert_eq!(frame.allocate(layout).unwrap().as_mut_ptr(), start);
+        assert!(frame.allocate(layout).is_ok());
+        assert!(frame.allocate(layout).is_err());
+        assert_eq!(frame.remaining(), 0);
+
+        frame.reset();
+        assert_eq!(frame.used(), 0);
+        assert_eq!(frame.remaining(), 64);
+    }
+
+    #[test]
+    /// Tests that allocations respect the requested alignment.
+    fn test_alignment() {
+        let mut buffer = [MaybeUninit::uninit(); 256];
+        let frame = StackAllocatorFrame::new(&mut buffer);
+        frame.allocate(Layout::new::<u8>()).unwrap();
+
+        for align in [2, 8, 16, 64] {
+            let layout = Layout::from_size_align(1, align).unwrap();
+            let block = frame.allocate(layout).unwrap();
+            assert!((block.as_mut_ptr() as usize).is_multiple_of(align));
+        }
+        assert!(frame.used() <= frame.capacity());
+    }
+}
@@ -0,0 +1,203 @@
+use core::{
+    alloc::{AllocError, Allocator, GlobalAlloc, Layout},
+    cell::UnsafeCell,
+    mem::MaybeUninit,
+    ptr::{self, NonNull},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::align::align_up;
+
+/// A thread-safe bump-pointer arena with an inline `N`-byte buffer that can be built in a `const`
+/// context.
+///
+/// [`ArenaAllocator::new`](crate::arena_allocator::ArenaAllocator::new) has to allocate its
+/// buffer on the heap, which cannot happen in a `const fn`, so an `ArenaAllocator` cannot
+/// initialise a `static`. `StaticArena` stores its buffer inline instead and claims regions with
+/// an atomic offset like [`SyncArenaAllocator`](crate::sync_arena::SyncArenaAllocator), which
+/// makes it `Sync` and therefore usable as a `static`, including as the global allocator:
+///
+/// ```
+/// use core::alloc::{GlobalAlloc, Layout};
+///
+/// use mem_allocs::static_arena::StaticArena;
+///
+/// static GLOBAL_ARENA: StaticArena<65536> = StaticArena::new();
+///
+/// let value = unsafe { GLOBAL_ARENA.alloc(Layout::new::<u64>()) };
+/// assert!(!value.is_null());
+/// assert_eq!(GLOBAL_ARENA.used(), 8);
+/// ```
+///
+/// Like [`FixedCapacityArena`](crate::fixed_capacity_arena::FixedCapacityArena), every pointer
+/// points into the struct itself, so [`Allocator`] is only implemented for `&StaticArena<N>` and
+/// an arena that is not a `static` stays borrowed, and in place, while collections use it.
+#[derive(Debug)]
+pub struct StaticArena<const N: usize> {
+    buffer: UnsafeCell<[MaybeUninit<u8>; N]>,
+    offset: AtomicUsize,
+}
+
+// SAFETY: the offset is only updated atomically, and each successful `compare_exchange` hands the
+// claimed region to exactly one thread, so no two threads ever write to the same bytes.
+unsafe impl<const N: usize> Sync for StaticArena<N> {}
+
+impl<const N: usize> StaticArena<N> {
+    /// Creates a new, empty arena.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            buffer: UnsafeCell::new([MaybeUninit::uninit(); N]),
+            offset: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the total size of the backing buffer in bytes.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of bytes consumed so far, including alignment padding.
+    #[must_use]
+    pub fn used(&self) -> usize {
+        self.offset.load(Ordering::SeqCst)
+    }
+
+    /// Returns the number of bytes still available for allocation.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        N - self.used()
+    }
+
+    /// Resets the arena, making its whole buffer available again.
+    ///
+    /// Taking `&mut self` guarantees no other thread is allocating at the same time. Pointers
+    /// handed out before the reset must no longer be used.
+    pub fn reset(&mut self) {
+        *self.offset.get_mut() = 0;
+    }
+
+    /// Claims a block for `layout` by advancing the offset atomically.
+    fn bump(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let base = self.buffer.get().cast::<u8>();
+        let mut offset = self.offset.load(Ordering::SeqCst);
+
+        loop {
+            let start =
+                align_up(base as usize + offset, layout.align()).ok_or(AllocError)? - base as usize;
+            let end = start.checked_add(layout.size()).ok_or(AllocError)?;
+            if end > N {
+                return Err(AllocError);
+            }
+
+            match self
+                .offset
+                .compare_exchange_weak(offset, end, Ordering::SeqCst, Ordering::SeqCst)
+            {
+                Ok(_) => {
+                    let allocated_ptr = unsafe { base.add(start) };
+                    return NonNull::new(ptr::slice_from_raw_parts_mut(
+                        allocated_ptr,
+                        layout.size(),
+                    ))
+                    .ok_or(AllocError);
+                }
+                Err(current) => offset = current,
+            }
+        }
+    }
+}
+
+impl<const N: usize> Default for StaticArena<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// SAFETY: the returned pointers point into the inline buffer, and the allocator borrows the arena,
+// so the arena cannot move while a collection that allocated from it is alive.
+unsafe impl<const N: usize> Allocator for &StaticArena<N> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.bump(layout)
+    }
+
+    unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {}
+}
+
+// SAFETY: a global allocator lives in a `static`, which never moves.
+unsafe impl<const N: usize> GlobalAlloc for StaticArena<N> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.bump(layout)
+            .map_or(ptr::null_mut(), NonNull::as_mut_ptr)
+    }
+
+    unsafe fn dealloc(&self, _: *mut u8, _: Layout) {}
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use alloc::vec::Vec;
+
+    static ARENA: StaticArena<4096> = StaticArena::new();
+
+    #[test]
+    /// Tests the `StaticArena` with a generic vector, allocating from a `static`.
+    fn test_generic_vector_with_static_arena() {
+        let mut vector: Vec<usize, &StaticArena<4096>> = Vec::with_capacity_in(100, &ARENA);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    /// Tests that allocations made concurrently from several threads never overlap.
+    fn test_concurrent_allocation() {
+        const THREADS: usize = 4;
+        const ALLOCATIONS: usize = 32;
+
+        let arena = StaticArena::<{ THREADS * ALLOCATIONS * 16 }>::new();
+        let layout = Layout::from_size_align(16, 8).unwrap();
+
+        let mut addresses: Vec<usize> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..THREADS)
+                .map(|_| {
+                    scope.spawn(|| {
+                        (0..ALLOCATIONS)
+                            .map(|_| (&arena).allocate(layout).unwrap().as_mut_ptr() as usize)
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        });
+
+        addresses.sort_unstable();
+        assert!(addresses.windows(2).all(|pair| pair[1] - pair[0] >= 16));
+        assert!((&arena).allocate(layout).is_err());
+    }
+
+    #[test]
+    /// Tests that resetting makes the whole buffer available again.
+    fn test_reset() {
+        let mut arena = StaticArena::<64>::new();
+        let layout = Layout::from_size_align(64, 1).unwrap();
+
+        (&arena).allocate(layout).unwrap();
+        assert_eq!(arena.remaining(), 0);
+        arena.reset();
+        assert_eq!(arena.remaining(), 64);
+    }
+}
@@ -0,0 +1,196 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// A snapshot of the counters tracked by a [`StatsAllocator`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Total number of bytes ever allocated, including bytes added by `grow`.
+    pub total_allocated: usize,
+    /// Highest number of bytes in use at any one time.
+    pub peak_in_use: usize,
+    /// Number of successful allocations.
+    pub allocations: usize,
+    /// Number of deallocations.
+    pub deallocations: usize,
+}
+
+/// A wrapper allocator that records allocation metrics while delegating to an inner allocator.
+///
+/// All counters are atomics, so the wrapper is `Sync` whenever the inner allocator is.
+#[derive(Debug, Default)]
+#[allow(clippy::module_name_repetitions)]
+pub struct StatsAllocator<A: Allocator> {
+    inner: A,
+    total_allocated: AtomicUsize,
+    in_use: AtomicUsize,
+    peak_in_use: AtomicUsize,
+    allocations: AtomicUsize,
+    deallocations: AtomicUsize,
+}
+
+impl<A: Allocator> StatsAllocator<A> {
+    /// Wraps `inner` with zeroed counters.
+    pub const fn new(inner: A) -> Self {
+        Self {
+            inner,
+            total_allocated: AtomicUsize::new(0),
+            in_use: AtomicUsize::new(0),
+            peak_in_use: AtomicUsize::new(0),
+            allocations: AtomicUsize::new(0),
+            deallocations: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns a copy of the current counters.
+    ///
+    /// Each counter is loaded individually, so a snapshot taken while other threads allocate may
+    /// mix values from slightly different moments.
+    pub fn stats(&self) -> AllocStats {
+        AllocStats {
+            total_allocated: self.total_allocated.load(Ordering::Relaxed),
+            peak_in_use: self.peak_in_use.load(Ordering::Relaxed),
+            allocations: self.allocations.load(Ordering::Relaxed),
+            deallocations: self.deallocations.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Zeroes all counters.
+    ///
+    /// Bytes that are still allocated keep being tracked, so the peak restarts from the current
+    /// number of bytes in use rather than from zero.
+    pub fn reset_stats(&self) {
+        self.total_allocated.store(0, Ordering::Relaxed);
+        self.allocations.store(0, Ordering::Relaxed);
+        self.deallocations.store(0, Ordering::Relaxed);
+        self.peak_in_use
+            .store(self.in_use.load(Ordering::Relaxed), Ordering::Relaxed);
+    }
+
+    /// Returns a reference to the wrapped allocator.
+    pub const fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    fn record_growth(&self, bytes: usize) {
+        self.total_allocated.fetch_add(bytes, Ordering::Relaxed);
+        let in_use = self.in_use.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        self.peak_in_use.fetch_max(in_use, Ordering::Relaxed);
+    }
+
+    fn record_shrinkage(&self, bytes: usize) {
+        self.in_use.fetch_sub(bytes, Ordering::Relaxed);
+    }
+}
+
+unsafe impl<A: Allocator> Allocator for StatsAllocator<A> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let allocated_ptr = self.inner.allocate(layout)?;
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+        self.record_growth(layout.size());
+        Ok(allocated_ptr)
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let allocated_ptr = self.inner.allocate_zeroed(layout)?;
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+        self.record_growth(layout.size());
+        Ok(allocated_ptr)
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        self.inner.deallocate(allocated_ptr, layout);
+        self.deallocations.fetch_add(1, Ordering::Relaxed);
+        self.record_shrinkage(layout.size());
+    }
+
+    unsafe fn grow(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let new_ptr = self.inner.grow(old_ptr, old_layout, new_layout)?;
+        self.record_growth(new_layout.size() - old_layout.size());
+        Ok(new_ptr)
+    }
+
+    unsafe fn grow_zeroed(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let new_ptr = self.inner.grow_zeroed(old_ptr, old_layout, new_layout)?;
+        self.record_growth(new_layout.size() - old_layout.size());
+        Ok(new_ptr)
+    }
+
+    unsafe fn shrink(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let new_ptr = self.inner.shrink(old_ptr, old_layout, new_layout)?;
+        self.record_shrinkage(old_layout.size() - new_layout.size());
+        Ok(new_ptr)
+    }
+}
+
+#[cfg(all(test, feature = "c_allocator"))]
+mod tests {
+    use super::*;
+    use crate::c_allocator::CAllocator;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `StatsAllocator` counters with a generic vector.
+    fn test_generic_vector_with_stats_allocator() {
+        let allocator = StatsAllocator::new(CAllocator);
+        {
+            let mut vector: Vec<u8, &StatsAllocator<CAllocator>> =
+                Vec::with_capacity_in(16, &allocator);
+            vector.extend(0..16);
+
+            assert_eq!(
+                allocator.stats(),
+                AllocStats {
+                    total_allocated: 16,
+                    peak_in_use: 16,
+                    allocations: 1,
+                    deallocations: 0,
+                }
+            );
+
+            vector.reserve_exact(48);
+            vector.shrink_to(32);
+            assert_eq!(vector.len(), 16);
+        }
+
+        assert_eq!(
+            allocator.stats(),
+            AllocStats {
+                total_allocated: 64,
+                peak_in_use: 64,
+                allocations: 1,
+                deallocations: 1,
+            }
+        );
+    }
+
+    #[test]
+    /// Tests that `reset_stats` zeroes the counters.
+    fn test_reset_stats() {
+        let allocator = StatsAllocator::new(CAllocator);
+        let layout = Layout::from_size_align(32, 8).unwrap();
+
+        let allocated_ptr = allocator.allocate(layout).unwrap();
+        unsafe { allocator.deallocate(allocated_ptr.as_non_null_ptr(), layout) };
+        allocator.reset_stats();
+
+        assert_eq!(allocator.stats(), AllocStats::default());
+    }
+}
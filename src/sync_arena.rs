@@ -0,0 +1,179 @@
+use core::{
+    alloc::{AllocError, Allocator, GlobalAlloc, Layout},
+    mem::MaybeUninit,
+    ptr::{self, NonNull},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use alloc::vec::Vec;
+
+use crate::align::align_up;
+
+/// A thread-safe bump-pointer memory allocator whose offset is an atomic.
+///
+/// Threads race to claim regions with a `compare_exchange` loop, so concurrent allocations always
+/// receive non-overlapping memory. Individual deallocations are ignored.
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct SyncArenaAllocator {
+    // Only kept to own the memory; all accesses go through `base`.
+    _buffer: Vec<MaybeUninit<u8>>,
+    base: NonNull<u8>,
+    capacity: usize,
+    offset: AtomicUsize,
+}
+
+// SAFETY: the offset is only updated atomically, and each successful `compare_exchange` hands the
+// claimed region to exactly one thread, so no two threads ever write to the same bytes.
+unsafe impl Send for SyncArenaAllocator {}
+unsafe impl Sync for SyncArenaAllocator {}
+
+impl SyncArenaAllocator {
+    /// Creates a new arena with a backing buffer of `bytes` bytes.
+    #[must_use]
+    pub fn new(bytes: usize) -> Self {
+        let mut buffer = alloc::vec![MaybeUninit::uninit(); bytes];
+        let base = NonNull::new(buffer.as_mut_ptr().cast::<u8>()).unwrap_or(NonNull::dangling());
+        Self {
+            _buffer: buffer,
+            base,
+            capacity: bytes,
+            offset: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the total size of the backing buffer in bytes.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of bytes consumed so far, including alignment padding.
+    #[must_use]
+    pub fn used(&self) -> usize {
+        self.offset.load(Ordering::SeqCst)
+    }
+
+    /// Returns the number of bytes still available for allocation.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.capacity - self.used()
+    }
+
+    /// Resets the arena, making its whole buffer available again.
+    ///
+    /// Taking `&mut self` guarantees that no collection on any thread still borrows the arena, so
+    /// no pointer handed out before the reset can be used afterwards.
+    pub fn reset(&mut self) {
+        *self.offset.get_mut() = 0;
+    }
+}
+
+unsafe impl Allocator for SyncArenaAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let base = self.base.as_ptr() as usize;
+        let mut offset = self.offset.load(Ordering::SeqCst);
+
+        loop {
+            let start = align_up(base + offset, layout.align()).ok_or(AllocError)? - base;
+            let end = start.checked_add(layout.size()).ok_or(AllocError)?;
+            if end > self.capacity {
+                return Err(AllocError);
+            }
+
+            match self
+                .offset
+                .compare_exchange_weak(offset, end, Ordering::SeqCst, Ordering::SeqCst)
+            {
+                Ok(_) => {
+                    let allocated_ptr = unsafe { self.base.add(start) };
+                    return Ok(NonNull::slice_from_raw_parts(allocated_ptr, layout.size()));
+                }
+                Err(current) => offset = current,
+            }
+        }
+    }
+
+    unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {}
+}
+
+unsafe impl GlobalAlloc for SyncArenaAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.allocate(layout)
+            .map_or(ptr::null_mut(), NonNull::as_mut_ptr)
+    }
+
+    unsafe fn dealloc(&self, _: *mut u8, _: Layout) {}
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `SyncArenaAllocator` with a generic vector.
+    fn test_generic_vector_with_sync_arena() {
+        let allocator = SyncArenaAllocator::new(1024);
+        let mut vector: Vec<usize, &SyncArenaAllocator> = Vec::with_capacity_in(100, &allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    /// Tests that allocations made concurrently from several threads never overlap.
+    fn test_concurrent_allocation() {
+        const THREADS: usize = 4;
+        const ALLOCATIONS: usize = 64;
+
+        let mut allocator = SyncArenaAllocator::new(THREADS * ALLOCATIONS * 16);
+        let layout = Layout::from_size_align(16, 1).unwrap();
+
+        let mut addresses: Vec<usize> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..THREADS)
+                .map(|thread_index| {
+                    let allocator = &allocator;
+                    scope.spawn(move || {
+                        (0..ALLOCATIONS)
+                            .map(|_| {
+                                let block = allocator.allocate(layout).unwrap();
+                                #[allow(clippy::cast_possible_truncation)]
+                                unsafe {
+                                    ptr::write_bytes(
+                                        block.as_mut_ptr(),
+                                        thread_index as u8,
+                                        layout.size(),
+                                    );
+                                }
+                                block.as_mut_ptr() as usize
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        });
+
+        assert_eq!(allocator.used(), allocator.capacity());
+        addresses.sort_unstable();
+        for pair in addresses.windows(2) {
+            assert!(pair[1] - pair[0] >= layout.size());
+        }
+        assert!(allocator.allocate(layout).is_err());
+
+        allocator.reset();
+        assert_eq!(allocator.used(), 0);
+    }
+}
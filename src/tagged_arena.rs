@@ -0,0 +1,275 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    mem,
+    ptr::{self, NonNull},
+};
+
+use alloc::alloc::Global;
+
+use crate::arena_allocator::ArenaAllocator;
+
+/// Tag given to blocks allocated through the plain [`Allocator`] interface.
+pub const UNTAGGED: u32 = 0;
+
+/// The 8-byte header stored in front of every block.
+#[derive(Debug, Clone, Copy)]
+#[repr(C, align(8))]
+struct Header {
+    tag: u32,
+    size: u32,
+}
+
+/// Size of a [`Header`], which is also the granularity headers are placed at.
+const HEADER_SIZE: usize = mem::size_of::<Header>();
+
+/// An arena that labels every allocation with a `u32` tag, such as a subsystem ID, and reports
+/// how many bytes each tag has consumed.
+///
+/// Every block is preceded by an 8-byte header holding its tag and size, so blocks of up to
+/// `u32::MAX` bytes can be allocated. [`TaggedArenaAllocator::usage_by_tag`] walks the headers
+/// from the start of the arena. Like any arena, freed blocks stay consumed until
+/// [`TaggedArenaAllocator::reset`], and they keep counting towards their tag until then.
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct TaggedArenaAllocator<A: Allocator = Global> {
+    arena: ArenaAllocator<A>,
+}
+
+impl TaggedArenaAllocator {
+    /// Creates a new tagged arena with a backing buffer of `bytes` bytes, which also has to hold
+    /// the headers.
+    #[must_use]
+    pub fn new(bytes: usize) -> Self {
+        Self::new_with_allocator(bytes, Global)
+    }
+}
+
+impl<A: Allocator> TaggedArenaAllocator<A> {
+    /// Creates a new tagged arena whose backing buffer of `bytes` bytes is allocated from `alloc`.
+    #[must_use]
+    pub fn new_with_allocator(bytes: usize, alloc: A) -> Self {
+        Self {
+            arena: ArenaAllocator::new_with_allocator(bytes, alloc),
+        }
+    }
+
+    /// Returns the size of the backing buffer in bytes.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.arena.capacity()
+    }
+
+    /// Returns the number of bytes consumed so far, including headers and alignment padding.
+    #[must_use]
+    pub fn used(&self) -> usize {
+        self.arena.used()
+    }
+
+    /// Allocates a block for `layout` and charges it to `tag`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AllocError` if the size does not fit in a `u32` or the arena is out of space.
+    pub fn allocate_tagged(&self, layout: Layout, tag: u32) -> Result<NonNull<[u8]>, AllocError> {
+        let size = u32::try_from(layout.size()).map_err(|_| AllocError)?;
+        let base = self.arena.base_ptr() as usize;
+        let header = (base + self.arena.used())
+            .checked_next_multiple_of(HEADER_SIZE)
+            .ok_or(AllocError)?;
+        let start = (header + HEADER_SIZE)
+            .checked_next_multiple_of(layout.align())
+            .ok_or(AllocError)?;
+        let total = (start - header)
+            .checked_add(layout.size())
+            .ok_or(AllocError)?;
+
+        // With header alignment the arena places the block exactly at `header`, so the headers
+        // form an unbroken chain from the start of the buffer.
+        let block = self
+            .arena
+            .allocate(Layout::from_size_align(total, HEADER_SIZE).map_err(|_| AllocError)?)?;
+        debug_assert_eq!(block.as_mut_ptr() as usize, header);
+
+        unsafe {
+            let block = block.as_mut_ptr();
+            // Padding before the header is filled with empty blocks of the same tag, which the
+            // walk steps over without counting anything.
+            for offset in (0..start - header - HEADER_SIZE).step_by(HEADER_SIZE) {
+                write_header(block.add(offset), Header { tag, size: 0 });
+            }
+            let allocated_ptr = block.add(start - header);
+            write_header(allocated_ptr.sub(HEADER_SIZE), Header { tag, size });
+            Ok(NonNull::slice_from_raw_parts(
+                NonNull::new_unchecked(allocated_ptr),
+                layout.size(),
+            ))
+        }
+    }
+
+    /// Returns the tag the block at `ptr` was allocated with.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must denote a block allocated by this arena since the last reset.
+    #[must_use]
+    pub const unsafe fn tag_of(&self, ptr: NonNull<u8>) -> u32 {
+        read_header(ptr.as_ptr().sub(HEADER_SIZE)).tag
+    }
+
+    /// Returns the number of bytes allocated under `tag` since the last reset, excluding headers
+    /// and padding.
+    #[must_use]
+    pub fn usage_by_tag(&self, tag: u32) -> usize {
+        let base = self.arena.base_ptr();
+        let end = self.arena.used();
+        let mut offset = 0;
+        let mut total = 0;
+
+        while offset < end {
+            offset = (base as usize + offset).next_multiple_of(HEADER_SIZE) - base as usize;
+            // SAFETY: every block starts with an initialised header at the next header boundary.
+            let header = unsafe { read_header(base.add(offset)) };
+            if header.tag == tag {
+                total += header.size as usize;
+            }
+            offset += HEADER_SIZE + header.size as usize;
+        }
+        total
+    }
+
+    /// Resets the arena, discarding every block and header.
+    ///
+    /// Taking `&mut self` ensures nothing borrowing the arena outlives the reset. Raw pointers
+    /// handed out before the reset must no longer be used.
+    pub const fn reset(&mut self) {
+        self.arena.reset();
+    }
+
+    /// Moves the block at `old_ptr` into a new block of `new_layout` with the same tag.
+    unsafe fn reallocate(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let new_ptr = self.allocate_tagged(new_layout, self.tag_of(old_ptr))?;
+        let copy_size = old_layout.size().min(new_layout.size());
+        ptr::copy_nonoverlapping(old_ptr.as_ptr(), new_ptr.as_mut_ptr(), copy_size);
+        Ok(new_ptr)
+    }
+}
+
+/// Writes `header` at `ptr`, which must be aligned to [`HEADER_SIZE`].
+#[allow(clippy::cast_ptr_alignment)] // Headers sit on header boundaries.
+const unsafe fn write_header(ptr: *mut u8, header: Header) {
+    ptr.cast::<Header>().write(header);
+}
+
+/// Reads the header at `ptr`, which must be aligned to [`HEADER_SIZE`].
+#[allow(clippy::cast_ptr_alignment)] // Headers sit on header boundaries.
+const unsafe fn read_header(ptr: *const u8) -> Header {
+    ptr.cast::<Header>().read()
+}
+
+unsafe impl<A: Allocator> Allocator for TaggedArenaAllocator<A> {
+    /// Blocks are charged to [`UNTAGGED`].
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.allocate_tagged(layout, UNTAGGED)
+    }
+
+    unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {}
+
+    /// The block is copied, keeping its tag.
+    unsafe fn grow(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.reallocate(old_ptr, old_layout, new_layout)
+    }
+
+    /// The block is copied, keeping its tag.
+    unsafe fn shrink(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.reallocate(old_ptr, old_layout, new_layout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `TaggedArenaAllocator` with a generic vector.
+    fn test_generic_vector_with_tagged_arena() {
+        let allocator = TaggedArenaAllocator::new(4096);
+        let mut vector: Vec<usize, &TaggedArenaAllocator> = Vec::with_capacity_in(100, &allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        assert_eq!(
+            allocator.usage_by_tag(UNTAGGED),
+            100 * mem::size_of::<usize>()
+        );
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    /// Tests per-tag accounting across three tags with mixed sizes and alignments.
+    fn test_usage_by_tag() {
+        const NETWORK: u32 = 1;
+        const RENDER: u32 = 2;
+        const AI: u32 = 3;
+
+        let mut allocator = TaggedArenaAllocator::new(4096);
+        let layouts = [
+            (3, 1, NETWORK),
+            (64, 64, RENDER),
+            (24, 8, AI),
+            (5, 2, NETWORK),
+            (100, 32, RENDER),
+        ];
+        for (size, align, tag) in layouts {
+            let block = allocator
+                .allocate_tagged(Layout::from_size_align(size, align).unwrap(), tag)
+                .unwrap();
+            assert!((block.as_mut_ptr() as usize).is_multiple_of(align));
+            assert_eq!(unsafe { allocator.tag_of(block.as_non_null_ptr()) }, tag);
+        }
+
+        assert_eq!(allocator.usage_by_tag(NETWORK), 8);
+        assert_eq!(allocator.usage_by_tag(RENDER), 164);
+        assert_eq!(allocator.usage_by_tag(AI), 24);
+        assert_eq!(allocator.usage_by_tag(4), 0);
+
+        assert!(allocator.used() <= allocator.capacity());
+        allocator.reset();
+        assert_eq!(allocator.usage_by_tag(RENDER), 0);
+        assert_eq!(allocator.used(), 0);
+    }
+
+    #[test]
+    /// Tests that a grown block keeps its tag.
+    fn test_grow_keeps_tag() {
+        let allocator = TaggedArenaAllocator::new(1024);
+        let old_layout = Layout::from_size_align(16, 8).unwrap();
+        let new_layout = Layout::from_size_align(32, 8).unwrap();
+        let block = allocator.allocate_tagged(old_layout, 7).unwrap();
+
+        let grown =
+            unsafe { allocator.grow(block.as_non_null_ptr(), old_layout, new_layout) }.unwrap();
+        assert_eq!(unsafe { allocator.tag_of(grown.as_non_null_ptr()) }, 7);
+        assert_eq!(allocator.usage_by_tag(7), 48);
+    }
+}
@@ -0,0 +1,336 @@
+use core::{
+    alloc::{Allocator, Layout},
+    ptr::{self, NonNull},
+    slice,
+};
+
+/// Runs the full conformance suite against allocators produced by `factory`.
+///
+/// Every check starts from a fresh allocator, so `factory` should return an allocator with room
+/// for at least a few kilobytes. The suite verifies that:
+///
+/// 1. zero-sized allocations succeed and can be freed,
+/// 2. a one-byte allocation succeeds and an array of 100 `i32`s can be written and read back,
+/// 3. every successful allocation honours alignments from 1 up to 4096 bytes,
+/// 4. `grow` provides at least the new size and keeps the old contents,
+/// 5. `shrink` provides at most the old size and keeps the remaining contents,
+/// 6. writing to one allocation never corrupts another.
+///
+/// # Panics
+///
+/// Panics with a message naming the failed check if the allocator misbehaves.
+pub fn conformance_test<A: Allocator>(factory: impl Fn() -> A) {
+    check_zero_size(&factory());
+    check_small_allocations(&factory());
+    check_alignment(&factory());
+    check_grow(&factory());
+    check_shrink(&factory());
+    check_no_corruption(&factory());
+}
+
+/// Returns the first `len` bytes of `block`.
+const unsafe fn bytes<'a>(block: NonNull<[u8]>, len: usize) -> &'a [u8] {
+    slice::from_raw_parts(block.as_mut_ptr(), len)
+}
+
+/// Checks that zero-sized allocations succeed, whatever their alignment.
+fn check_zero_size<A: Allocator>(allocator: &A) {
+    for align in [1, 8, 64] {
+        let layout = Layout::from_size_align(0, align).unwrap();
+        let block = allocator
+            .allocate(layout)
+            .expect("zero-sized allocation failed");
+        assert!(
+            (block.as_mut_ptr() as usize).is_multiple_of(align),
+            "zero-sized allocation is not aligned to {align} bytes"
+        );
+        unsafe { allocator.deallocate(block.as_non_null_ptr(), layout) };
+    }
+}
+
+/// Checks that a single byte and an array of `i32`s can be allocated, written and read back.
+#[allow(clippy::cast_ptr_alignment)] // The array layout is aligned for `i32`.
+fn check_small_allocations<A: Allocator>(allocator: &A) {
+    let layout = Layout::from_size_align(1, 1).unwrap();
+    let block = allocator
+        .allocate(layout)
+        .expect("one-byte allocation failed");
+    assert!(!block.is_empty(), "one-byte allocation is empty");
+    unsafe { allocator.deallocate(block.as_non_null_ptr(), layout) };
+
+    let layout = Layout::array::<i32>(100).unwrap();
+    let block = allocator
+        .allocate(layout)
+        .expect("allocation of 100 i32s failed");
+    assert!(
+        block.len() >= layout.size(),
+        "array allocation is too short"
+    );
+    let values = unsafe { slice::from_raw_parts_mut(block.as_mut_ptr().cast::<i32>(), 100) };
+    for (value, expected) in values.iter_mut().zip(0..) {
+        *value = expected;
+    }
+    assert!(
+        values
+            .iter()
+            .zip(0..)
+            .all(|(&value, expected)| value == expected),
+        "array contents changed"
+    );
+    unsafe { allocator.deallocate(block.as_non_null_ptr(), layout) };
+}
+
+/// Checks that every allocation that succeeds honours its alignment.
+fn check_alignment<A: Allocator>(allocator: &A) {
+    for shift in 0..=12 {
+        let align = 1 << shift;
+        let layout = Layout::from_size_align(8, align).unwrap();
+        if let Ok(block) = allocator.allocate(layout) {
+            assert!(
+                (block.as_mut_ptr() as usize).is_multiple_of(align),
+                "allocation is not aligned to {align} bytes"
+            );
+            unsafe { allocator.deallocate(block.as_non_null_ptr(), layout) };
+        }
+    }
+}
+
+/// Checks that `grow` extends a block and keeps its contents.
+fn check_grow<A: Allocator>(allocator: &A) {
+    let old_layout = Layout::from_size_align(16, 8).unwrap();
+    let new_layout = Layout::from_size_align(64, 8).unwrap();
+
+    let block = allocator.allocate(old_layout).expect("allocation failed");
+    unsafe { ptr::write_bytes(block.as_mut_ptr(), 0xA5, 16) };
+
+    let grown = unsafe { allocator.grow(block.as_non_null_ptr(), old_layout, new_layout) }
+        .expect("grow failed");
+    assert!(grown.len() >= 64, "grow did not provide the new size");
+    assert!(
+        unsafe { bytes(grown, 16) }.iter().all(|&byte| byte == 0xA5),
+        "grow lost the old contents"
+    );
+    unsafe { allocator.deallocate(grown.as_non_null_ptr(), new_layout) };
+}
+
+/// Checks that `shrink` reduces a block and keeps the remaining contents.
+fn check_shrink<A: Allocator>(allocator: &A) {
+    let old_layout = Layout::from_size_align(64, 8).unwrap();
+    let new_layout = Layout::from_size_align(16, 8).unwrap();
+
+    let block = allocator.allocate(old_layout).expect("allocation failed");
+    unsafe { ptr::write_bytes(block.as_mut_ptr(), 0x5A, 64) };
+
+    let shrunk = unsafe { allocator.shrink(block.as_non_null_ptr(), old_layout, new_layout) }
+        .expect("shrink failed");
+    assert!(shrunk.len() >= 16, "shrink did not provide the new size");
+    assert!(
+        shrunk.len() <= block.len(),
+        "shrink increased the usable size"
+    );
+    assert!(
+        unsafe { bytes(shrunk, 16) }
+            .iter()
+            .all(|&byte| byte == 0x5A),
+        "shrink lost the remaining contents"
+    );
+    unsafe { allocator.deallocate(shrunk.as_non_null_ptr(), new_layout) };
+}
+
+/// Checks that live allocations of different sizes and alignments do not overwrite each other.
+fn check_no_corruption<A: Allocator>(allocator: &A) {
+    const LAYOUTS: [(usize, usize); 6] = [(1, 1), (3, 1), (8, 8), (24, 8), (64, 16), (100, 32)];
+
+    let mut blocks = [None; LAYOUTS.len()];
+    for ((size, align), (block, tag)) in LAYOUTS.iter().zip(blocks.iter_mut().zip(1_u8..)) {
+        let layout = Layout::from_size_align(*size, *align).unwrap();
+        let allocated = allocator.allocate(layout).expect("allocation failed");
+        unsafe { ptr::write_bytes(allocated.as_mut_ptr(), tag, *size) };
+        *block = Some((allocated, layout));
+    }
+
+    for (block, tag) in blocks.iter().zip(1_u8..) {
+        let (allocated, layout) = block.unwrap();
+        assert!(
+            unsafe { bytes(allocated, layout.size()) }
+                .iter()
+                .all(|&byte| byte == tag),
+            "allocation {tag} was corrupted by another allocation"
+        );
+    }
+
+    for (allocated, layout) in blocks.into_iter().flatten() {
+        unsafe { allocator.deallocate(allocated.as_non_null_ptr(), layout) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::alloc::Global;
+
+    #[test]
+    /// Tests that the global allocator passes the conformance suite.
+    fn test_global_conformance() {
+        conformance_test(|| Global);
+    }
+
+    #[test]
+    #[cfg(feature = "arena_allocator")]
+    /// Tests that the `ArenaAllocator` passes the conformance suite.
+    fn test_arena_allocator_conformance() {
+        conformance_test(|| crate::arena_allocator::ArenaAllocator::new(16 * 1024));
+    }
+
+    #[test]
+    #[cfg(feature = "c_allocator")]
+    /// Tests that the C allocators pass the conformance suite.
+    fn test_c_allocator_conformance() {
+        conformance_test(|| crate::c_allocator::CAllocator);
+        conformance_test(|| crate::c_allocator::RawCAllocator);
+    }
+
+    #[test]
+    #[cfg(feature = "stack_allocator")]
+    /// Tests that the `StackAllocator` passes the conformance suite.
+    fn test_stack_allocator_conformance() {
+        conformance_test(|| crate::stack_allocator::StackAllocator::new(16 * 1024));
+    }
+
+    #[test]
+    #[cfg(feature = "free_list_allocator")]
+    /// Tests that the `FreeListAllocator` passes the conformance suite.
+    fn test_free_list_allocator_conformance() {
+        conformance_test(|| crate::free_list_allocator::FreeListAllocator::new(16 * 1024));
+    }
+
+    #[test]
+    #[cfg(feature = "tlsf_allocator")]
+    /// Tests that the `TlsfAllocator` passes the conformance suite.
+    fn test_tlsf_allocator_conformance() {
+        conformance_test(|| crate::tlsf_allocator::TlsfAllocator::new(16 * 1024));
+    }
+
+    #[test]
+    #[cfg(feature = "buddy_allocator")]
+    /// Tests that the `BuddyAllocator` passes the conformance suite.
+    fn test_buddy_allocator_conformance() {
+        conformance_test(|| crate::buddy_allocator::BuddyAllocator::new(16 * 1024, 64));
+    }
+
+    #[test]
+    #[cfg(feature = "bitmap_allocator")]
+    /// Tests that the `BitmapAllocator` passes the conformance suite.
+    fn test_bitmap_allocator_conformance() {
+        conformance_test(|| crate::bitmap_allocator::BitmapAllocator::new(512, 16));
+    }
+
+    #[test]
+    #[cfg(feature = "mmap_allocator")]
+    /// Tests that the `MmapAllocator` passes the conformance suite.
+    fn test_mmap_allocator_conformance() {
+        conformance_test(|| crate::mmap_allocator::MmapAllocator);
+    }
+
+    #[test]
+    #[cfg(feature = "mimalloc")]
+    /// Tests that the `MiMallocAllocator` passes the conformance suite.
+    fn test_mimalloc_allocator_conformance() {
+        conformance_test(|| crate::mimalloc_allocator::MiMallocAllocator);
+    }
+
+    #[test]
+    #[cfg(feature = "recycling_arena")]
+    /// Tests that the `RecyclingArena` passes the conformance suite.
+    fn test_recycling_arena_conformance() {
+        conformance_test(|| crate::recycling_arena::RecyclingArena::new(8192));
+    }
+
+    #[test]
+    #[cfg(feature = "pool_allocator")]
+    /// Tests that the `PoolAllocator` passes the conformance suite.
+    fn test_pool_allocator_conformance() {
+        conformance_test(|| crate::pool_allocator::PoolAllocator::new(512, 16));
+    }
+
+    #[test]
+    #[cfg(feature = "segregated_allocator")]
+    /// Tests that the `SegregatedAllocator` passes the conformance suite.
+    fn test_segregated_allocator_conformance() {
+        conformance_test(|| crate::segregated_allocator::SegregatedAllocator::new(16));
+    }
+
+    #[test]
+    #[cfg(feature = "checked_arena")]
+    /// Tests that the `CheckedArenaAllocator` passes the conformance suite.
+    fn test_checked_arena_conformance() {
+        conformance_test(|| crate::checked_arena::CheckedArenaAllocator::new(16 * 1024));
+    }
+
+    #[test]
+    #[cfg(feature = "guarded_arena")]
+    /// Tests that the `GuardedArenaAllocator` passes the conformance suite.
+    fn test_guarded_arena_conformance() {
+        conformance_test(|| crate::guarded_arena::GuardedArenaAllocator::new(32 * 1024));
+    }
+
+    #[test]
+    #[cfg(feature = "tagged_arena")]
+    /// Tests that the `TaggedArenaAllocator` passes the conformance suite.
+    fn test_tagged_arena_conformance() {
+        conformance_test(|| crate::tagged_arena::TaggedArenaAllocator::new(32 * 1024));
+    }
+
+    #[test]
+    #[cfg(feature = "report_allocator")]
+    /// Tests that the `ReportAllocator` passes the conformance suite.
+    fn test_report_allocator_conformance() {
+        use crate::report_allocator::{CountingReporter, ReportAllocator};
+
+        conformance_test(|| ReportAllocator::new(alloc::alloc::Global, CountingReporter::new()));
+    }
+
+    #[test]
+    #[cfg(feature = "chunk_arena")]
+    /// Tests that the `ChunkArenaAllocator` passes the conformance suite.
+    fn test_chunk_arena_conformance() {
+        conformance_test(|| crate::chunk_arena::ChunkArenaAllocator::new(16 * 1024));
+    }
+
+    #[test]
+    #[cfg(all(feature = "mirrored_allocator", feature = "c_allocator"))]
+    /// Tests that the `MirroredAllocator` passes the conformance suite.
+    fn test_mirrored_allocator_conformance() {
+        use crate::c_allocator::{CAllocator, RawCAllocator};
+
+        conformance_test(|| {
+            crate::mirrored_allocator::MirroredAllocator::new(CAllocator, RawCAllocator)
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "stack_frame")]
+    /// Tests that the `StackAllocatorFrame` passes the conformance suite.
+    fn test_stack_frame_conformance() {
+        use crate::stack_frame::StackAllocatorFrame;
+        use core::mem::MaybeUninit;
+
+        let mut buffer = [MaybeUninit::uninit(); 16 * 1024];
+        let (start, len) = (buffer.as_mut_ptr(), buffer.len());
+        conformance_test(|| {
+            // SAFETY: the suite drops every frame before asking for the next one, so each frame
+            // has the buffer to itself, and the buffer outlives all of them.
+            StackAllocatorFrame::new(unsafe { slice::from_raw_parts_mut(start, len) })
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "padded_allocator")]
+    /// Tests that the `PaddedAllocator` passes the conformance suite.
+    fn test_padded_allocator_conformance() {
+        use crate::padded_allocator::PaddedAllocator;
+
+        conformance_test(|| PaddedAllocator::<_, 1>::new(alloc::alloc::Global));
+        conformance_test(|| PaddedAllocator::<_, 16>::new(alloc::alloc::Global));
+    }
+}
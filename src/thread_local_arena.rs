@@ -0,0 +1,183 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    cell::RefCell,
+    marker::PhantomData,
+    ptr::NonNull,
+};
+
+use std::thread::LocalKey;
+
+use crate::arena_allocator::ArenaAllocator;
+
+/// Declares a thread-local [`ArenaAllocator`] with `CAPACITY` bytes per thread and evaluates to a
+/// [`ThreadLocalArena`] handle for it.
+///
+/// Every invocation declares its own thread-local static, so handles from different invocations
+/// never share arenas. `CAPACITY` is evaluated lazily on each thread that first uses the handle
+/// and must not refer to local variables.
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use mem_allocs::{thread_local_arena, thread_local_arena::ThreadLocalArena};
+///
+/// let arena: ThreadLocalArena = thread_local_arena!(1024);
+/// let mut values = Vec::with_capacity_in(4, arena);
+/// values.extend([1, 2, 3, 4]);
+/// assert_eq!(values.iter().sum::<i32>(), 10);
+/// ```
+#[macro_export]
+macro_rules! thread_local_arena {
+    ($capacity:expr) => {{
+        ::std::thread_local! {
+            static ARENA: ::core::cell::RefCell<$crate::arena_allocator::ArenaAllocator> =
+                ::core::cell::RefCell::new($crate::arena_allocator::ArenaAllocator::new($capacity));
+        }
+        $crate::thread_local_arena::ThreadLocalArena::new(&ARENA)
+    }};
+}
+
+/// A handle to a per-thread [`ArenaAllocator`], allocating without any synchronisation.
+///
+/// Every thread that allocates through the handle gets its own arena, created on first use, so
+/// threads never contend with each other. Use [`thread_local_arena!`](crate::thread_local_arena!)
+/// to declare the thread-local storage and obtain a handle.
+///
+/// Memory lives in the arena of the thread that allocated it. Pointers may only be used, resized
+/// and freed on that thread, and they dangle once the thread exits or resets its arena. The
+/// handle is therefore neither `Send` nor `Sync`, so collections using it stay on their thread:
+///
+/// ```compile_fail
+/// #![feature(allocator_api)]
+///
+/// use mem_allocs::thread_local_arena;
+///
+/// let values = Vec::<u8, _>::new_in(thread_local_arena!(1024));
+/// std::thread::spawn(move || drop(values));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ThreadLocalArena {
+    key: &'static LocalKey<RefCell<ArenaAllocator>>,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl ThreadLocalArena {
+    /// Creates a handle for the arenas stored in `key`.
+    ///
+    /// Prefer [`thread_local_arena!`](crate::thread_local_arena!), which declares `key` as well.
+    #[must_use]
+    pub const fn new(key: &'static LocalKey<RefCell<ArenaAllocator>>) -> Self {
+        Self {
+            key,
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Returns the capacity of the current thread's arena in bytes.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.key.with_borrow(ArenaAllocator::capacity)
+    }
+
+    /// Returns the number of bytes consumed so far on the current thread.
+    #[must_use]
+    pub fn used(&self) -> usize {
+        self.key.with_borrow(ArenaAllocator::used)
+    }
+
+    /// Returns the number of bytes still available on the current thread.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.key.with_borrow(ArenaAllocator::remaining)
+    }
+
+    /// Resets the current thread's arena, leaving the arenas of other threads untouched.
+    ///
+    /// # Safety
+    ///
+    /// Handles are `Copy`, so the borrow checker cannot tell whether blocks from this thread's
+    /// arena are still in use. The caller must ensure no block handed out on this thread before
+    /// the reset is used afterwards; that includes the buffers of every live collection
+    /// allocating through a handle to the same arena.
+    pub unsafe fn reset(&self) {
+        self.key.with_borrow_mut(ArenaAllocator::reset);
+    }
+}
+
+unsafe impl Allocator for ThreadLocalArena {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.key
+            .try_with(|arena| arena.borrow_mut().allocate(layout))
+            .map_err(|_| AllocError)?
+    }
+
+    unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {}
+
+    unsafe fn grow(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.key
+            .try_with(|arena| arena.borrow_mut().grow(old_ptr, old_layout, new_layout))
+            .map_err(|_| AllocError)?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use core::ptr;
+
+    #[test]
+    /// Tests the `ThreadLocalArena` with a generic vector.
+    fn test_generic_vector_with_thread_local_arena() {
+        let arena = thread_local_arena!(1024);
+        let mut vector: Vec<usize, ThreadLocalArena> = Vec::with_capacity_in(100, arena);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+        assert_eq!(arena.used(), 100 * core::mem::size_of::<usize>());
+    }
+
+    #[test]
+    /// Tests that threads allocate from separate arenas and reset them independently.
+    fn test_per_thread_arenas() {
+        std::thread_local! {
+            static ARENA: RefCell<ArenaAllocator> = RefCell::new(ArenaAllocator::new(256));
+        }
+        let layout = Layout::from_size_align(64, 8).unwrap();
+
+        std::thread::scope(|scope| {
+            for tag in [0x11_u8, 0x22] {
+                // Handles cannot cross threads, so each thread makes its own for the same key.
+                scope.spawn(move || {
+                    let arena = ThreadLocalArena::new(&ARENA);
+                    let first = arena.allocate(layout).unwrap();
+                    unsafe { ptr::write_bytes(first.as_mut_ptr(), tag, 64) };
+                    assert_eq!(arena.used(), 64);
+
+                    for _ in 0..8 {
+                        // SAFETY: `first` is only read again after it is handed out anew.
+                        unsafe { arena.reset() };
+                        let block = arena.allocate(layout).unwrap();
+                        assert_eq!(block, first);
+                        let bytes = unsafe { core::slice::from_raw_parts(block.as_mut_ptr(), 64) };
+                        assert!(bytes.iter().all(|&byte| byte == tag));
+                        std::thread::yield_now();
+                    }
+                });
+            }
+        });
+
+        assert_eq!(ThreadLocalArena::new(&ARENA).used(), 0);
+    }
+}
@@ -0,0 +1,608 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    cell::UnsafeCell,
+    mem::{self, MaybeUninit},
+    ptr::{self, NonNull},
+};
+
+use alloc::vec::Vec;
+
+use crate::align::align_up;
+
+/// Size of the header in front of every block's payload: the previous physical block and the size.
+const HEADER: usize = 2 * mem::size_of::<usize>();
+
+/// Alignment of every payload and granularity of every payload size.
+const ALIGN: usize = HEADER;
+
+/// Smallest payload, large enough to hold the free-list links of a free block.
+const BLOCK_MIN: usize = 2 * mem::size_of::<usize>();
+
+/// Log2 of the number of second-level classes per first-level class.
+const SL_LOG: u32 = 4;
+
+/// Number of second-level classes per first-level class.
+const SL_COUNT: usize = 1 << SL_LOG;
+
+/// Log2 of the smallest size handled by the power-of-two first-level classes; smaller sizes all
+/// share first-level class zero with a linear second level.
+const FL_SHIFT: u32 = SL_LOG + ALIGN.trailing_zeros();
+
+/// Smallest size handled by the power-of-two first-level classes.
+const SMALL_BLOCK: usize = 1 << FL_SHIFT;
+
+/// Number of first-level classes needed to cover every `usize` size.
+const FL_COUNT: usize = (usize::BITS - FL_SHIFT + 1) as usize;
+
+/// Flag stored in the low bit of a block's size when the block is free.
+const FREE: usize = 1;
+
+/// A cell of the backing storage, aligned to at least `ALIGN` bytes.
+///
+/// Payloads are only aligned to `ALIGN` if the buffer starts on such a boundary; a plain
+/// `[usize; 2]` would only guarantee the alignment of a `usize`.
+#[derive(Debug, Clone, Copy)]
+#[repr(align(16))]
+#[allow(dead_code)] // The field is only storage, accessed through raw pointers.
+struct Unit(MaybeUninit<[usize; 2]>);
+
+const _: () = assert!(mem::align_of::<Unit>().is_multiple_of(ALIGN));
+
+/// Header of a block. The free-list links overlap the payload and are only valid while the block
+/// is free; the zero-sized sentinel at the end of the buffer has no room for them.
+#[repr(C)]
+struct BlockHeader {
+    prev_phys: Option<NonNull<Self>>,
+    size_and_flags: usize,
+    next_free: Option<NonNull<Self>>,
+    prev_free: Option<NonNull<Self>>,
+}
+
+type Block = NonNull<BlockHeader>;
+
+/// Returns the payload size of `block`.
+unsafe fn size(block: Block) -> usize {
+    (*block.as_ptr()).size_and_flags & !FREE
+}
+
+/// Sets the payload size of `block`, keeping its free flag.
+unsafe fn set_size(block: Block, size: usize) {
+    let header = block.as_ptr();
+    (*header).size_and_flags = size | ((*header).size_and_flags & FREE);
+}
+
+/// Returns `true` if `block` is free.
+unsafe fn is_free(block: Block) -> bool {
+    (*block.as_ptr()).size_and_flags & FREE != 0
+}
+
+/// Marks `block` as free or used.
+unsafe fn set_free(block: Block, free: bool) {
+    let header = block.as_ptr();
+    (*header).size_and_flags = ((*header).size_and_flags & !FREE) | usize::from(free);
+}
+
+/// Returns the payload of `block`.
+const unsafe fn payload(block: Block) -> NonNull<u8> {
+    block.cast::<u8>().add(HEADER)
+}
+
+/// Returns the block whose payload starts at `allocated_ptr`.
+const unsafe fn from_payload(allocated_ptr: NonNull<u8>) -> Block {
+    allocated_ptr.sub(HEADER).cast::<BlockHeader>()
+}
+
+/// Returns the block physically following `block`.
+unsafe fn next_phys(block: Block) -> Block {
+    block.byte_add(HEADER + size(block))
+}
+
+/// Splits `block` after `size` payload bytes and returns the free block made of the rest.
+unsafe fn split(block: Block, size: usize) -> Block {
+    let remaining = block.byte_add(HEADER + size);
+    let remaining_size = self::size(block) - size - HEADER;
+    (*remaining.as_ptr()).prev_phys = Some(block);
+    (*remaining.as_ptr()).size_and_flags = remaining_size | FREE;
+    (*next_phys(remaining).as_ptr()).prev_phys = Some(remaining);
+    set_size(block, size);
+    remaining
+}
+
+/// Merges `next`, the block physically following `block`, into `block`.
+unsafe fn absorb(block: Block, next: Block) {
+    set_size(block, size(block) + HEADER + size(next));
+    (*next_phys(block).as_ptr()).prev_phys = Some(block);
+}
+
+/// Returns `true` if `block` is large enough to be split after `size` payload bytes.
+unsafe fn can_split(block: Block, size: usize) -> bool {
+    self::size(block) >= size + HEADER + BLOCK_MIN
+}
+
+/// Rounds a request up to a valid payload size.
+fn adjust_size(size: usize) -> Option<usize> {
+    Some(align_up(size, ALIGN)?.max(BLOCK_MIN))
+}
+
+/// Returns the first- and second-level class of a free block of `size` bytes.
+const fn mapping_insert(size: usize) -> (usize, usize) {
+    if size < SMALL_BLOCK {
+        (0, size / (SMALL_BLOCK / SL_COUNT))
+    } else {
+        let fl = usize::BITS - 1 - size.leading_zeros();
+        let sl = (size >> (fl - SL_LOG)) ^ SL_COUNT;
+        ((fl - FL_SHIFT + 1) as usize, sl)
+    }
+}
+
+/// Returns the first class whose blocks are all at least `size` bytes.
+fn mapping_search(size: usize) -> Option<(usize, usize)> {
+    let size = if size < SMALL_BLOCK {
+        size
+    } else {
+        let fl = usize::BITS - 1 - size.leading_zeros();
+        size.checked_add((1 << (fl - SL_LOG)) - 1)?
+    };
+    Some(mapping_insert(size))
+}
+
+/// The two-level index of free lists.
+#[derive(Debug)]
+struct Control {
+    /// Bit `fl` is set if any list of first-level class `fl` is non-empty.
+    fl_bitmap: usize,
+    /// Bit `sl` of entry `fl` is set if the list of class `(fl, sl)` is non-empty.
+    sl_bitmap: [u32; FL_COUNT],
+    blocks: [[Option<Block>; SL_COUNT]; FL_COUNT],
+}
+
+impl Control {
+    /// Pushes the free `block` onto the list of its class.
+    unsafe fn insert(&mut self, block: Block) {
+        let (fl, sl) = mapping_insert(size(block));
+        let head = self.blocks[fl][sl];
+        (*block.as_ptr()).next_free = head;
+        (*block.as_ptr()).prev_free = None;
+        if let Some(head) = head {
+            (*head.as_ptr()).prev_free = Some(block);
+        }
+        self.blocks[fl][sl] = Some(block);
+        self.fl_bitmap |= 1 << fl;
+        self.sl_bitmap[fl] |= 1 << sl;
+    }
+
+    /// Unlinks the free `block` from the list of its class.
+    unsafe fn remove(&mut self, block: Block) {
+        let (fl, sl) = mapping_insert(size(block));
+        let next = (*block.as_ptr()).next_free;
+        let prev = (*block.as_ptr()).prev_free;
+        if let Some(next) = next {
+            (*next.as_ptr()).prev_free = prev;
+        }
+        if let Some(prev) = prev {
+            (*prev.as_ptr()).next_free = next;
+            return;
+        }
+
+        self.blocks[fl][sl] = next;
+        if next.is_none() {
+            self.sl_bitmap[fl] &= !(1 << sl);
+            if self.sl_bitmap[fl] == 0 {
+                self.fl_bitmap &= !(1 << fl);
+            }
+        }
+    }
+
+    /// Removes and returns a free block with a payload of at least `size` bytes.
+    unsafe fn take(&mut self, size: usize) -> Option<Block> {
+        let (mut fl, sl) = mapping_search(size)?;
+        if fl >= FL_COUNT {
+            return None;
+        }
+
+        let mut sl_map = self.sl_bitmap[fl] & (u32::MAX << sl);
+        if sl_map == 0 {
+            // `fl + 1` is at most `FL_COUNT`, which is below `usize::BITS`.
+            let fl_map = self.fl_bitmap & (usize::MAX << (fl + 1));
+            if fl_map == 0 {
+                return None;
+            }
+            fl = fl_map.trailing_zeros() as usize;
+            sl_map = self.sl_bitmap[fl];
+        }
+
+        let block = self.blocks[fl][sl_map.trailing_zeros() as usize]?;
+        self.remove(block);
+        Some(block)
+    }
+
+    /// Merges the free `block` with its physical predecessor if that one is free too.
+    unsafe fn merge_prev(&mut self, block: Block) -> Block {
+        match (*block.as_ptr()).prev_phys {
+            Some(prev) if is_free(prev) => {
+                self.remove(prev);
+                absorb(prev, block);
+                prev
+            }
+            _ => block,
+        }
+    }
+
+    /// Merges `block` with its physical successor if that one is free.
+    unsafe fn merge_next(&mut self, block: Block) {
+        let next = next_phys(block);
+        if is_free(next) {
+            self.remove(next);
+            absorb(block, next);
+        }
+    }
+
+    /// Gives the tail of the used `block` beyond `size` payload bytes back to the free lists.
+    unsafe fn trim_used(&mut self, block: Block, size: usize) {
+        if can_split(block, size) {
+            let remaining = split(block, size);
+            self.merge_next(remaining);
+            self.insert(remaining);
+        }
+    }
+}
+
+/// A Two-Level Segregated Fit memory allocator with O(1) allocation and deallocation.
+///
+/// Free blocks are indexed by a power-of-two first level and a linear second level of 16
+/// sub-classes, with a bitmap per level, so finding a suitable block takes a couple of bit scans
+/// instead of a list walk. Freed blocks are merged with free physical neighbours immediately,
+/// which keeps fragmentation low and makes the timing of every operation bounded, as real-time
+/// code requires.
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct TlsfAllocator {
+    // Only kept to own the memory; all accesses go through `base` and the free lists.
+    _buffer: Vec<Unit>,
+    base: NonNull<u8>,
+    capacity: usize,
+    control: UnsafeCell<Control>,
+}
+
+impl TlsfAllocator {
+    /// Creates a new allocator with a backing buffer of `bytes` bytes.
+    ///
+    /// Part of the buffer holds block headers, so the largest possible allocation, reported by
+    /// [`TlsfAllocator::capacity`], is slightly smaller.
+    #[must_use]
+    pub fn new(bytes: usize) -> Self {
+        let unit = mem::size_of::<Unit>();
+        let mut buffer: Vec<Unit> = alloc::vec![Unit(MaybeUninit::uninit()); bytes / unit];
+        let base = NonNull::new(buffer.as_mut_ptr().cast::<u8>()).unwrap_or(NonNull::dangling());
+        let total = buffer.len() * unit;
+        let mut control = Control {
+            fl_bitmap: 0,
+            sl_bitmap: [0; FL_COUNT],
+            blocks: [[None; SL_COUNT]; FL_COUNT],
+        };
+
+        // One free block spanning the buffer, followed by a used zero-sized sentinel that stops
+        // merges at the end.
+        let capacity = total.saturating_sub(2 * HEADER);
+        let capacity = if capacity >= BLOCK_MIN {
+            unsafe {
+                let block = base.cast::<BlockHeader>();
+                (*block.as_ptr()).prev_phys = None;
+                (*block.as_ptr()).size_and_flags = capacity | FREE;
+                let sentinel = next_phys(block);
+                (*sentinel.as_ptr()).prev_phys = Some(block);
+                (*sentinel.as_ptr()).size_and_flags = 0;
+                control.insert(block);
+            }
+            capacity
+        } else {
+            0
+        };
+
+        Self {
+            _buffer: buffer,
+            base,
+            capacity,
+            control: UnsafeCell::new(control),
+        }
+    }
+
+    /// Returns the size of the largest possible allocation, when nothing is allocated.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the total payload size of all free blocks.
+    #[must_use]
+    pub fn free_bytes(&self) -> usize {
+        self.blocks()
+            .filter(|&block| unsafe { is_free(block) })
+            .map(|block| unsafe { size(block) })
+            .sum()
+    }
+
+    /// Returns the number of separate free blocks.
+    #[must_use]
+    pub fn free_block_count(&self) -> usize {
+        self.blocks()
+            .filter(|&block| unsafe { is_free(block) })
+            .count()
+    }
+
+    /// Iterates over all blocks in address order, excluding the sentinel.
+    fn blocks(&self) -> impl Iterator<Item = Block> + '_ {
+        let first = (self.capacity > 0).then(|| self.base.cast::<BlockHeader>());
+        core::iter::successors(first, |&block| {
+            let next = unsafe { next_phys(block) };
+            (unsafe { size(next) } > 0).then_some(next)
+        })
+    }
+
+    /// Moves the block at `old_ptr` to a fresh allocation for `new_layout`.
+    unsafe fn reallocate(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let new_ptr = self.allocate(new_layout)?;
+        let copy_size = old_layout.size().min(new_layout.size());
+        ptr::copy_nonoverlapping(old_ptr.as_ptr(), new_ptr.as_mut_ptr(), copy_size);
+        self.deallocate(old_ptr, old_layout);
+        Ok(new_ptr)
+    }
+}
+
+unsafe impl Allocator for TlsfAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let control = unsafe { &mut *self.control.get() };
+        let size = adjust_size(layout.size()).ok_or(AllocError)?;
+        let align = layout.align();
+
+        // Over-aligned requests need room to skip ahead to an aligned address, leaving a gap large
+        // enough to become a free block of its own.
+        let gap_min = HEADER + BLOCK_MIN;
+        let search_size = if align > ALIGN {
+            size.checked_add(align)
+                .and_then(|size| size.checked_add(gap_min))
+                .ok_or(AllocError)?
+        } else {
+            size
+        };
+
+        unsafe {
+            let mut block = control.take(search_size).ok_or(AllocError)?;
+
+            if align > ALIGN {
+                let start = payload(block).as_ptr() as usize;
+                let mut aligned = align_up(start, align).ok_or(AllocError)?;
+                if aligned != start && aligned - start < gap_min {
+                    aligned = align_up(start + gap_min, align).ok_or(AllocError)?;
+                }
+                if aligned != start {
+                    let remaining = split(block, aligned - start - HEADER);
+                    control.insert(block);
+                    block = remaining;
+                }
+            }
+
+            if can_split(block, size) {
+                control.insert(split(block, size));
+            }
+            set_free(block, false);
+
+            Ok(NonNull::slice_from_raw_parts(payload(block), layout.size()))
+        }
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, _: Layout) {
+        let control = &mut *self.control.get();
+        let block = from_payload(allocated_ptr);
+        set_free(block, true);
+        let block = control.merge_prev(block);
+        control.merge_next(block);
+        control.insert(block);
+    }
+
+    unsafe fn grow(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let control = &mut *self.control.get();
+        let block = from_payload(old_ptr);
+        let needed = adjust_size(new_layout.size()).ok_or(AllocError)?;
+
+        // Grow in place by taking over the free physical successor when it is large enough.
+        if (old_ptr.as_ptr() as usize).is_multiple_of(new_layout.align()) {
+            let next = next_phys(block);
+            if size(block) >= needed {
+                return Ok(NonNull::slice_from_raw_parts(old_ptr, new_layout.size()));
+            }
+            if is_free(next) && size(block) + HEADER + size(next) >= needed {
+                control.remove(next);
+                absorb(block, next);
+                control.trim_used(block, needed);
+                return Ok(NonNull::slice_from_raw_parts(old_ptr, new_layout.size()));
+            }
+        }
+
+        self.reallocate(old_ptr, old_layout, new_layout)
+    }
+
+    unsafe fn shrink(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        if !(old_ptr.as_ptr() as usize).is_multiple_of(new_layout.align()) {
+            return self.reallocate(old_ptr, old_layout, new_layout);
+        }
+
+        let control = &mut *self.control.get();
+        let needed = adjust_size(new_layout.size()).ok_or(AllocError)?;
+        control.trim_used(from_payload(old_ptr), needed);
+        Ok(NonNull::slice_from_raw_parts(old_ptr, new_layout.size()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `TlsfAllocator` with a generic vector.
+    fn test_generic_vector_with_tlsf_allocator() {
+        let allocator = TlsfAllocator::new(4096);
+        let mut vector: Vec<usize, &TlsfAllocator> = Vec::new_in(&allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+        assert_eq!(allocator.free_bytes(), allocator.capacity());
+    }
+
+    #[test]
+    /// Tests the size class mapping at the boundary between the linear and logarithmic classes.
+    fn test_mapping() {
+        assert_eq!(mapping_insert(0), (0, 0));
+        assert_eq!(mapping_insert(SMALL_BLOCK - ALIGN), (0, SL_COUNT - 1));
+        assert_eq!(mapping_insert(SMALL_BLOCK), (1, 0));
+        assert_eq!(mapping_insert(2 * SMALL_BLOCK - 1), (1, SL_COUNT - 1));
+        assert_eq!(mapping_insert(2 * SMALL_BLOCK), (2, 0));
+        assert_eq!(mapping_search(SMALL_BLOCK + 1), Some((1, 1)));
+        assert_eq!(mapping_insert(usize::MAX).0, FL_COUNT - 1);
+    }
+
+    #[test]
+    /// Tests that allocations respect large alignments and that exhausting the buffer fails.
+    fn test_alignment_and_exhaustion() {
+        let allocator = TlsfAllocator::new(8192);
+
+        for align in [1, 8, 64, 256, 1024] {
+            let layout = Layout::from_size_align(24, align).unwrap();
+            let block = allocator.allocate(layout).unwrap();
+            assert_eq!(block.as_mut_ptr() as usize % align, 0);
+        }
+
+        let huge = Layout::from_size_align(allocator.capacity(), 1).unwrap();
+        assert!(allocator.allocate(huge).is_err());
+    }
+
+    #[test]
+    /// Tests that `grow` extends a block into its free neighbour and `shrink` gives the tail back.
+    fn test_grow_and_shrink_in_place() {
+        let allocator = TlsfAllocator::new(4096);
+        let small = Layout::from_size_align(32, 8).unwrap();
+        let large = Layout::from_size_align(1024, 8).unwrap();
+
+        let block = allocator.allocate(small).unwrap();
+        unsafe { ptr::write_bytes(block.as_mut_ptr(), 0xAB, 32) };
+
+        let grown = unsafe {
+            allocator
+                .grow(block.as_non_null_ptr(), small, large)
+                .unwrap()
+        };
+        assert_eq!(grown.as_mut_ptr(), block.as_mut_ptr());
+        let bytes = unsafe { core::slice::from_raw_parts(grown.as_mut_ptr(), 32) };
+        assert!(bytes.iter().all(|&byte| byte == 0xAB));
+        assert_eq!(allocator.free_bytes(), allocator.capacity() - 1024 - HEADER);
+
+        let shrunk = unsafe {
+            allocator
+                .shrink(grown.as_non_null_ptr(), large, small)
+                .unwrap()
+        };
+        assert_eq!(shrunk.as_mut_ptr(), block.as_mut_ptr());
+        assert_eq!(allocator.free_bytes(), allocator.capacity() - 32 - HEADER);
+        assert_eq!(allocator.free_block_count(), 1);
+
+        unsafe { allocator.deallocate(shrunk.as_non_null_ptr(), small) };
+        assert_eq!(allocator.free_bytes(), allocator.capacity());
+    }
+
+    #[test]
+    /// Tests that `grow` moves the block when its neighbour is in use.
+    fn test_grow_by_moving() {
+        let allocator = TlsfAllocator::new(4096);
+        let small = Layout::from_size_align(32, 8).unwrap();
+        let large = Layout::from_size_align(512, 8).unwrap();
+
+        let block = allocator.allocate(small).unwrap();
+        let neighbour = allocator.allocate(small).unwrap();
+        unsafe { ptr::write_bytes(block.as_mut_ptr(), 0xCD, 32) };
+
+        let grown = unsafe {
+            allocator
+                .grow(block.as_non_null_ptr(), small, large)
+                .unwrap()
+        };
+        assert_ne!(grown.as_mut_ptr(), block.as_mut_ptr());
+        let bytes = unsafe { core::slice::from_raw_parts(grown.as_mut_ptr(), 32) };
+        assert!(bytes.iter().all(|&byte| byte == 0xCD));
+
+        unsafe {
+            allocator.deallocate(neighbour.as_non_null_ptr(), small);
+            allocator.deallocate(grown.as_non_null_ptr(), large);
+        }
+        assert_eq!(allocator.free_bytes(), allocator.capacity());
+        assert_eq!(allocator.free_block_count(), 1);
+    }
+
+    #[test]
+    /// Tests thousands of random allocations and frees, checking the contents of every live block
+    /// and that all memory is free again at the end.
+    fn test_random_stress() {
+        let allocator = TlsfAllocator::new(1 << 16);
+        let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+        let mut next_random = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        let mut live: Vec<(NonNull<[u8]>, Layout, u8)> = Vec::new();
+
+        for step in 0..5000_u32 {
+            let random = next_random();
+            if live.is_empty() || random % 3 != 0 {
+                let size = usize::try_from(random % 700).unwrap();
+                let align = 1 << ((random >> 32) % 8);
+                let layout = Layout::from_size_align(size, align).unwrap();
+                let Ok(block) = allocator.allocate(layout) else {
+                    continue;
+                };
+                assert_eq!(block.as_mut_ptr() as usize % align, 0);
+                let tag = step.to_le_bytes()[0];
+                unsafe { ptr::write_bytes(block.as_mut_ptr(), tag, size) };
+                live.push((block, layout, tag));
+            } else {
+                let index = usize::try_from(random >> 16).unwrap() % live.len();
+                let (block, layout, tag) = live.swap_remove(index);
+                let bytes =
+                    unsafe { core::slice::from_raw_parts(block.as_mut_ptr(), layout.size()) };
+                assert!(bytes.iter().all(|&byte| byte == tag));
+                unsafe { allocator.deallocate(block.as_non_null_ptr(), layout) };
+            }
+        }
+
+        for (block, layout, tag) in live {
+            let bytes = unsafe { core::slice::from_raw_parts(block.as_mut_ptr(), layout.size()) };
+            assert!(bytes.iter().all(|&byte| byte == tag));
+            unsafe { allocator.deallocate(block.as_non_null_ptr(), layout) };
+        }
+
+        assert_eq!(allocator.free_bytes(), allocator.capacity());
+        assert_eq!(allocator.free_block_count(), 1);
+    }
+}
@@ -0,0 +1,136 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    ptr::NonNull,
+};
+
+/// An allocation or deallocation observed by a [`TracingAllocator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AllocEvent {
+    /// A block was handed out by the inner allocator.
+    Allocate {
+        /// The requested size in bytes.
+        size: usize,
+        /// The requested alignment in bytes.
+        align: usize,
+        /// The start of the block.
+        ptr: NonNull<u8>,
+    },
+    /// A block is about to be returned to the inner allocator.
+    Deallocate {
+        /// The start of the block.
+        ptr: NonNull<u8>,
+        /// The size the block was allocated with.
+        size: usize,
+        /// The alignment the block was allocated with.
+        align: usize,
+    },
+}
+
+/// A wrapper allocator that reports every allocation and deallocation to a callback.
+///
+/// The callback is a plain `fn` pointer rather than a closure so the wrapper needs no heap and
+/// works in `no_std` code; use statics to collect state. Deallocations are reported before they
+/// are forwarded, allocations once the inner allocator has produced a pointer. Resizing goes
+/// through the default `Allocator` methods, so it shows up as an allocation followed by a
+/// deallocation.
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use std::{
+///     alloc::Global,
+///     sync::atomic::{AtomicUsize, Ordering},
+/// };
+///
+/// use mem_allocs::tracing_allocator::{AllocEvent, TracingAllocator};
+///
+/// static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+///
+/// fn count(event: AllocEvent) {
+///     if let AllocEvent::Allocate { .. } = event {
+///         ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+///
+/// let allocator = TracingAllocator::new(Global, count);
+/// let boxed = Box::new_in(42, &allocator);
+/// drop(boxed);
+/// assert_eq!(ALLOCATIONS.load(Ordering::Relaxed), 1);
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[allow(clippy::module_name_repetitions)]
+pub struct TracingAllocator<A: Allocator> {
+    inner: A,
+    callback: fn(AllocEvent),
+}
+
+impl<A: Allocator> TracingAllocator<A> {
+    /// Wraps `inner` so that every allocation and deallocation is reported to `callback`.
+    pub const fn new(inner: A, callback: fn(AllocEvent)) -> Self {
+        Self { inner, callback }
+    }
+
+    /// Returns a reference to the wrapped allocator.
+    pub const fn inner(&self) -> &A {
+        &self.inner
+    }
+}
+
+unsafe impl<A: Allocator> Allocator for TracingAllocator<A> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let allocated_ptr = self.inner.allocate(layout)?;
+        (self.callback)(AllocEvent::Allocate {
+            size: layout.size(),
+            align: layout.align(),
+            ptr: allocated_ptr.as_non_null_ptr(),
+        });
+        Ok(allocated_ptr)
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        (self.callback)(AllocEvent::Deallocate {
+            ptr: allocated_ptr,
+            size: layout.size(),
+            align: layout.align(),
+        });
+        self.inner.deallocate(allocated_ptr, layout);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{alloc::Global, vec::Vec};
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+    static DEALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+    /// Sums up the bytes of every event.
+    fn record(event: AllocEvent) {
+        match event {
+            AllocEvent::Allocate { size, .. } => ALLOCATED.fetch_add(size, Ordering::SeqCst),
+            AllocEvent::Deallocate { size, .. } => DEALLOCATED.fetch_add(size, Ordering::SeqCst),
+        };
+    }
+
+    #[test]
+    /// Tests the `TracingAllocator` with a generic vector, checking that every byte is reported.
+    fn test_generic_vector_with_tracing_allocator() {
+        let allocator = TracingAllocator::new(Global, record);
+        let mut vector: Vec<usize, TracingAllocator<Global>> = Vec::new_in(allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+
+        let allocated = ALLOCATED.load(Ordering::SeqCst);
+        assert!(allocated >= 100 * core::mem::size_of::<usize>());
+        assert_eq!(DEALLOCATED.load(Ordering::SeqCst), allocated);
+    }
+}
@@ -0,0 +1,170 @@
+use core::{
+    alloc::{AllocError, Allocator, GlobalAlloc, Layout},
+    arch::wasm32,
+    hint,
+    ptr::{self, NonNull},
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+/// Size of a WebAssembly page in bytes.
+pub const WASM_PAGE_SIZE: usize = 64 * 1024;
+
+/// Address of the next free byte of the heap.
+static HEAP_NEXT: AtomicUsize = AtomicUsize::new(0);
+
+/// End of the pages the heap has claimed with `memory.grow`, or zero before the first allocation.
+static HEAP_END: AtomicUsize = AtomicUsize::new(0);
+
+/// Held while the heap is updated, since growing memory and moving both ends is not one atomic
+/// step.
+static HEAP_LOCK: AtomicBool = AtomicBool::new(false);
+
+/// A bump allocator for bare `wasm32` targets that grows linear memory with `memory.grow`.
+///
+/// There is no `malloc` on `wasm32-unknown-unknown` without `wasm-bindgen` or Emscripten, so this
+/// allocator manages linear memory itself. The heap starts at the end of the memory the module
+/// was instantiated with and grows by whole pages as needed. The heap only uses pages claimed by
+/// its own `memory.grow` calls, so memory grown by other code is never handed out. Linear memory
+/// can never shrink, so deallocation is a no-op and there is no `reset`; the allocator suits
+/// short-lived or allocate-once modules.
+///
+/// All instances share one heap, so the allocator is usually installed as the global allocator
+/// of a wasm module:
+///
+/// ```no_run
+/// use mem_allocs::wasm_allocator::WasmAllocator;
+///
+/// #[global_allocator]
+/// static ALLOCATOR: WasmAllocator = WasmAllocator;
+///
+/// #[no_mangle]
+/// pub extern "C" fn sum(count: u32) -> u32 {
+///     let values: Vec<u32> = (0..count).collect();
+///     values.iter().sum()
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+#[allow(clippy::module_name_repetitions)]
+pub struct WasmAllocator;
+
+/// Runs `f` while holding the heap lock.
+fn with_heap_locked<R>(f: impl FnOnce() -> R) -> R {
+    while HEAP_LOCK
+        .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+        .is_err()
+    {
+        hint::spin_loop();
+    }
+    let result = f();
+    HEAP_LOCK.store(false, Ordering::Release);
+    result
+}
+
+/// Claims at least `bytes` more bytes of linear memory and returns the old end of memory, where
+/// the claimed pages start, together with the new end.
+///
+/// # Errors
+///
+/// Returns an `AllocError` if the host refuses to grow the memory.
+fn grow_memory(bytes: usize) -> Result<(usize, usize), AllocError> {
+    let pages = bytes.div_ceil(WASM_PAGE_SIZE).max(1);
+    let old_pages = wasm32::memory_grow(0, pages);
+    if old_pages == usize::MAX {
+        return Err(AllocError);
+    }
+    let start = old_pages.checked_mul(WASM_PAGE_SIZE).ok_or(AllocError)?;
+    let end = (old_pages + pages)
+        .checked_mul(WASM_PAGE_SIZE)
+        .ok_or(AllocError)?;
+    Ok((start, end))
+}
+
+unsafe impl Allocator for WasmAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        with_heap_locked(|| {
+            let mut next = HEAP_NEXT.load(Ordering::Relaxed);
+            let mut end = HEAP_END.load(Ordering::Relaxed);
+
+            loop {
+                let start = next
+                    .checked_next_multiple_of(layout.align())
+                    .ok_or(AllocError)?;
+                let block_end = start.checked_add(layout.size()).ok_or(AllocError)?;
+                if end != 0 && block_end <= end {
+                    HEAP_NEXT.store(block_end, Ordering::Relaxed);
+                    let allocated_ptr = ptr::with_exposed_provenance_mut::<u8>(start);
+                    return NonNull::new(ptr::slice_from_raw_parts_mut(
+                        allocated_ptr,
+                        layout.size(),
+                    ))
+                    .ok_or(AllocError);
+                }
+
+                // The claimed pages start at the old end of memory, which only continues the heap
+                // if nothing else grew the memory since the heap last did. Otherwise the heap
+                // moves to the new pages and the rest of the old ones is abandoned.
+                let (grown, grown_end) = grow_memory(block_end.saturating_sub(end))?;
+                if end == 0 || grown != end {
+                    next = grown;
+                }
+                end = grown_end;
+                HEAP_NEXT.store(next, Ordering::Relaxed);
+                HEAP_END.store(end, Ordering::Relaxed);
+            }
+        })
+    }
+
+    /// Every byte of the heap comes from `memory.grow`, which zeroes new pages, and is handed out
+    /// only once, so the memory is already zeroed.
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {}
+}
+
+unsafe impl GlobalAlloc for WasmAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.allocate(layout)
+            .map_or(ptr::null_mut(), NonNull::as_mut_ptr)
+    }
+
+    /// The heap only ever holds fresh pages, so the memory is already zeroed.
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        self.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, _: *mut u8, _: Layout) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `WasmAllocator` with a generic vector.
+    fn test_generic_vector_with_wasm_allocator() {
+        let mut vector: Vec<usize, WasmAllocator> = Vec::with_capacity_in(100, WasmAllocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    /// Tests that allocations beyond the current memory grow it and come back zeroed.
+    fn test_grows_memory() {
+        let before = wasm32::memory_size(0);
+        let layout = Layout::from_size_align(2 * WASM_PAGE_SIZE, 16).unwrap();
+        let block = WasmAllocator.allocate_zeroed(layout).unwrap();
+
+        assert!(wasm32::memory_size(0) > before);
+        assert!(unsafe { block.as_ref() }.iter().all(|&byte| byte == 0));
+    }
+}
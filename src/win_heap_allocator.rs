@@ -0,0 +1,142 @@
+use core::{
+    alloc::{AllocError, Allocator, GlobalAlloc, Layout},
+    cmp, mem,
+    ptr::{self, NonNull},
+};
+
+use winapi::{
+    ctypes::c_void,
+    um::{
+        heapapi::{GetProcessHeap, HeapAlloc, HeapFree, HeapReAlloc},
+        winnt::HEAP_ZERO_MEMORY,
+    },
+};
+
+/// Alignment that `HeapAlloc` already guarantees (`MEMORY_ALLOCATION_ALIGNMENT`).
+const MIN_ALIGN: usize = 2 * mem::size_of::<usize>();
+
+/// A custom memory allocator that interfaces with the Windows process heap (`HeapAlloc`/`HeapFree`).
+///
+/// Alignments above what the heap guarantees are handled by over-allocating and storing the
+/// pointer returned by `HeapAlloc` just in front of the aligned pointer handed out.
+#[derive(Debug, Default, Clone, Copy)]
+#[allow(clippy::module_name_repetitions)]
+pub struct WinHeapAllocator;
+
+unsafe impl Allocator for WinHeapAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let allocated_ptr = unsafe { heap_allocate(layout, 0) };
+        NonNull::new(ptr::slice_from_raw_parts_mut(allocated_ptr, layout.size())).ok_or(AllocError)
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let allocated_ptr = unsafe { heap_allocate(layout, HEAP_ZERO_MEMORY) };
+        NonNull::new(ptr::slice_from_raw_parts_mut(allocated_ptr, layout.size())).ok_or(AllocError)
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        heap_free(allocated_ptr.as_ptr(), layout);
+    }
+}
+
+unsafe impl GlobalAlloc for WinHeapAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        heap_allocate(layout, 0)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        heap_allocate(layout, HEAP_ZERO_MEMORY)
+    }
+
+    unsafe fn dealloc(&self, allocated_ptr: *mut u8, layout: Layout) {
+        heap_free(allocated_ptr, layout);
+    }
+
+    unsafe fn realloc(&self, old_ptr: *mut u8, old_layout: Layout, new_size: usize) -> *mut u8 {
+        if old_layout.align() <= MIN_ALIGN {
+            return HeapReAlloc(GetProcessHeap(), 0, old_ptr.cast::<c_void>(), new_size)
+                .cast::<u8>();
+        }
+
+        let new_layout = Layout::from_size_align_unchecked(new_size, old_layout.align());
+        let new_ptr = self.alloc(new_layout);
+        if !new_ptr.is_null() {
+            let copy_size = cmp::min(old_layout.size(), new_size);
+            ptr::copy_nonoverlapping(old_ptr, new_ptr, copy_size);
+            self.dealloc(old_ptr, old_layout);
+        }
+        new_ptr
+    }
+}
+
+/// Allocates memory for `layout` from the process heap, passing `flags` to `HeapAlloc`.
+///
+/// Returns a null pointer if the allocation fails.
+#[allow(clippy::cast_ptr_alignment)] // The aligned pointer is at least `MIN_ALIGN`-aligned.
+unsafe fn heap_allocate(layout: Layout, flags: u32) -> *mut u8 {
+    let heap = GetProcessHeap();
+    if layout.align() <= MIN_ALIGN {
+        return HeapAlloc(heap, flags, layout.size()).cast::<u8>();
+    }
+
+    let Some(padded_size) = layout.size().checked_add(layout.align()) else {
+        return ptr::null_mut();
+    };
+    let raw_ptr = HeapAlloc(heap, flags, padded_size).cast::<u8>();
+    if raw_ptr.is_null() {
+        return raw_ptr;
+    }
+
+    // `HeapAlloc` returns at least `MIN_ALIGN`-aligned memory, so there are always at least
+    // `MIN_ALIGN` bytes of padding in front of the aligned pointer to hold the original pointer.
+    let offset = layout.align() - (raw_ptr as usize & (layout.align() - 1));
+    let aligned_ptr = raw_ptr.add(offset);
+    aligned_ptr.cast::<*mut u8>().sub(1).write(raw_ptr);
+    aligned_ptr
+}
+
+/// Returns memory obtained from [`heap_allocate`] with the same `layout` to the process heap.
+#[allow(clippy::cast_ptr_alignment)] // The aligned pointer is at least `MIN_ALIGN`-aligned.
+unsafe fn heap_free(allocated_ptr: *mut u8, layout: Layout) {
+    let raw_ptr = if layout.align() <= MIN_ALIGN {
+        allocated_ptr
+    } else {
+        allocated_ptr.cast::<*mut u8>().sub(1).read()
+    };
+    HeapFree(GetProcessHeap(), 0, raw_ptr.cast::<c_void>());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    /// Tests the `WinHeapAllocator` with a generic vector.
+    fn test_generic_vector_with_win_heap_allocator() {
+        let allocator = WinHeapAllocator;
+        let mut vector: Vec<usize, WinHeapAllocator> = Vec::with_capacity_in(100, allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+
+        assert_eq!(vector.len(), 100);
+        for (expected_index, actual_value) in vector.into_iter().enumerate().take(100) {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+
+    #[test]
+    /// Tests that over-aligned and zeroed allocations honor their layout.
+    fn test_aligned_zeroed_allocation() {
+        let layout = Layout::from_size_align(256, 256).unwrap();
+        let allocated_ptr = WinHeapAllocator.allocate_zeroed(layout).unwrap();
+
+        assert_eq!(allocated_ptr.as_mut_ptr() as usize % 256, 0);
+        let bytes = unsafe { core::slice::from_raw_parts(allocated_ptr.as_mut_ptr(), 256) };
+        assert!(bytes.iter().all(|&byte| byte == 0));
+
+        unsafe { WinHeapAllocator.deallocate(allocated_ptr.as_non_null_ptr(), layout) };
+    }
+}
@@ -0,0 +1,151 @@
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    cmp,
+    ptr::{self, NonNull},
+    sync::atomic::{compiler_fence, Ordering},
+};
+
+/// A wrapper allocator that wipes memory with zeros before handing it back to the inner allocator.
+///
+/// This keeps secrets such as key material from lingering in freed memory.
+#[derive(Debug, Default, Clone, Copy)]
+#[allow(clippy::module_name_repetitions)]
+pub struct ZeroizingAllocator<A: Allocator> {
+    inner: A,
+}
+
+impl<A: Allocator> ZeroizingAllocator<A> {
+    /// Wraps `inner` so that every block is zeroed before it is freed.
+    pub const fn new(inner: A) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the wrapped allocator.
+    pub const fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Moves the contents of the old block into `new_ptr`, then wipes and frees the old block.
+    ///
+    /// Resizing is never delegated to the inner allocator, because it would release the old block
+    /// without wiping it whenever it has to move the data.
+    unsafe fn reallocate(
+        &self,
+        new_ptr: NonNull<[u8]>,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+    ) -> NonNull<[u8]> {
+        let copy_size = cmp::min(old_layout.size(), new_ptr.len());
+        ptr::copy_nonoverlapping(old_ptr.as_ptr(), new_ptr.as_mut_ptr(), copy_size);
+        self.deallocate(old_ptr, old_layout);
+        new_ptr
+    }
+}
+
+/// Overwrites `len` bytes starting at `start` with zeros.
+///
+/// The writes are volatile, so the compiler cannot treat them as dead stores and elide them even
+/// though the memory is freed right after. The fence keeps them from being moved past the
+/// deallocation.
+unsafe fn zeroize(start: *mut u8, len: usize) {
+    for offset in 0..len {
+        ptr::write_volatile(start.add(offset), 0);
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+unsafe impl<A: Allocator> Allocator for ZeroizingAllocator<A> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.inner.allocate(layout)
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.inner.allocate_zeroed(layout)
+    }
+
+    unsafe fn deallocate(&self, allocated_ptr: NonNull<u8>, layout: Layout) {
+        zeroize(allocated_ptr.as_ptr(), layout.size());
+        self.inner.deallocate(allocated_ptr, layout);
+    }
+
+    unsafe fn grow(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        Ok(self.reallocate(self.inner.allocate(new_layout)?, old_ptr, old_layout))
+    }
+
+    unsafe fn grow_zeroed(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        Ok(self.reallocate(self.inner.allocate_zeroed(new_layout)?, old_ptr, old_layout))
+    }
+
+    unsafe fn shrink(
+        &self,
+        old_ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        // The old block, including the cut-off tail, is wiped before it is released.
+        Ok(self.reallocate(self.inner.allocate(new_layout)?, old_ptr, old_layout))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "arena_allocator")]
+    /// Tests that freed memory reads back as zeros when the same address is handed out again.
+    fn test_deallocate_zeroes_memory() {
+        use crate::arena_allocator::ArenaAllocator;
+
+        let mut arena = ArenaAllocator::new(64);
+        let layout = Layout::from_size_align(32, 1).unwrap();
+
+        let allocator = ZeroizingAllocator::new(&arena);
+        let first = allocator.allocate(layout).unwrap();
+        unsafe {
+            ptr::write_bytes(first.as_mut_ptr(), 0xAB, layout.size());
+            allocator.deallocate(first.as_non_null_ptr(), layout);
+        }
+
+        arena.reset();
+        let allocator = ZeroizingAllocator::new(&arena);
+        let second = allocator.allocate(layout).unwrap();
+        assert_eq!(first.as_mut_ptr(), second.as_mut_ptr());
+
+        let bytes = unsafe { core::slice::from_raw_parts(second.as_mut_ptr(), layout.size()) };
+        assert!(bytes.iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    #[cfg(feature = "c_allocator")]
+    /// Tests the `ZeroizingAllocator` with a generic vector.
+    fn test_generic_vector_with_zeroizing_allocator() {
+        use crate::c_allocator::CAllocator;
+        use alloc::vec::Vec;
+
+        let allocator = ZeroizingAllocator::new(CAllocator);
+        let mut vector: Vec<usize, ZeroizingAllocator<CAllocator>> =
+            Vec::with_capacity_in(100, allocator);
+
+        for index in 0..100 {
+            vector.push(index);
+        }
+        vector.truncate(10);
+        vector.shrink_to_fit();
+
+        assert_eq!(vector.len(), 10);
+        for (expected_index, actual_value) in vector.into_iter().enumerate() {
+            assert_eq!(actual_value, expected_index);
+        }
+    }
+}
@@ -0,0 +1,14 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "allocator_pool")]
+fn allocator_pool() {
+    use common::test_allocator;
+    use mem_allocs::allocator_pool::LinearAllocatorPool;
+
+    let pool = LinearAllocatorPool::new(2, 16 * 1024);
+    let arena = pool.checkout().unwrap();
+    test_allocator(&*arena).unwrap();
+}
@@ -0,0 +1,23 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "arena_allocator")]
+fn arena_allocator() {
+    use common::test_allocator;
+    use mem_allocs::arena_allocator::ArenaAllocator;
+
+    test_allocator(ArenaAllocator::new(1024)).unwrap();
+}
+
+#[test]
+#[cfg(feature = "arena_allocator")]
+fn arena_allocator_view() {
+    use common::test_allocator;
+    use core::mem::MaybeUninit;
+    use mem_allocs::arena_allocator::ArenaAllocator;
+
+    let mut buffer = [MaybeUninit::uninit(); 1024];
+    test_allocator(ArenaAllocator::from_slice(&mut buffer)).unwrap();
+}
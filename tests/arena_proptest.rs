@@ -0,0 +1,61 @@
+#![cfg(feature = "arena_allocator")]
+#![feature(allocator_api, slice_ptr_get)]
+
+use std::alloc::{Allocator, Layout};
+
+use mem_allocs::arena_allocator::ArenaAllocator;
+use proptest::prelude::*;
+
+const CAPACITY: usize = 64 * 1024;
+
+/// Layouts with sizes in `1..4096` and power-of-two alignments up to 64.
+fn layouts() -> impl Strategy<Value = Vec<Layout>> {
+    prop::collection::vec(
+        (1..4096_usize, 0..=6_u32)
+            .prop_map(|(size, shift)| Layout::from_size_align(size, 1 << shift).unwrap()),
+        1..64,
+    )
+}
+
+/// Allocates every layout in turn, returning the start offset and size of each allocation that
+/// succeeded.
+fn run(arena: &ArenaAllocator, layouts: &[Layout]) -> Vec<Option<(usize, usize)>> {
+    // A zero-sized allocation from an empty arena points at the start of its buffer.
+    let base = arena.allocate(Layout::new::<()>()).unwrap().as_mut_ptr() as usize;
+    layouts
+        .iter()
+        .map(|&layout| {
+            let block = arena.allocate(layout).ok()?;
+            assert!(arena.offset() <= arena.capacity());
+            let start = block.as_mut_ptr() as usize;
+            assert_eq!(start % layout.align(), 0);
+            Some((start - base, layout.size()))
+        })
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn allocations_never_overlap(layouts in layouts()) {
+        let arena = ArenaAllocator::new(CAPACITY);
+        let mut blocks: Vec<(usize, usize)> = run(&arena, &layouts).into_iter().flatten().collect();
+
+        blocks.sort_unstable();
+        for pair in blocks.windows(2) {
+            prop_assert!(pair[0].0 + pair[0].1 <= pair[1].0, "{:?} overlaps {:?}", pair[0], pair[1]);
+        }
+        prop_assert!(arena.offset() <= arena.capacity());
+    }
+
+    #[test]
+    fn reset_replays_identically(layouts in layouts()) {
+        let mut arena = ArenaAllocator::new(CAPACITY);
+        let first = run(&arena, &layouts);
+        let offset = arena.offset();
+
+        arena.reset();
+        let second = run(&arena, &layouts);
+        prop_assert_eq!(first, second);
+        prop_assert_eq!(arena.offset(), offset);
+    }
+}
@@ -0,0 +1,14 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "bitmap_allocator")]
+fn bitmap_allocator() {
+    use common::test_allocator;
+    use mem_allocs::bitmap_allocator::BitmapAllocator;
+
+    let allocator = BitmapAllocator::new(1024, 8);
+    test_allocator(&allocator).unwrap();
+    assert_eq!(allocator.used_blocks(), 0);
+}
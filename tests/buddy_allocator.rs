@@ -0,0 +1,14 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "buddy_allocator")]
+fn buddy_allocator() {
+    use common::test_allocator;
+    use mem_allocs::buddy_allocator::BuddyAllocator;
+
+    let allocator = BuddyAllocator::new(4096, 16);
+    test_allocator(&allocator).unwrap();
+    assert_eq!(allocator.free_blocks_at_order(12), 1);
+}
@@ -19,3 +19,22 @@ fn raw_c_allocator() {
 
     test_allocator(RawCAllocator).unwrap();
 }
+
+#[test]
+#[cfg(feature = "c_allocator")]
+fn zero_size_layouts() {
+    use mem_allocs::c_allocator::{CAllocator, RawCAllocator};
+    use std::alloc::{Allocator, Layout};
+
+    fn check<A: Allocator>(allocator: &A) {
+        for layout in [Layout::new::<()>(), Layout::from_size_align(0, 64).unwrap()] {
+            let block = allocator.allocate(layout).unwrap();
+            assert_eq!(block.len(), 0);
+            assert!((block.as_mut_ptr() as usize).is_multiple_of(layout.align()));
+            unsafe { allocator.deallocate(block.as_non_null_ptr(), layout) };
+        }
+    }
+
+    check(&CAllocator);
+    check(&RawCAllocator);
+}
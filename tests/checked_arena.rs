@@ -0,0 +1,13 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "checked_arena")]
+fn checked_arena() {
+    use common::test_allocator;
+    use mem_allocs::checked_arena::CheckedArenaAllocator;
+
+    let allocator = CheckedArenaAllocator::new(16 * 1024);
+    test_allocator(&allocator).unwrap();
+}
@@ -0,0 +1,13 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "chunk_arena")]
+fn chunk_arena() {
+    use common::test_allocator;
+    use mem_allocs::chunk_arena::ChunkArenaAllocator;
+
+    let allocator = ChunkArenaAllocator::new(1024);
+    test_allocator(&allocator).unwrap();
+}
@@ -24,6 +24,8 @@ use std::{
 /// test_allocator(allocator).expect("Allocator test failed");
 /// ```
 #[allow(clippy::cast_ptr_alignment)] // This is to allow casting raw pointers with non-aligned types.
+#[allow(dead_code)] // Test crates whose feature is disabled do not call this.
+#[allow(clippy::manual_assert_eq)] // Keeps the original asserts; the lint is newer than this helper.
 pub fn test_allocator<A: Allocator>(allocator: A) -> Result<(), Box<dyn Error>> {
     // Allocate memory for 100 `i32` elements
     let allocation_layout = Layout::array::<i32>(100)?;
@@ -0,0 +1,12 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(all(feature = "debug_allocator", feature = "c_allocator"))]
+fn debug_allocator() {
+    use common::test_allocator;
+    use mem_allocs::{c_allocator::CAllocator, debug_allocator::DebugAllocator};
+
+    test_allocator(DebugAllocator::new(CAllocator)).unwrap();
+}
@@ -0,0 +1,24 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(all(
+    feature = "fallback_allocator",
+    feature = "arena_allocator",
+    feature = "c_allocator"
+))]
+fn fallback_allocator() {
+    use common::test_allocator;
+    use mem_allocs::{
+        arena_allocator::ArenaAllocator, c_allocator::CAllocator,
+        fallback_allocator::FallbackAllocator,
+    };
+
+    test_allocator(FallbackAllocator::new(
+        ArenaAllocator::new(1024),
+        CAllocator,
+    ))
+    .unwrap();
+    test_allocator(FallbackAllocator::new(ArenaAllocator::new(16), CAllocator)).unwrap();
+}
@@ -0,0 +1,12 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "fixed_capacity_arena")]
+fn fixed_capacity_arena() {
+    use common::test_allocator;
+    use mem_allocs::fixed_capacity_arena::FixedCapacityArena;
+
+    test_allocator(&FixedCapacityArena::<1024>::new()).unwrap();
+}
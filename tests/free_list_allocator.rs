@@ -0,0 +1,22 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "free_list_allocator")]
+fn free_list_allocator() {
+    use common::test_allocator;
+    use mem_allocs::free_list_allocator::{FreeListAllocator, SearchStrategy};
+
+    for strategy in [
+        SearchStrategy::FirstFit,
+        SearchStrategy::BestFit,
+        SearchStrategy::WorstFit,
+        SearchStrategy::NextFit,
+    ] {
+        let allocator = FreeListAllocator::with_strategy(4096, strategy);
+        test_allocator(&allocator).unwrap();
+        assert_eq!(allocator.free_bytes(), allocator.capacity());
+        assert_eq!(allocator.free_block_count(), 1);
+    }
+}
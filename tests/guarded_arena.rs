@@ -0,0 +1,14 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "guarded_arena")]
+fn guarded_arena() {
+    use common::test_allocator;
+    use mem_allocs::guarded_arena::GuardedArenaAllocator;
+
+    let allocator = GuardedArenaAllocator::new(32 * 1024);
+    test_allocator(&allocator).unwrap();
+    allocator.check_canaries();
+}
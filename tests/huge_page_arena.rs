@@ -0,0 +1,12 @@
+#![cfg(all(feature = "huge_page", target_os = "linux"))]
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+fn huge_page_arena() {
+    use common::test_allocator;
+    use mem_allocs::huge_page_arena::HugePageArena;
+
+    test_allocator(HugePageArena::new(1024).unwrap()).unwrap();
+}
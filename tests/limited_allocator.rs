@@ -0,0 +1,14 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(all(feature = "limited_allocator", feature = "c_allocator"))]
+fn limited_allocator() {
+    use common::test_allocator;
+    use mem_allocs::{c_allocator::CAllocator, limited_allocator::LimitedAllocator};
+
+    let allocator = LimitedAllocator::new(CAllocator, 1024);
+    test_allocator(&allocator).unwrap();
+    assert_eq!(allocator.in_use(), 0);
+}
@@ -0,0 +1,13 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "linked_arena")]
+fn linked_arena() {
+    use common::test_allocator;
+    use mem_allocs::linked_arena::LinkedArenaAllocator;
+
+    test_allocator(LinkedArenaAllocator::new(1024)).unwrap();
+    test_allocator(LinkedArenaAllocator::new(64)).unwrap();
+}
@@ -0,0 +1,16 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "memory_budget")]
+fn memory_budget() {
+    use common::test_allocator;
+    use mem_allocs::{arena_allocator::ArenaAllocator, memory_budget::MemoryBudget};
+
+    let arena = ArenaAllocator::new(32 * 1024);
+    let budget = MemoryBudget::new(&arena, &[("audio", 8 * 1024), ("physics", 8 * 1024)]).unwrap();
+    for (_, subsystem) in budget.iter() {
+        test_allocator(subsystem).unwrap();
+    }
+}
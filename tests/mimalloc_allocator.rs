@@ -0,0 +1,12 @@
+#![cfg(feature = "mimalloc")]
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+fn mimalloc_allocator() {
+    use common::test_allocator;
+    use mem_allocs::mimalloc_allocator::MiMallocAllocator;
+
+    test_allocator(MiMallocAllocator).unwrap();
+}
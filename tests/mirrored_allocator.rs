@@ -0,0 +1,17 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(all(feature = "mirrored_allocator", feature = "c_allocator"))]
+fn mirrored_allocator() {
+    use common::test_allocator;
+    use mem_allocs::{
+        c_allocator::{CAllocator, RawCAllocator},
+        mirrored_allocator::MirroredAllocator,
+    };
+
+    let allocator = MirroredAllocator::new(CAllocator, RawCAllocator);
+    test_allocator(&allocator).unwrap();
+    assert_eq!(allocator.live_allocations(), 0);
+}
@@ -0,0 +1,15 @@
+#![cfg(all(
+    feature = "mmap_allocator",
+    any(target_os = "linux", target_os = "macos")
+))]
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+fn mmap_allocator() {
+    use common::test_allocator;
+    use mem_allocs::mmap_allocator::MmapAllocator;
+
+    test_allocator(MmapAllocator).unwrap();
+}
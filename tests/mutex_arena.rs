@@ -0,0 +1,27 @@
+#![cfg(feature = "mutex_arena")]
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+use mem_allocs::mutex_arena::MutexArenaAllocator;
+
+#[global_allocator]
+static GLOBAL: MutexArenaAllocator = MutexArenaAllocator::new(64 * 1024 * 1024);
+
+#[test]
+fn mutex_arena() {
+    let allocator = MutexArenaAllocator::new(16 * 1024);
+    common::test_allocator(&allocator).unwrap();
+}
+
+#[test]
+fn mutex_arena_as_global_allocator() {
+    let before = GLOBAL.used();
+    let handles: Vec<_> = (0..4)
+        .map(|index| std::thread::spawn(move || vec![index; 1000]))
+        .collect();
+    for (index, handle) in handles.into_iter().enumerate() {
+        assert_eq!(handle.join().unwrap(), vec![index; 1000]);
+    }
+    assert!(GLOBAL.used() > before);
+}
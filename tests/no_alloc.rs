@@ -0,0 +1,43 @@
+//! Checks that the allocators without heap-backed state work in a `no_std` crate that does not
+//! link the `alloc` crate. Run with
+//! `cargo test --no-default-features --features fixed_capacity_arena,c_allocator --test no_alloc`.
+
+#![cfg(all(
+    feature = "fixed_capacity_arena",
+    feature = "c_allocator",
+    not(feature = "alloc")
+))]
+#![no_std]
+#![feature(allocator_api)]
+
+use core::alloc::{Allocator, Layout};
+
+use mem_allocs::{
+    c_allocator::{CAllocator, RawCAllocator},
+    fixed_capacity_arena::FixedCapacityArena,
+};
+
+/// Allocates an array of `u32`s from `allocator`, writes it, reads it back and frees it.
+fn round_trip<A: Allocator>(allocator: A) {
+    let layout = Layout::array::<u32>(16).unwrap();
+    let block = allocator.allocate(layout).unwrap().cast::<u32>();
+    for index in 0..16 {
+        unsafe { block.add(index).write(index as u32) };
+    }
+    for index in 0..16 {
+        assert_eq!(unsafe { block.add(index).read() }, index as u32);
+    }
+    unsafe { allocator.deallocate(block.cast(), layout) };
+}
+
+#[test]
+fn fixed_capacity_arena() {
+    let arena = FixedCapacityArena::<256>::new();
+    round_trip(&arena);
+}
+
+#[test]
+fn c_allocators() {
+    round_trip(&CAllocator);
+    round_trip(&RawCAllocator);
+}
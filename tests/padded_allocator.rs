@@ -0,0 +1,12 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(all(feature = "padded_allocator", feature = "c_allocator"))]
+fn padded_allocator() {
+    use common::test_allocator;
+    use mem_allocs::{c_allocator::CAllocator, padded_allocator::PaddedAllocator};
+
+    test_allocator(PaddedAllocator::<_, 1>::new(CAllocator)).unwrap();
+}
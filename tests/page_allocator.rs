@@ -0,0 +1,15 @@
+#![cfg(all(
+    feature = "page_allocator",
+    any(target_os = "linux", target_os = "macos", target_os = "windows")
+))]
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+fn page_allocator() {
+    use common::test_allocator;
+    use mem_allocs::page_allocator::PageAllocator;
+
+    test_allocator(PageAllocator).unwrap();
+}
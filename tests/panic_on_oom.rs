@@ -0,0 +1,12 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(all(feature = "panic_on_oom", feature = "c_allocator"))]
+fn panic_on_oom() {
+    use common::test_allocator;
+    use mem_allocs::{c_allocator::CAllocator, panic_on_oom::PanicOnOomAllocator};
+
+    test_allocator(PanicOnOomAllocator::new(CAllocator)).unwrap();
+}
@@ -0,0 +1,12 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "pool_allocator")]
+fn pool_allocator() {
+    use common::test_allocator;
+    use mem_allocs::pool_allocator::PoolAllocator;
+
+    test_allocator(PoolAllocator::new(1024, 8)).unwrap();
+}
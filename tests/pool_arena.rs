@@ -0,0 +1,14 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "pool_arena")]
+fn pool_arena() {
+    use common::test_allocator;
+    use mem_allocs::{arena_allocator::ArenaAllocator, pool_arena::PoolArena};
+
+    let arena = ArenaAllocator::new(8192);
+    let pools = PoolArena::new(&arena, &[(64, 16), (1024, 4)]).unwrap();
+    test_allocator(&pools).unwrap();
+}
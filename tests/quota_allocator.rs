@@ -0,0 +1,17 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(all(feature = "quota_allocator", feature = "c_allocator"))]
+fn quota_allocator() {
+    use common::test_allocator;
+    use mem_allocs::{
+        c_allocator::CAllocator,
+        quota_allocator::{global_usage, QuotaAllocator},
+    };
+
+    let allocator = QuotaAllocator::new(CAllocator, "integration", 1024);
+    test_allocator(&allocator).unwrap();
+    assert_eq!(global_usage("integration"), 0);
+}
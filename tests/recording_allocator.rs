@@ -0,0 +1,15 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "recording_allocator")]
+fn recording_allocator() {
+    use common::test_allocator;
+    use mem_allocs::recording_allocator::RecordingAllocator;
+    use std::alloc::Global;
+
+    let allocator = RecordingAllocator::<Global, 8>::new(Global);
+    test_allocator(&allocator).unwrap();
+    assert!(allocator.total_events() > 0);
+}
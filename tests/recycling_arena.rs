@@ -0,0 +1,12 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "recycling_arena")]
+fn recycling_arena() {
+    use common::test_allocator;
+    use mem_allocs::recycling_arena::RecyclingArena;
+
+    test_allocator(RecyclingArena::new(1024)).unwrap();
+}
@@ -0,0 +1,14 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "region")]
+fn region() {
+    use common::test_allocator;
+    use mem_allocs::{arena_allocator::ArenaAllocator, region::Region};
+
+    let parent = ArenaAllocator::new(16 * 1024);
+    let region = Region::new(&parent, 8 * 1024).unwrap();
+    test_allocator(&region).unwrap();
+}
@@ -0,0 +1,18 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "report_allocator")]
+fn report_allocator() {
+    use common::test_allocator;
+    use mem_allocs::report_allocator::{CountingReporter, ReportAllocator};
+    use std::alloc::Global;
+
+    let allocator = ReportAllocator::new(Global, CountingReporter::new());
+    test_allocator(&allocator).unwrap();
+    assert_eq!(
+        allocator.reporter().allocations(),
+        allocator.reporter().deallocations()
+    );
+}
@@ -0,0 +1,14 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "segmented_arena")]
+fn segmented_arena() {
+    use common::test_allocator;
+    use mem_allocs::segmented_arena::SegmentedArena;
+
+    let arena = SegmentedArena::new(&[("level", 16 * 1024), ("frame", 16 * 1024)]);
+    test_allocator(arena.segment("level").unwrap()).unwrap();
+    test_allocator(arena.segment("frame").unwrap()).unwrap();
+}
@@ -0,0 +1,12 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "segregated_allocator")]
+fn segregated_allocator() {
+    use common::test_allocator;
+    use mem_allocs::segregated_allocator::SegregatedAllocator;
+
+    test_allocator(SegregatedAllocator::new(8)).unwrap();
+}
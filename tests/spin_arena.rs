@@ -0,0 +1,12 @@
+#![cfg(feature = "spin_arena")]
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+fn spin_arena() {
+    use common::test_allocator;
+    use mem_allocs::spin_arena::SpinArenaAllocator;
+
+    test_allocator(SpinArenaAllocator::new(1024)).unwrap();
+}
@@ -0,0 +1,14 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "stack_allocator")]
+fn stack_allocator() {
+    use common::test_allocator;
+    use mem_allocs::stack_allocator::StackAllocator;
+
+    let allocator = StackAllocator::new(1024);
+    test_allocator(&allocator).unwrap();
+    assert_eq!(allocator.used(), 0);
+}
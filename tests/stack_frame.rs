@@ -0,0 +1,14 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "stack_frame")]
+fn stack_frame() {
+    use common::test_allocator;
+    use core::mem::MaybeUninit;
+    use mem_allocs::stack_frame::StackAllocatorFrame;
+
+    let mut buffer = [MaybeUninit::uninit(); 4096];
+    test_allocator(StackAllocatorFrame::new(&mut buffer)).unwrap();
+}
@@ -0,0 +1,13 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "static_arena")]
+fn static_arena() {
+    use common::test_allocator;
+    use mem_allocs::static_arena::StaticArena;
+
+    static ARENA: StaticArena<1024> = StaticArena::new();
+    test_allocator(&ARENA).unwrap();
+}
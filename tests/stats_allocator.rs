@@ -0,0 +1,16 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(all(feature = "stats_allocator", feature = "c_allocator"))]
+fn stats_allocator() {
+    use common::test_allocator;
+    use mem_allocs::{c_allocator::CAllocator, stats_allocator::StatsAllocator};
+
+    let allocator = StatsAllocator::new(CAllocator);
+    test_allocator(&allocator).unwrap();
+
+    let stats = allocator.stats();
+    assert_eq!(stats.allocations, stats.deallocations);
+}
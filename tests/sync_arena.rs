@@ -0,0 +1,12 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "sync_arena")]
+fn sync_arena() {
+    use common::test_allocator;
+    use mem_allocs::sync_arena::SyncArenaAllocator;
+
+    test_allocator(SyncArenaAllocator::new(1024)).unwrap();
+}
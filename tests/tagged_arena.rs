@@ -0,0 +1,14 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "tagged_arena")]
+fn tagged_arena() {
+    use common::test_allocator;
+    use mem_allocs::tagged_arena::{TaggedArenaAllocator, UNTAGGED};
+
+    let allocator = TaggedArenaAllocator::new(32 * 1024);
+    test_allocator(&allocator).unwrap();
+    assert!(allocator.usage_by_tag(UNTAGGED) > 0);
+}
@@ -0,0 +1,12 @@
+#![cfg(feature = "thread_local_arena")]
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+fn thread_local_arena() {
+    use common::test_allocator;
+    use mem_allocs::thread_local_arena;
+
+    test_allocator(thread_local_arena!(1024)).unwrap();
+}
@@ -0,0 +1,14 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(feature = "tlsf_allocator")]
+fn tlsf_allocator() {
+    use common::test_allocator;
+    use mem_allocs::tlsf_allocator::TlsfAllocator;
+
+    let allocator = TlsfAllocator::new(4096);
+    test_allocator(&allocator).unwrap();
+    assert_eq!(allocator.free_bytes(), allocator.capacity());
+}
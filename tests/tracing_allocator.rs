@@ -0,0 +1,17 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(all(feature = "tracing_allocator", feature = "c_allocator"))]
+fn tracing_allocator() {
+    use common::test_allocator;
+    use mem_allocs::{
+        c_allocator::CAllocator,
+        tracing_allocator::{AllocEvent, TracingAllocator},
+    };
+
+    fn ignore(_: AllocEvent) {}
+
+    test_allocator(TracingAllocator::new(CAllocator, ignore)).unwrap();
+}
@@ -0,0 +1,12 @@
+#![cfg(all(feature = "wasm_allocator", target_arch = "wasm32"))]
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+fn wasm_allocator() {
+    use common::test_allocator;
+    use mem_allocs::wasm_allocator::WasmAllocator;
+
+    test_allocator(&WasmAllocator).unwrap();
+}
@@ -0,0 +1,12 @@
+#![cfg(all(feature = "win_heap_allocator", target_os = "windows"))]
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+fn win_heap_allocator() {
+    use common::test_allocator;
+    use mem_allocs::win_heap_allocator::WinHeapAllocator;
+
+    test_allocator(WinHeapAllocator).unwrap();
+}
@@ -0,0 +1,12 @@
+#![feature(allocator_api, slice_ptr_get)]
+
+mod common;
+
+#[test]
+#[cfg(all(feature = "zeroizing_allocator", feature = "c_allocator"))]
+fn zeroizing_allocator() {
+    use common::test_allocator;
+    use mem_allocs::{c_allocator::CAllocator, zeroizing_allocator::ZeroizingAllocator};
+
+    test_allocator(ZeroizingAllocator::new(CAllocator)).unwrap();
+}